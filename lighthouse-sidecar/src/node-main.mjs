@@ -11,7 +11,9 @@
  * - Disables all browser caching for real network metrics
  * - Counts DOM nodes excluding SVG children
  *
- * Usage: node node-main.mjs <url> <chrome-path> [--html]
+ * Usage: node node-main.mjs <url> <chrome-path> [--html] [--proxy-server=<scheme://host:port>]
+ *
+ * Proxy credentials are read from ECOINDEX_PROXY_USERNAME / ECOINDEX_PROXY_PASSWORD.
  */
 
 import { startFlow } from 'lighthouse';
@@ -20,6 +22,12 @@ import { writeFileSync } from 'node:fs';
 import { tmpdir } from 'node:os';
 import { join } from 'node:path';
 
+/**
+ * Version of the JSON output format, checked by the Rust backend.
+ * Bump it on any breaking change to the success payload.
+ */
+const PROTOCOL_VERSION = 1;
+
 // ============================================================================
 // Global state for cleanup on exit
 // ============================================================================
@@ -115,6 +123,9 @@ function parseArgs() {
   const url = args[0];
   const chromePath = args[1];
   const includeHtml = args.includes('--html');
+  const proxyServer = args
+    .find((arg) => arg.startsWith('--proxy-server='))
+    ?.slice('--proxy-server='.length);
 
   try {
     new URL(url);
@@ -122,7 +133,7 @@ function parseArgs() {
     return null;
   }
 
-  return { url, chromePath, includeHtml };
+  return { url, chromePath, includeHtml, proxyServer };
 }
 
 /**
@@ -132,7 +143,8 @@ function printUsage() {
   const usage = {
     error: true,
     code: 'INVALID_ARGS',
-    message: 'Usage: node node-main.mjs <url> <chrome-path> [--html]',
+    message:
+      'Usage: node node-main.mjs <url> <chrome-path> [--html] [--proxy-server=<scheme://host:port>]',
     details: 'Example: node node-main.mjs https://example.com /path/to/chrome',
   };
   console.log(JSON.stringify(usage));
@@ -290,9 +302,9 @@ function extractNetworkStats(lhr) {
 
 /**
  * Extract detailed information for each HTTP request
- * Includes cache TTL from uses-long-cache-ttl audit
+ * Includes cache TTL from uses-long-cache-ttl audit and the captured initiator type
  */
-function extractRequestDetails(lhr) {
+function extractRequestDetails(lhr, initiators = new Map()) {
   const networkRequestsAudit = lhr.audits?.['network-requests'];
   if (!networkRequestsAudit?.details?.items) {
     return [];
@@ -351,6 +363,7 @@ function extractRequestDetails(lhr) {
         duration: Math.round((endTime - startTime) * 100) / 100,
         fromCache: transferSize === 0 && resourceSize > 0,
         cacheLifetimeMs,
+        initiator: initiators.get(url) || '',
       };
     });
 }
@@ -426,13 +439,13 @@ function extractPerformanceMetrics(lhr) {
   const perfCategory = lhr.categories?.['performance'];
 
   return {
-    performance: Math.round((perfCategory?.score || 0) * 100),
-    fcp: extractNumericValue(audits['first-contentful-paint'], 0),
-    lcp: extractNumericValue(audits['largest-contentful-paint'], 0),
-    tbt: extractNumericValue(audits['total-blocking-time'], 0),
-    cls: extractNumericValue(audits['cumulative-layout-shift'], 0),
-    si: extractNumericValue(audits['speed-index'], 0),
-    tti: extractNumericValue(audits['interactive'], 0),
+    performance: categoryScore(perfCategory),
+    fcp: extractNumericValue(audits['first-contentful-paint'], null),
+    lcp: extractNumericValue(audits['largest-contentful-paint'], null),
+    tbt: extractNumericValue(audits['total-blocking-time'], null),
+    cls: extractNumericValue(audits['cumulative-layout-shift'], null),
+    si: extractNumericValue(audits['speed-index'], null),
+    tti: extractNumericValue(audits['interactive'], null),
   };
 }
 
@@ -458,7 +471,7 @@ function extractAccessibilityMetrics(lhr) {
   }
 
   return {
-    accessibility: Math.round((a11yCategory?.score || 0) * 100),
+    accessibility: categoryScore(a11yCategory),
     issues: issues.slice(0, 10),
   };
 }
@@ -482,11 +495,29 @@ function extractOtherScores(lhr) {
   const seoCategory = lhr.categories?.['seo'];
 
   return {
-    bestPractices: Math.round((bpCategory?.score || 0) * 100),
-    seo: Math.round((seoCategory?.score || 0) * 100),
+    bestPractices: categoryScore(bpCategory),
+    seo: categoryScore(seoCategory),
   };
 }
 
+/**
+ * Category score out of 100, or null when the category errored
+ * (the Rust side then reports a partial result instead of a 0 score)
+ */
+function categoryScore(category) {
+  if (typeof category?.score !== 'number') return null;
+  return Math.round(category.score * 100);
+}
+
+/**
+ * Extract the load event time (ms since navigation start) from the metrics audit
+ * Null when the page never fired its load event or the audit errored
+ */
+function extractLoadEventMs(lhr) {
+  const observedLoad = lhr.audits?.metrics?.details?.items?.[0]?.observedLoad;
+  return typeof observedLoad === 'number' ? Math.round(observedLoad * 100) / 100 : null;
+}
+
 /**
  * Extract TTFB (Time To First Byte) from server-response-time audit
  * Falls back to calculating from first document request if audit not available
@@ -655,24 +686,55 @@ function extractNumericValue(audit, defaultValue) {
  * Run Lighthouse analysis using Flow API (matching EcoindexApp methodology)
  * Returns raw metrics - EcoIndex calculation is done in Rust
  */
-async function runAnalysis(url, chromePath, includeHtml = false) {
+async function runAnalysis(url, chromePath, includeHtml = false, proxyServer = undefined) {
   try {
     // Launch browser using puppeteer-core directly
     // Store in global variable for cleanup on signals
     activeBrowser = await puppeteer.launch({
       executablePath: chromePath,
       headless: 'new',
-      args: CHROME_FLAGS,
+      args: proxyServer ? [...CHROME_FLAGS, `--proxy-server=${proxyServer}`] : CHROME_FLAGS,
     });
 
     const page = await activeBrowser.newPage();
     await page.setViewport({ width: 1920, height: 1080 });
 
+    // Answer proxy auth challenges with the credentials passed by the app
+    const proxyUsername = process.env.ECOINDEX_PROXY_USERNAME;
+    if (proxyServer && proxyUsername) {
+      await page.authenticate({
+        username: proxyUsername,
+        password: process.env.ECOINDEX_PROXY_PASSWORD ?? '',
+      });
+    }
+
     // Disable cache via CDP for cold analysis (real network metrics)
     const cdpClient = await page.createCDPSession();
     await cdpClient.send('Network.enable');
     await cdpClient.send('Network.setCacheDisabled', { cacheDisabled: true });
 
+    // Record what triggered each request (parser, script, preload...), first one wins
+    const initiators = new Map();
+    cdpClient.on('Network.requestWillBeSent', (event) => {
+      if (!initiators.has(event.request.url)) {
+        initiators.set(event.request.url, event.initiator?.type || '');
+      }
+    });
+
+    // Record the protocol each response was actually served with, which can
+    // differ from the request's after a redirect or upgrade, and its headers
+    // (compression, CDN cache, header size analytics). Last one wins.
+    const responseProtocols = new Map();
+    const responseHeaders = new Map();
+    cdpClient.on('Network.responseReceived', (event) => {
+      if (event.response?.protocol) {
+        responseProtocols.set(event.response.url, event.response.protocol);
+      }
+      if (event.response?.headers) {
+        responseHeaders.set(event.response.url, event.response.headers);
+      }
+    });
+
     // COLD NAVIGATION - Direct analysis without cache
     const flow = await startFlow(page, {
       config: LIGHTHOUSE_CONFIG,
@@ -716,7 +778,7 @@ async function runAnalysis(url, chromePath, includeHtml = false) {
     const resourceBreakdown = extractResourceBreakdown(lhr);
 
     // Extract detailed request information
-    const requests = extractRequestDetails(lhr);
+    const requests = extractRequestDetails(lhr, initiators);
 
     // Extract cache analysis from uses-long-cache-ttl audit
     const cacheAnalysis = extractCacheAnalysis(lhr);
@@ -737,6 +799,7 @@ async function runAnalysis(url, chromePath, includeHtml = false) {
 
     // Build result with raw metrics (no EcoIndex calculation)
     const analysisResult = {
+      version: PROTOCOL_VERSION,
       url: lhr.finalDisplayedUrl || url,
       rawMetrics: {
         domElements: Math.round(domElements),
@@ -745,6 +808,9 @@ async function runAnalysis(url, chromePath, includeHtml = false) {
       },
       resourceBreakdown,
       requests,
+      responseProtocols: Object.fromEntries(responseProtocols),
+      responseHeaders: Object.fromEntries(responseHeaders),
+      loadEventMs: extractLoadEventMs(lhr),
       cacheAnalysis,
       lighthouse: {
         ...perfMetrics,
@@ -814,9 +880,9 @@ async function main() {
     process.exit(1);
   }
 
-  const { url, chromePath, includeHtml } = args;
+  const { url, chromePath, includeHtml, proxyServer } = args;
 
-  const result = await runAnalysis(url, chromePath, includeHtml);
+  const result = await runAnalysis(url, chromePath, includeHtml, proxyServer);
 
   // Always output JSON to stdout
   console.log(JSON.stringify(result));
@@ -0,0 +1,280 @@
+//! Versioned (cache-busted) URL detection.
+//!
+//! A versioned URL changes whenever its content does (`?v=3`, `app.3f9a1c2e.js`),
+//! so it can safely be cached for a long time.
+
+use super::cache_stats::MS_WEEK;
+use super::safe_percentage;
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+
+/// Query parameters commonly used for cache busting.
+const VERSION_PARAMS: [&str; 12] = [
+    "v",
+    "ver",
+    "version",
+    "t",
+    "ts",
+    "_",
+    "cb",
+    "cachebuster",
+    "hash",
+    "rev",
+    "build",
+    "bust",
+];
+
+/// Minimum length of a content hash in a filename or query value.
+const MIN_HASH_LEN: usize = 8;
+
+/// Maximum number of example URLs reported.
+const MAX_EXAMPLES: usize = 20;
+
+/// Versioned vs unversioned static resources, split by cache TTL.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheBustingAnalytics {
+    /// Static resources checked (scripts, stylesheets, images, fonts, media).
+    pub checked_count: u32,
+    /// Resources with a versioned URL.
+    pub versioned_count: u32,
+    /// Resources with a raw URL.
+    pub unversioned_count: u32,
+    /// Share of versioned resources (0-100).
+    pub versioned_percentage: f64,
+    /// Versioned resources cached for at least 7 days (ideal).
+    pub versioned_long_ttl: u32,
+    /// Versioned resources cached for less than 7 days (long TTL would be safe).
+    pub versioned_short_ttl: u32,
+    /// Unversioned resources cached for at least 7 days (updates may be stale).
+    pub unversioned_long_ttl: u32,
+    /// Unversioned resources cached for less than 7 days.
+    pub unversioned_short_ttl: u32,
+    /// Versioned URLs with a short TTL, largest first (at most 20).
+    pub missed_long_cache: Vec<String>,
+}
+
+impl CacheBustingAnalytics {
+    /// Compute cache-busting analytics from requests.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        let checked: Vec<&RequestDetail> = requests.iter().filter(|r| Self::is_static(r)).collect();
+
+        let mut counts = [[0u32; 2]; 2];
+        let mut missed: Vec<&RequestDetail> = Vec::new();
+        for request in &checked {
+            let versioned = is_versioned_url(&request.url);
+            let long_ttl = request.cache_lifetime_ms >= MS_WEEK;
+            counts[usize::from(versioned)][usize::from(long_ttl)] += 1;
+            if versioned && !long_ttl {
+                missed.push(request);
+            }
+        }
+        missed.sort_by(|a, b| {
+            b.transfer_size
+                .cmp(&a.transfer_size)
+                .then_with(|| a.url.cmp(&b.url))
+        });
+
+        let checked_count = checked.len() as u32;
+        let versioned_count = counts[1][0] + counts[1][1];
+        let versioned_percentage =
+            safe_percentage(f64::from(versioned_count), f64::from(checked_count));
+
+        Self {
+            checked_count,
+            versioned_count,
+            unversioned_count: checked_count - versioned_count,
+            versioned_percentage,
+            versioned_long_ttl: counts[1][1],
+            versioned_short_ttl: counts[1][0],
+            unversioned_long_ttl: counts[0][1],
+            unversioned_short_ttl: counts[0][0],
+            missed_long_cache: missed
+                .into_iter()
+                .take(MAX_EXAMPLES)
+                .map(|r| r.url.clone())
+                .collect(),
+        }
+    }
+
+    /// Combine the analytics of two pages, recomputing the versioned share from the totals.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let checked_count = self.checked_count + other.checked_count;
+        let versioned_count = self.versioned_count + other.versioned_count;
+        let mut missed_long_cache = self.missed_long_cache.clone();
+        for url in &other.missed_long_cache {
+            if missed_long_cache.len() < MAX_EXAMPLES && !missed_long_cache.contains(url) {
+                missed_long_cache.push(url.clone());
+            }
+        }
+
+        Self {
+            checked_count,
+            versioned_count,
+            unversioned_count: self.unversioned_count + other.unversioned_count,
+            versioned_percentage: safe_percentage(
+                f64::from(versioned_count),
+                f64::from(checked_count),
+            ),
+            versioned_long_ttl: self.versioned_long_ttl + other.versioned_long_ttl,
+            versioned_short_ttl: self.versioned_short_ttl + other.versioned_short_ttl,
+            unversioned_long_ttl: self.unversioned_long_ttl + other.unversioned_long_ttl,
+            unversioned_short_ttl: self.unversioned_short_ttl + other.unversioned_short_ttl,
+            missed_long_cache,
+        }
+    }
+
+    /// Static assets are the ones worth versioning (not documents or API calls).
+    fn is_static(request: &RequestDetail) -> bool {
+        matches!(
+            request.resource_type.as_str(),
+            "Script" | "Stylesheet" | "Image" | "Font" | "Media"
+        )
+    }
+}
+
+/// Whether `url` carries a version: cache-busting query parameter, content hash
+/// in the filename, or version segment in the path (`/v2/`, `@1.2.3`).
+#[must_use]
+pub fn is_versioned_url(url: &str) -> bool {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return false;
+    };
+
+    let query_versioned = parsed.query_pairs().any(|(name, value)| {
+        let name = name.to_ascii_lowercase();
+        (VERSION_PARAMS.contains(&name.as_str()) && !value.is_empty())
+            || (value.is_empty() && is_hash(&name))
+    });
+    if query_versioned {
+        return true;
+    }
+
+    let segments: Vec<&str> = parsed
+        .path_segments()
+        .map(Iterator::collect)
+        .unwrap_or_default();
+    let Some((filename, dirs)) = segments.split_last() else {
+        return false;
+    };
+
+    let filename_hash = filename.split(['.', '-', '_']).skip(1).any(is_hash);
+    let path_version = dirs.iter().any(|dir| is_version_segment(dir))
+        || filename.split_once('@').is_some_and(|(_, v)| is_semver(v))
+        || dirs
+            .iter()
+            .any(|dir| dir.split_once('@').is_some_and(|(_, v)| is_semver(v)));
+
+    filename_hash || path_version
+}
+
+/// Content hash: long alphanumeric token containing digits and letters.
+fn is_hash(token: &str) -> bool {
+    token.len() >= MIN_HASH_LEN
+        && token.chars().all(|c| c.is_ascii_alphanumeric())
+        && token.chars().any(|c| c.is_ascii_digit())
+        && token.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+/// Path segment such as `v2` or `v1.4.0`.
+fn is_version_segment(segment: &str) -> bool {
+    segment
+        .strip_prefix(['v', 'V'])
+        .is_some_and(|rest| !rest.is_empty() && is_semver(rest))
+}
+
+/// Dotted numeric version (`2`, `1.4`, `1.4.0`).
+fn is_semver(version: &str) -> bool {
+    !version.is_empty()
+        && version
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(url: &str, cache_lifetime_ms: u64) -> RequestDetail {
+        RequestDetail {
+            url: url.to_string(),
+            cache_lifetime_ms,
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_versioned_urls() {
+        for url in [
+            "https://example.com/app.js?v=3",
+            "https://example.com/style.css?ver=6.4.2",
+            "https://example.com/app.js?_=1700000000",
+            "https://example.com/app.js?3f9a1c2e7b",
+            "https://example.com/static/app.3f9a1c2e.js",
+            "https://example.com/main-5b7e9d21a3.css",
+            "https://example.com/chunk_a1b2c3d4e5.js",
+            "https://example.com/v2/lib.js",
+            "https://example.com/assets/v1.4.0/lib.js",
+            "https://cdn.jsdelivr.net/npm/vue@3.4.21/dist/vue.js",
+        ] {
+            assert!(is_versioned_url(url), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_unversioned_urls() {
+        for url in [
+            "https://example.com/app.js",
+            "https://example.com/css/style.css",
+            "https://example.com/app.js?lang=fr",
+            "https://example.com/images/logo.png",
+            "https://example.com/jquery.min.js",
+            "https://example.com/vendor/video.js",
+            "https://example.com/app.js?v=",
+            "not a url",
+        ] {
+            assert!(!is_versioned_url(url), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_grouped_by_ttl() {
+        let requests = vec![
+            make_request("https://example.com/app.3f9a1c2e.js", MS_WEEK * 52),
+            make_request("https://example.com/lib.js?v=2", 3_600_000),
+            make_request("https://example.com/raw.js", MS_WEEK * 4),
+            make_request("https://example.com/other.js", 0),
+            make_request("https://example.com/more.js", 0),
+        ];
+        let result = CacheBustingAnalytics::compute(&requests);
+
+        assert_eq!(result.checked_count, 5);
+        assert_eq!(result.versioned_count, 2);
+        assert_eq!(result.unversioned_count, 3);
+        assert!((result.versioned_percentage - 40.0).abs() < 1e-9);
+        assert_eq!(result.versioned_long_ttl, 1);
+        assert_eq!(result.versioned_short_ttl, 1);
+        assert_eq!(result.unversioned_long_ttl, 1);
+        assert_eq!(result.unversioned_short_ttl, 2);
+        assert_eq!(
+            result.missed_long_cache,
+            vec!["https://example.com/lib.js?v=2"]
+        );
+    }
+
+    #[test]
+    fn test_documents_and_api_calls_ignored() {
+        let mut document = make_request("https://example.com/?v=1", 0);
+        document.resource_type = "Document".to_string();
+        let mut api = make_request("https://example.com/api?t=123", 0);
+        api.resource_type = "Fetch".to_string();
+
+        let result = CacheBustingAnalytics::compute(&[document, api]);
+        assert_eq!(result.checked_count, 0);
+        assert!(result.versioned_percentage.abs() < f64::EPSILON);
+    }
+}
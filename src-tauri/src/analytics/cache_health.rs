@@ -0,0 +1,207 @@
+//! Per-domain cache health rollup.
+//!
+//! Combines the domain grouping of [`DomainAnalytics`](super::DomainAnalytics)
+//! with the cache TTL threshold of [`CacheAnalytics`](super::CacheAnalytics) to
+//! show which origin has the worst caching policy.
+
+use super::cache_stats::MS_WEEK;
+use super::registrable::registrable_domain;
+use super::{safe_percentage, DomainGrouping, Locale};
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Cache health of a single domain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DomainCacheHealth {
+    /// Domain name.
+    pub domain: String,
+    /// Number of resources served by this domain.
+    pub resource_count: u32,
+    /// Resources cached for at least 7 days.
+    pub well_cached_count: u32,
+    /// Total transfer size in bytes.
+    pub total_bytes: u64,
+    /// Transfer size of resources cached for at least 7 days.
+    pub well_cached_bytes: u64,
+    /// Percentage of resources (by count) with an adequate TTL.
+    pub count_percentage: f64,
+    /// Percentage of bytes with an adequate TTL.
+    pub bytes_percentage: f64,
+}
+
+/// Cache health per domain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheHealthAnalytics {
+    /// Domains sorted worst-first (by bytes, then count percentage).
+    pub domains: Vec<DomainCacheHealth>,
+}
+
+impl CacheHealthAnalytics {
+    /// Compute cache health per host.
+    #[must_use]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        Self::compute_grouped(requests, Locale::default(), DomainGrouping::default())
+    }
+
+    /// Compute cache health, grouping requests like the domain statistics.
+    #[must_use]
+    pub fn compute_grouped(
+        requests: &[RequestDetail],
+        locale: Locale,
+        grouping: DomainGrouping,
+    ) -> Self {
+        // domain -> (count, well cached count, bytes, well cached bytes)
+        let mut by_domain: HashMap<String, (u32, u32, u64, u64)> = HashMap::new();
+        for req in requests {
+            let key = match grouping {
+                DomainGrouping::Host => req.domain.clone(),
+                DomainGrouping::RegistrableDomain => registrable_domain(&req.domain),
+            };
+            let well_cached = req.cache_lifetime_ms >= MS_WEEK;
+            let entry = by_domain.entry(key).or_default();
+            entry.0 += 1;
+            entry.2 += req.transfer_size;
+            if well_cached {
+                entry.1 += 1;
+                entry.3 += req.transfer_size;
+            }
+        }
+
+        let domains = by_domain
+            .into_iter()
+            .map(|(domain, (count, cached_count, bytes, cached_bytes))| {
+                let domain = if domain.is_empty() {
+                    locale.unknown_domain_label().to_string()
+                } else {
+                    domain
+                };
+                DomainCacheHealth::new(domain, count, cached_count, bytes, cached_bytes)
+            })
+            .collect();
+
+        Self::sorted(domains)
+    }
+
+    /// Combine the cache health of two pages, summing domains with the same label.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut domains: Vec<DomainCacheHealth> = Vec::new();
+        for health in self.domains.iter().chain(&other.domains) {
+            if let Some(existing) = domains.iter_mut().find(|d| d.domain == health.domain) {
+                *existing = DomainCacheHealth::new(
+                    health.domain.clone(),
+                    existing.resource_count + health.resource_count,
+                    existing.well_cached_count + health.well_cached_count,
+                    existing.total_bytes + health.total_bytes,
+                    existing.well_cached_bytes + health.well_cached_bytes,
+                );
+            } else {
+                domains.push(health.clone());
+            }
+        }
+        Self::sorted(domains)
+    }
+
+    fn sorted(mut domains: Vec<DomainCacheHealth>) -> Self {
+        domains.sort_by(|a, b| {
+            a.bytes_percentage
+                .total_cmp(&b.bytes_percentage)
+                .then_with(|| a.count_percentage.total_cmp(&b.count_percentage))
+                .then_with(|| b.total_bytes.cmp(&a.total_bytes))
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+
+        Self { domains }
+    }
+}
+
+impl DomainCacheHealth {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(domain: String, count: u32, cached_count: u32, bytes: u64, cached_bytes: u64) -> Self {
+        Self {
+            domain,
+            resource_count: count,
+            well_cached_count: cached_count,
+            total_bytes: bytes,
+            well_cached_bytes: cached_bytes,
+            count_percentage: safe_percentage(f64::from(cached_count), f64::from(count)),
+            // Zero-byte domains are judged on their count alone
+            bytes_percentage: if bytes > 0 {
+                safe_percentage(cached_bytes as f64, bytes as f64)
+            } else {
+                safe_percentage(f64::from(cached_count), f64::from(count))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(domain: &str, transfer_size: u64, cache_lifetime_ms: u64) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://{domain}/test"),
+            domain: domain.to_string(),
+            mime_type: "image/png".to_string(),
+            resource_type: "Image".to_string(),
+            transfer_size,
+            resource_size: transfer_size,
+            priority: "Low".to_string(),
+            cache_lifetime_ms,
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_domains_sorted_worst_first() {
+        let year = 31_536_000_000;
+        let requests = vec![
+            make_request("cdn.example.com", 100_000, year),
+            make_request("cdn.example.com", 100_000, year),
+            make_request("cdn.example.com", 50_000, 0),
+            make_request("api.example.com", 10_000, 0),
+            make_request("api.example.com", 30_000, 3_600_000),
+            make_request("api.example.com", 10_000, year),
+        ];
+        let result = CacheHealthAnalytics::compute(&requests);
+
+        assert_eq!(result.domains.len(), 2);
+        let worst = &result.domains[0];
+        assert_eq!(worst.domain, "api.example.com");
+        assert_eq!(worst.resource_count, 3);
+        assert_eq!(worst.well_cached_count, 1);
+        assert_eq!(worst.well_cached_bytes, 10_000);
+        assert!((worst.bytes_percentage - 20.0).abs() < 1e-9);
+
+        let best = &result.domains[1];
+        assert_eq!(best.domain, "cdn.example.com");
+        assert!((best.count_percentage - 200.0 / 3.0).abs() < 1e-9);
+        assert!((best.bytes_percentage - 80.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_requests() {
+        assert!(CacheHealthAnalytics::compute(&[]).domains.is_empty());
+    }
+
+    #[test]
+    fn test_registrable_grouping() {
+        let requests = vec![
+            make_request("a.cdn.com", 1000, 0),
+            make_request("b.cdn.com", 1000, 31_536_000_000),
+        ];
+        let result = CacheHealthAnalytics::compute_grouped(
+            &requests,
+            Locale::En,
+            DomainGrouping::RegistrableDomain,
+        );
+        assert_eq!(result.domains.len(), 1);
+        assert_eq!(result.domains[0].domain, "cdn.com");
+        assert!((result.domains[0].bytes_percentage - 50.0).abs() < 1e-9);
+    }
+}
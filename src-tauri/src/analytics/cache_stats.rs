@@ -1,16 +1,87 @@
 //! Cache analysis computation.
 
+use super::filename::url_filename;
+use super::{safe_percentage, Locale};
 use crate::sidecar::RequestDetail;
 use serde::{Deserialize, Serialize};
 
 const MS_HOUR: u64 = 3_600_000;
 const MS_DAY: u64 = 86_400_000;
-const MS_WEEK: u64 = 604_800_000;
+pub(super) const MS_WEEK: u64 = 604_800_000;
+
+/// Cache TTL bucket of a [`CacheGroup`], from no cache to well cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CacheBucket {
+    /// No cache lifetime.
+    NoCache,
+    /// Less than an hour.
+    Hour,
+    /// Less than a day.
+    Day,
+    /// Less than a week.
+    Week,
+    /// A week or more.
+    LongLived,
+}
+
+impl CacheBucket {
+    /// Every bucket, from no cache to well cached.
+    pub const ALL: [Self; 5] = [
+        Self::NoCache,
+        Self::Hour,
+        Self::Day,
+        Self::Week,
+        Self::LongLived,
+    ];
+
+    /// Bucket of a cache lifetime in milliseconds.
+    #[must_use]
+    pub const fn of(cache_lifetime_ms: u64) -> Self {
+        match cache_lifetime_ms {
+            0 => Self::NoCache,
+            ms if ms < MS_HOUR => Self::Hour,
+            ms if ms < MS_DAY => Self::Day,
+            ms if ms < MS_WEEK => Self::Week,
+            _ => Self::LongLived,
+        }
+    }
+
+    /// Color for visualization.
+    #[must_use]
+    pub const fn color(self) -> &'static str {
+        match self {
+            Self::NoCache => "#ef4444",   // red
+            Self::Hour => "#f59e0b",      // amber
+            Self::Day => "#eab308",       // yellow
+            Self::Week => "#84cc16",      // lime
+            Self::LongLived => "#10b981", // green
+        }
+    }
+
+    /// Group label in the given locale.
+    #[must_use]
+    pub const fn label(self, locale: Locale) -> &'static str {
+        match (self, locale) {
+            (Self::NoCache, _) => locale.none_label(),
+            (Self::Hour, Locale::Fr) => "< 1 heure",
+            (Self::Hour, Locale::En) => "< 1 hour",
+            (Self::Day, Locale::Fr) => "< 1 jour",
+            (Self::Day, Locale::En) => "< 1 day",
+            (Self::Week, Locale::Fr) => "< 7 jours",
+            (Self::Week, Locale::En) => "< 7 days",
+            (Self::LongLived, Locale::Fr) => ">= 7 jours",
+            (Self::LongLived, Locale::En) => ">= 7 days",
+        }
+    }
+}
 
 /// Cache TTL group.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", from = "StoredCacheGroup")]
 pub struct CacheGroup {
+    /// TTL bucket of the group.
+    pub bucket: CacheBucket,
     /// Group label (e.g., "< 1 heure").
     pub label: String,
     /// Number of resources in this group.
@@ -21,6 +92,37 @@ pub struct CacheGroup {
     pub color: String,
 }
 
+/// [`CacheGroup`] as stored in history, possibly without its bucket.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredCacheGroup {
+    #[serde(default)]
+    bucket: Option<CacheBucket>,
+    label: String,
+    count: u32,
+    percentage: f64,
+    color: String,
+}
+
+impl From<StoredCacheGroup> for CacheGroup {
+    fn from(stored: StoredCacheGroup) -> Self {
+        // Groups saved before buckets were added are recognized by their color
+        let bucket = stored.bucket.unwrap_or_else(|| {
+            CacheBucket::ALL
+                .into_iter()
+                .find(|b| b.color() == stored.color)
+                .unwrap_or(CacheBucket::NoCache)
+        });
+        Self {
+            bucket,
+            label: stored.label,
+            count: stored.count,
+            percentage: stored.percentage,
+            color: stored.color,
+        }
+    }
+}
+
 /// Resource with problematic cache TTL.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -44,7 +146,7 @@ pub struct ProblematicResource {
 }
 
 /// Aggregated cache analytics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CacheAnalytics {
     /// Cache TTL groups.
@@ -60,8 +162,14 @@ pub struct CacheAnalytics {
 impl CacheAnalytics {
     /// Compute cache analytics from requests.
     #[must_use]
-    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
     pub fn compute(requests: &[RequestDetail]) -> Self {
+        Self::compute_with_locale(requests, Locale::default())
+    }
+
+    /// Compute cache analytics from requests with labels in the given locale.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn compute_with_locale(requests: &[RequestDetail], locale: Locale) -> Self {
         let total = requests.len() as u32;
         if total == 0 {
             return Self {
@@ -72,72 +180,24 @@ impl CacheAnalytics {
             };
         }
 
-        // Count resources per TTL bucket
-        let mut none = 0u32;
-        let mut hour = 0u32;
-        let mut day = 0u32;
-        let mut week = 0u32;
-        let mut good = 0u32;
-
-        for req in requests {
-            let ms = req.cache_lifetime_ms;
-            if ms == 0 {
-                none += 1;
-            } else if ms < MS_HOUR {
-                hour += 1;
-            } else if ms < MS_DAY {
-                day += 1;
-            } else if ms < MS_WEEK {
-                week += 1;
-            } else {
-                good += 1;
-            }
-        }
-
+        // One group per non-empty TTL bucket
         let total_f64 = f64::from(total);
-
-        // Build groups (only non-empty)
-        let mut groups = vec![];
-        if none > 0 {
-            groups.push(CacheGroup {
-                label: "Aucun".to_string(),
-                count: none,
-                percentage: (f64::from(none) / total_f64) * 100.0,
-                color: "#ef4444".to_string(), // red
-            });
-        }
-        if hour > 0 {
-            groups.push(CacheGroup {
-                label: "< 1 heure".to_string(),
-                count: hour,
-                percentage: (f64::from(hour) / total_f64) * 100.0,
-                color: "#f59e0b".to_string(), // amber
-            });
-        }
-        if day > 0 {
-            groups.push(CacheGroup {
-                label: "< 1 jour".to_string(),
-                count: day,
-                percentage: (f64::from(day) / total_f64) * 100.0,
-                color: "#eab308".to_string(), // yellow
-            });
-        }
-        if week > 0 {
-            groups.push(CacheGroup {
-                label: "< 7 jours".to_string(),
-                count: week,
-                percentage: (f64::from(week) / total_f64) * 100.0,
-                color: "#84cc16".to_string(), // lime
-            });
-        }
-        if good > 0 {
-            groups.push(CacheGroup {
-                label: ">= 7 jours".to_string(),
-                count: good,
-                percentage: (f64::from(good) / total_f64) * 100.0,
-                color: "#10b981".to_string(), // green
-            });
-        }
+        let groups = CacheBucket::ALL
+            .into_iter()
+            .filter_map(|bucket| {
+                let count = requests
+                    .iter()
+                    .filter(|r| CacheBucket::of(r.cache_lifetime_ms) == bucket)
+                    .count() as u32;
+                (count > 0).then(|| CacheGroup {
+                    bucket,
+                    label: bucket.label(locale).to_string(),
+                    count,
+                    percentage: safe_percentage(f64::from(count), total_f64),
+                    color: bucket.color().to_string(),
+                })
+            })
+            .collect();
 
         // Problematic resources: cache < 7 days, sorted by impact (no-cache + large size first)
         let mut problematic: Vec<_> = requests
@@ -163,9 +223,9 @@ impl CacheAnalytics {
                     domain: r.domain.clone(),
                     filename: Self::extract_filename(&r.url),
                     cache_lifetime_ms: ms,
-                    cache_ttl_label: Self::format_ttl(ms),
+                    cache_ttl_label: Self::format_ttl(ms, locale),
                     badge_class: Self::get_badge_class(ms),
-                    badge_text: Self::get_badge_text(ms),
+                    badge_text: Self::get_badge_text(ms, locale),
                     resource_size: r.resource_size,
                 }
             })
@@ -181,19 +241,58 @@ impl CacheAnalytics {
         }
     }
 
+    /// Combine the cache analytics of two pages.
+    ///
+    /// Groups are matched by bucket, counts summed and percentages recomputed;
+    /// problematic resources are re-sorted by impact.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut groups: Vec<CacheGroup> = Vec::new();
+        for group in self.groups.iter().chain(&other.groups) {
+            if let Some(existing) = groups.iter_mut().find(|g| g.bucket == group.bucket) {
+                existing.count += group.count;
+            } else {
+                groups.push(group.clone());
+            }
+        }
+        groups.sort_by_key(|g| g.bucket);
+
+        let total = self.total_resources + other.total_resources;
+        for group in &mut groups {
+            group.percentage = safe_percentage(f64::from(group.count), f64::from(total));
+        }
+
+        let mut problematic_resources: Vec<ProblematicResource> = self
+            .problematic_resources
+            .iter()
+            .chain(&other.problematic_resources)
+            .cloned()
+            .collect();
+        problematic_resources.sort_by(|a, b| {
+            let impact_a = Self::compute_impact(a.cache_lifetime_ms, a.resource_size);
+            let impact_b = Self::compute_impact(b.cache_lifetime_ms, b.resource_size);
+            impact_b
+                .partial_cmp(&impact_a)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        Self {
+            groups,
+            problematic_resources,
+            total_resources: total,
+            problematic_count: self.problematic_count + other.problematic_count,
+        }
+    }
+
     /// Extract filename from URL.
     fn extract_filename(url: &str) -> String {
-        url::Url::parse(url)
-            .ok()
-            .and_then(|u| u.path_segments()?.next_back().map(str::to_string))
-            .filter(|s| !s.is_empty())
-            .unwrap_or_else(|| url.to_string())
+        url_filename(url).unwrap_or_else(|| url.to_string())
     }
 
     /// Format TTL in human-readable form.
-    fn format_ttl(ms: u64) -> String {
+    fn format_ttl(ms: u64, locale: Locale) -> String {
         if ms == 0 {
-            return "Aucun".to_string();
+            return locale.none_label().to_string();
         }
         let seconds = ms / 1000;
         if seconds < 60 {
@@ -203,7 +302,7 @@ impl CacheAnalytics {
         } else if seconds < 86400 {
             format!("{}h", seconds / 3600)
         } else {
-            format!("{}j", seconds / 86400)
+            format!("{}{}", seconds / 86400, locale.day_suffix())
         }
     }
 
@@ -219,15 +318,15 @@ impl CacheAnalytics {
     }
 
     /// Get badge text based on TTL.
-    fn get_badge_text(ms: u64) -> String {
+    fn get_badge_text(ms: u64, locale: Locale) -> String {
         if ms == 0 {
             "!".to_string()
         } else if ms < MS_HOUR {
             "<1h".to_string()
         } else if ms < MS_DAY {
-            "<1j".to_string()
+            format!("<1{}", locale.day_suffix())
         } else {
-            "<7j".to_string()
+            format!("<7{}", locale.day_suffix())
         }
     }
 
@@ -247,23 +346,14 @@ impl CacheAnalytics {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analytics::test_support::request;
 
     fn make_request(cache_lifetime_ms: u64) -> RequestDetail {
         RequestDetail {
             url: "https://example.com/test.js".to_string(),
-            domain: "example.com".to_string(),
-            protocol: "h2".to_string(),
-            status_code: 200,
-            mime_type: "application/javascript".to_string(),
-            resource_type: "Script".to_string(),
-            transfer_size: 1000,
             resource_size: 2000,
-            priority: "High".to_string(),
-            start_time: 0.0,
-            end_time: 100.0,
-            duration: 100.0,
-            from_cache: false,
             cache_lifetime_ms,
+            ..request()
         }
     }
 
@@ -291,13 +381,63 @@ mod tests {
         assert_eq!(result.problematic_count, 4); // All except >= 7 days
     }
 
+    #[test]
+    fn test_merge_matches_groups_by_bucket() {
+        let page = |bucket: CacheBucket, count: u32| CacheAnalytics {
+            groups: vec![CacheGroup {
+                bucket,
+                label: bucket.label(Locale::En).to_string(),
+                count,
+                percentage: 100.0,
+                // Same display color for every group
+                color: "#64748b".to_string(),
+            }],
+            total_resources: count,
+            ..CacheAnalytics::default()
+        };
+        let merged = page(CacheBucket::LongLived, 3)
+            .merge(&page(CacheBucket::NoCache, 1))
+            .merge(&page(CacheBucket::LongLived, 4));
+
+        let groups: Vec<(CacheBucket, u32)> =
+            merged.groups.iter().map(|g| (g.bucket, g.count)).collect();
+        assert_eq!(
+            groups,
+            vec![(CacheBucket::NoCache, 1), (CacheBucket::LongLived, 7)]
+        );
+        assert!((merged.groups[1].percentage - 87.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stored_group_without_bucket() {
+        let group = serde_json::from_value::<CacheGroup>(serde_json::json!({
+            "label": "< 1 jour", "count": 2, "percentage": 40.0, "color": "#eab308"
+        }));
+        assert!(group.is_ok_and(|g| g.bucket == CacheBucket::Day));
+
+        let json = serde_json::to_value(CacheAnalytics::compute(&[make_request(0)]));
+        assert!(json.is_ok_and(|j| j["groups"][0]["bucket"] == "noCache"));
+    }
+
     #[test]
     fn test_format_ttl() {
-        assert_eq!(CacheAnalytics::format_ttl(0), "Aucun");
-        assert_eq!(CacheAnalytics::format_ttl(30_000), "30s");
-        assert_eq!(CacheAnalytics::format_ttl(120_000), "2min");
-        assert_eq!(CacheAnalytics::format_ttl(7_200_000), "2h");
-        assert_eq!(CacheAnalytics::format_ttl(172_800_000), "2j");
+        assert_eq!(CacheAnalytics::format_ttl(0, Locale::Fr), "Aucun");
+        assert_eq!(CacheAnalytics::format_ttl(30_000, Locale::Fr), "30s");
+        assert_eq!(CacheAnalytics::format_ttl(120_000, Locale::Fr), "2min");
+        assert_eq!(CacheAnalytics::format_ttl(7_200_000, Locale::Fr), "2h");
+        assert_eq!(CacheAnalytics::format_ttl(172_800_000, Locale::Fr), "2j");
+    }
+
+    #[test]
+    fn test_english_labels() {
+        let requests = vec![make_request(0), make_request(MS_DAY - 1)];
+        let result = CacheAnalytics::compute_with_locale(&requests, Locale::En);
+
+        assert_eq!(result.groups[0].label, "None");
+        assert_eq!(result.groups[1].label, "< 1 day");
+        assert_eq!(result.problematic_resources[0].cache_ttl_label, "None");
+        assert_eq!(result.problematic_resources[1].badge_text, "<1d");
+        assert_eq!(CacheAnalytics::format_ttl(172_800_000, Locale::En), "2d");
     }
 
     #[test]
@@ -310,5 +450,9 @@ mod tests {
             CacheAnalytics::extract_filename("https://cdn.example.com/styles/app.css?v=123"),
             "app.css"
         );
+        assert_eq!(
+            CacheAnalytics::extract_filename("https://example.com/js/my%20file.js"),
+            "my file.js"
+        );
     }
 }
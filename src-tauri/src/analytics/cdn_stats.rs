@@ -0,0 +1,240 @@
+//! CDN edge-cache effectiveness from the `Age` response header.
+//!
+//! A shared cache (CDN or reverse proxy) sets `Age` to the number of seconds
+//! the response spent in its cache: `Age > 0` means the edge answered, while
+//! `Age: 0` or no `Age` means the request went back to the origin.
+
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Response headers set by shared caches. A domain sending any of them is
+/// served through a CDN.
+const CDN_HEADERS: [&str; 7] = [
+    "age",
+    "via",
+    "x-cache",
+    "cf-cache-status",
+    "x-served-by",
+    "x-amz-cf-id",
+    "akamai-cache-status",
+];
+
+/// Edge-cache hits of a single CDN domain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CdnDomain {
+    /// Domain name.
+    pub domain: String,
+    /// Responses served from the edge cache (`Age > 0`).
+    pub edge_hits: u32,
+    /// Responses fetched from the origin (`Age: 0` or absent).
+    pub origin_hits: u32,
+    /// Share of responses served from the edge cache (0-1).
+    pub edge_hit_ratio: f64,
+}
+
+/// Edge-cache effectiveness per CDN domain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CdnAnalytics {
+    /// CDN domains sorted by edge-hit ratio, worst first.
+    pub domains: Vec<CdnDomain>,
+    /// Edge-cache hits across all CDN domains.
+    pub edge_hits: u32,
+    /// Origin hits across all CDN domains.
+    pub origin_hits: u32,
+    /// Share of CDN responses served from the edge cache (0-1); `None` when
+    /// no CDN response was seen, e.g. response headers were not captured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edge_hit_ratio: Option<f64>,
+}
+
+impl CdnAnalytics {
+    /// Count edge and origin hits per CDN domain.
+    ///
+    /// Only requests with captured response headers are considered; domains
+    /// without any shared-cache header are not behind a CDN and are left out.
+    #[must_use]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        // domain -> (served through a CDN, edge hits, origin hits)
+        let mut by_domain: HashMap<&str, (bool, u32, u32)> = HashMap::new();
+        for req in requests.iter().filter(|r| !r.response_headers.is_empty()) {
+            let entry = by_domain.entry(req.domain.as_str()).or_default();
+            entry.0 |= CDN_HEADERS
+                .iter()
+                .any(|h| req.response_headers.contains_key(*h));
+            if age_seconds(req) > 0 {
+                entry.1 += 1;
+            } else {
+                entry.2 += 1;
+            }
+        }
+
+        let domains = by_domain
+            .into_iter()
+            .filter(|(_, (cdn, _, _))| *cdn)
+            .map(|(domain, (_, edge, origin))| CdnDomain::new(domain.to_string(), edge, origin))
+            .collect();
+        Self::sorted(domains)
+    }
+
+    /// Combine the analytics of two pages, summing domains with the same name.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut domains: Vec<CdnDomain> = Vec::new();
+        for cdn in self.domains.iter().chain(&other.domains) {
+            if let Some(existing) = domains.iter_mut().find(|d| d.domain == cdn.domain) {
+                *existing = CdnDomain::new(
+                    cdn.domain.clone(),
+                    existing.edge_hits + cdn.edge_hits,
+                    existing.origin_hits + cdn.origin_hits,
+                );
+            } else {
+                domains.push(cdn.clone());
+            }
+        }
+        Self::sorted(domains)
+    }
+
+    fn sorted(mut domains: Vec<CdnDomain>) -> Self {
+        domains.sort_by(|a, b| {
+            a.edge_hit_ratio
+                .total_cmp(&b.edge_hit_ratio)
+                .then_with(|| b.origin_hits.cmp(&a.origin_hits))
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+        let edge_hits = domains.iter().map(|d| d.edge_hits).sum();
+        let origin_hits = domains.iter().map(|d| d.origin_hits).sum();
+        Self {
+            domains,
+            edge_hits,
+            origin_hits,
+            edge_hit_ratio: (edge_hits + origin_hits > 0).then(|| ratio(edge_hits, origin_hits)),
+        }
+    }
+}
+
+impl CdnDomain {
+    fn new(domain: String, edge_hits: u32, origin_hits: u32) -> Self {
+        Self {
+            domain,
+            edge_hits,
+            origin_hits,
+            edge_hit_ratio: ratio(edge_hits, origin_hits),
+        }
+    }
+}
+
+/// Value of the `Age` header in seconds; missing or malformed counts as 0.
+fn age_seconds(request: &RequestDetail) -> u64 {
+    request
+        .response_headers
+        .get("age")
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+fn ratio(edge_hits: u32, origin_hits: u32) -> f64 {
+    let total = edge_hits + origin_hits;
+    if total > 0 {
+        f64::from(edge_hits) / f64::from(total)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(domain: &str, headers: &[(&str, &str)]) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://{domain}/asset"),
+            domain: domain.to_string(),
+            mime_type: "image/png".to_string(),
+            resource_type: "Image".to_string(),
+            priority: "Low".to_string(),
+            response_headers: headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_edge_hit_ratio_from_age() {
+        let requests = vec![
+            make_request("cdn.example.com", &[("age", "3600")]),
+            make_request("cdn.example.com", &[("age", "12")]),
+            make_request("cdn.example.com", &[("age", "0")]),
+            // No Age header: fetched from the origin
+            make_request("cdn.example.com", &[("x-cache", "MISS")]),
+        ];
+        let result = CdnAnalytics::compute(&requests);
+
+        assert_eq!(result.domains.len(), 1);
+        let cdn = &result.domains[0];
+        assert_eq!(cdn.domain, "cdn.example.com");
+        assert_eq!(cdn.edge_hits, 2);
+        assert_eq!(cdn.origin_hits, 2);
+        assert!((cdn.edge_hit_ratio - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_non_cdn_domains_and_uncaptured_headers_ignored() {
+        let requests = vec![
+            make_request("example.com", &[("content-type", "text/html")]),
+            make_request("static.example.com", &[]),
+            make_request(
+                "edge.example.net",
+                &[("age", "bogus"), ("via", "1.1 varnish")],
+            ),
+        ];
+        let result = CdnAnalytics::compute(&requests);
+
+        assert_eq!(result.domains.len(), 1);
+        assert_eq!(result.domains[0].domain, "edge.example.net");
+        assert_eq!(result.domains[0].origin_hits, 1);
+        assert_eq!(result.edge_hit_ratio, Some(0.0));
+    }
+
+    #[test]
+    fn test_no_data_without_captured_headers() {
+        let result = CdnAnalytics::compute(&[make_request("cdn.example.com", &[])]);
+
+        assert!(result.domains.is_empty());
+        assert_eq!(result.edge_hit_ratio, None);
+        assert_eq!(result.merge(&CdnAnalytics::default()).edge_hit_ratio, None);
+    }
+
+    #[test]
+    fn test_sorted_worst_first_and_merge() {
+        let page_a = CdnAnalytics::compute(&[
+            make_request("good.cdn.com", &[("age", "10")]),
+            make_request("bad.cdn.com", &[("age", "0")]),
+        ]);
+        assert_eq!(page_a.domains[0].domain, "bad.cdn.com");
+        assert!(page_a
+            .edge_hit_ratio
+            .is_some_and(|r| (r - 0.5).abs() < 1e-9));
+
+        let page_b = CdnAnalytics::compute(&[
+            make_request("bad.cdn.com", &[("age", "5")]),
+            make_request("bad.cdn.com", &[("age", "7")]),
+            make_request("bad.cdn.com", &[("age", "9")]),
+        ]);
+        let merged = page_a.merge(&page_b);
+
+        assert_eq!(merged.domains.len(), 2);
+        assert_eq!(merged.domains[0].domain, "bad.cdn.com");
+        assert_eq!(merged.domains[0].edge_hits, 3);
+        assert_eq!(merged.domains[0].origin_hits, 1);
+        assert_eq!(merged.edge_hits, 4);
+        assert!(merged
+            .edge_hit_ratio
+            .is_some_and(|r| (r - 0.8).abs() < 1e-9));
+    }
+}
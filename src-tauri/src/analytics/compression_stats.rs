@@ -0,0 +1,216 @@
+//! Text compression detection from captured response headers.
+
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+
+/// Resources smaller than this are not worth compressing.
+const MIN_SIZE_BYTES: u64 = 1024;
+
+/// Typical share of bytes saved by gzip/brotli on text resources.
+const TYPICAL_SAVINGS_RATIO: f64 = 0.7;
+
+/// Text resource served without compression.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UncompressedResource {
+    /// Full URL of the resource.
+    pub url: String,
+    /// Domain/hostname.
+    pub domain: String,
+    /// MIME type of the response.
+    pub mime_type: String,
+    /// Resource size in bytes.
+    pub resource_size: u64,
+    /// Estimated bytes saved by enabling compression.
+    pub estimated_savings: u64,
+}
+
+/// Aggregated text compression analytics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextCompressionAnalytics {
+    /// Uncompressed text resources, sorted by estimated savings.
+    pub uncompressed_resources: Vec<UncompressedResource>,
+    /// Total estimated savings in bytes.
+    pub potential_savings: u64,
+    /// Number of text resources checked (headers captured, above minimum size).
+    pub checked_count: u32,
+    /// Number of uncompressed text resources.
+    pub uncompressed_count: u32,
+}
+
+impl TextCompressionAnalytics {
+    /// Compute text compression analytics from requests.
+    ///
+    /// Only requests with captured response headers are considered, since a
+    /// missing `content-encoding` is otherwise indistinguishable from no capture.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        let candidates: Vec<&RequestDetail> = requests
+            .iter()
+            .filter(|r| !r.response_headers.is_empty())
+            .filter(|r| r.resource_size >= MIN_SIZE_BYTES)
+            .filter(|r| Self::is_text_mime(&r.mime_type))
+            .collect();
+
+        let mut uncompressed_resources: Vec<UncompressedResource> = candidates
+            .iter()
+            .filter(|r| !Self::is_compressed(r))
+            .map(|r| UncompressedResource {
+                url: r.url.clone(),
+                domain: r.domain.clone(),
+                mime_type: r.mime_type.clone(),
+                resource_size: r.resource_size,
+                estimated_savings: (r.resource_size as f64 * TYPICAL_SAVINGS_RATIO) as u64,
+            })
+            .collect();
+
+        uncompressed_resources.sort_by_key(|r| std::cmp::Reverse(r.estimated_savings));
+
+        let potential_savings = uncompressed_resources
+            .iter()
+            .map(|r| r.estimated_savings)
+            .sum();
+
+        Self {
+            uncompressed_count: uncompressed_resources.len() as u32,
+            uncompressed_resources,
+            potential_savings,
+            checked_count: candidates.len() as u32,
+        }
+    }
+
+    /// Combine the analytics of two pages, keeping resources sorted by savings.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut uncompressed_resources: Vec<UncompressedResource> = self
+            .uncompressed_resources
+            .iter()
+            .chain(&other.uncompressed_resources)
+            .cloned()
+            .collect();
+        uncompressed_resources.sort_by_key(|r| std::cmp::Reverse(r.estimated_savings));
+
+        Self {
+            uncompressed_resources,
+            potential_savings: self.potential_savings + other.potential_savings,
+            checked_count: self.checked_count + other.checked_count,
+            uncompressed_count: self.uncompressed_count + other.uncompressed_count,
+        }
+    }
+
+    /// Check whether a MIME type is text-based (HTML, CSS, JS, JSON, SVG, XML).
+    fn is_text_mime(mime_type: &str) -> bool {
+        let mime = mime_type
+            .split(';')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .to_lowercase();
+        mime.starts_with("text/")
+            || mime.ends_with("+json")
+            || mime.ends_with("+xml")
+            || matches!(
+                mime.as_str(),
+                "application/javascript"
+                    | "application/x-javascript"
+                    | "application/ecmascript"
+                    | "application/json"
+                    | "application/xml"
+                    | "image/svg+xml"
+            )
+    }
+
+    /// Check whether the response was served with a content encoding.
+    fn is_compressed(request: &RequestDetail) -> bool {
+        request
+            .response_headers
+            .get("content-encoding")
+            .map(|v| v.trim().to_lowercase())
+            .is_some_and(|v| !v.is_empty() && v != "identity")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+    use std::collections::HashMap;
+
+    fn make_request(mime_type: &str, size: u64, encoding: Option<&str>) -> RequestDetail {
+        let mut response_headers = HashMap::new();
+        response_headers.insert("content-type".to_string(), mime_type.to_string());
+        if let Some(encoding) = encoding {
+            response_headers.insert("content-encoding".to_string(), encoding.to_string());
+        }
+        RequestDetail {
+            url: "https://example.com/resource".to_string(),
+            mime_type: mime_type.to_string(),
+            transfer_size: size,
+            resource_size: size,
+            response_headers,
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_empty_requests() {
+        let result = TextCompressionAnalytics::compute(&[]);
+        assert_eq!(result.uncompressed_count, 0);
+        assert_eq!(result.potential_savings, 0);
+    }
+
+    #[test]
+    fn test_compressed_text_not_flagged() {
+        let requests = vec![
+            make_request("application/javascript", 10_000, Some("br")),
+            make_request("text/css", 10_000, Some("gzip")),
+        ];
+        let result = TextCompressionAnalytics::compute(&requests);
+
+        assert_eq!(result.checked_count, 2);
+        assert_eq!(result.uncompressed_count, 0);
+    }
+
+    #[test]
+    fn test_uncompressed_text_flagged() {
+        let requests = vec![
+            make_request("text/html; charset=utf-8", 10_000, None),
+            make_request("image/svg+xml", 20_000, Some("identity")),
+        ];
+        let result = TextCompressionAnalytics::compute(&requests);
+
+        assert_eq!(result.uncompressed_count, 2);
+        // Sorted by savings: SVG first
+        assert_eq!(result.uncompressed_resources[0].mime_type, "image/svg+xml");
+        assert_eq!(result.uncompressed_resources[0].estimated_savings, 14_000);
+        assert_eq!(result.potential_savings, 21_000);
+    }
+
+    #[test]
+    fn test_binary_and_small_resources_ignored() {
+        let requests = vec![
+            make_request("image/png", 50_000, None),
+            make_request("font/woff2", 30_000, None),
+            make_request("application/json", 500, None),
+        ];
+        let result = TextCompressionAnalytics::compute(&requests);
+
+        assert_eq!(result.checked_count, 0);
+        assert_eq!(result.uncompressed_count, 0);
+    }
+
+    #[test]
+    fn test_requests_without_captured_headers_ignored() {
+        let mut request = make_request("application/javascript", 10_000, None);
+        request.response_headers.clear();
+        let result = TextCompressionAnalytics::compute(&[request]);
+
+        assert_eq!(result.checked_count, 0);
+    }
+}
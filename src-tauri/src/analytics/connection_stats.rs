@@ -0,0 +1,218 @@
+//! Connection reuse (handshake) estimation.
+
+use super::ProtocolAnalytics;
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Parallel connections Chrome opens per HTTP/1.1 origin.
+const HTTP1_MAX_CONNECTIONS: u32 = 6;
+
+/// Above this number of origins, consolidating them is recommended.
+const MANY_ORIGINS: u32 = 10;
+
+/// Estimated connections for a single origin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginConnections {
+    /// Domain/hostname.
+    pub domain: String,
+    /// Whether requests were multiplexed (HTTP/2 or HTTP/3).
+    pub multiplexed: bool,
+    /// Number of network requests (cache hits excluded).
+    pub request_count: u32,
+    /// Estimated TCP/TLS handshakes.
+    pub estimated_handshakes: u32,
+}
+
+/// Suggested action to reduce handshakes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionRecommendation {
+    /// Connections are already well reused.
+    #[default]
+    None,
+    /// Serve HTTP/1.1 origins over HTTP/2 or HTTP/3.
+    EnableMultiplexing,
+    /// Reduce the number of third-party origins.
+    ReduceOrigins,
+    /// Both enable multiplexing and reduce origins.
+    EnableMultiplexingAndReduceOrigins,
+}
+
+/// Aggregated connection analytics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionAnalytics {
+    /// Per-origin estimates, sorted by handshakes.
+    pub origins: Vec<OriginConnections>,
+    /// Number of distinct origins contacted.
+    pub origin_count: u32,
+    /// Origins served over HTTP/1.1.
+    pub http1_origin_count: u32,
+    /// Estimated TCP/TLS handshakes for the whole page.
+    pub estimated_handshakes: u32,
+    /// Suggested action.
+    pub recommendation: ConnectionRecommendation,
+}
+
+impl ConnectionAnalytics {
+    /// Estimate connection handshakes from requests.
+    ///
+    /// HTTP/2 and HTTP/3 origins reuse a single connection; HTTP/1.1 origins
+    /// open one connection per parallel request, up to six.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        // domain -> (requests, multiplexed)
+        let mut by_origin: HashMap<&str, (u32, bool)> = HashMap::new();
+        for req in requests.iter().filter(|r| !r.from_cache) {
+            let protocol = ProtocolAnalytics::normalize_protocol(&req.protocol);
+            let multiplexed = protocol == "HTTP/2" || protocol == "HTTP/3";
+            let entry = by_origin.entry(req.domain.as_str()).or_insert((0, false));
+            entry.0 += 1;
+            entry.1 |= multiplexed;
+        }
+
+        let origins: Vec<OriginConnections> = by_origin
+            .into_iter()
+            .map(|(domain, (request_count, multiplexed))| OriginConnections {
+                domain: domain.to_string(),
+                multiplexed,
+                request_count,
+                estimated_handshakes: if multiplexed {
+                    1
+                } else {
+                    request_count.min(HTTP1_MAX_CONNECTIONS)
+                },
+            })
+            .collect();
+
+        Self::from_origins(origins)
+    }
+
+    /// Combine the estimates of two pages.
+    ///
+    /// Each page load opens its own connections, so handshakes per origin add up;
+    /// the recommendation is re-derived from the merged origins.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut origins: Vec<OriginConnections> = Vec::new();
+        for origin in self.origins.iter().chain(&other.origins) {
+            if let Some(existing) = origins.iter_mut().find(|o| o.domain == origin.domain) {
+                existing.multiplexed |= origin.multiplexed;
+                existing.request_count += origin.request_count;
+                existing.estimated_handshakes += origin.estimated_handshakes;
+            } else {
+                origins.push(origin.clone());
+            }
+        }
+        Self::from_origins(origins)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_origins(mut origins: Vec<OriginConnections>) -> Self {
+        origins.sort_by(|a, b| {
+            b.estimated_handshakes
+                .cmp(&a.estimated_handshakes)
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+
+        let origin_count = origins.len() as u32;
+        let http1_origin_count = origins.iter().filter(|o| !o.multiplexed).count() as u32;
+        let estimated_handshakes = origins.iter().map(|o| o.estimated_handshakes).sum();
+
+        let repeated_handshakes = origins.iter().any(|o| o.estimated_handshakes > 1);
+        let recommendation = match (repeated_handshakes, origin_count > MANY_ORIGINS) {
+            (false, false) => ConnectionRecommendation::None,
+            (true, false) => ConnectionRecommendation::EnableMultiplexing,
+            (false, true) => ConnectionRecommendation::ReduceOrigins,
+            (true, true) => ConnectionRecommendation::EnableMultiplexingAndReduceOrigins,
+        };
+
+        Self {
+            origins,
+            origin_count,
+            http1_origin_count,
+            estimated_handshakes,
+            recommendation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(domain: &str, protocol: &str) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://{domain}/test"),
+            domain: domain.to_string(),
+            protocol: protocol.to_string(),
+            mime_type: "text/html".to_string(),
+            resource_type: "Document".to_string(),
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_empty_requests() {
+        let result = ConnectionAnalytics::compute(&[]);
+        assert_eq!(result.origin_count, 0);
+        assert_eq!(result.estimated_handshakes, 0);
+        assert_eq!(result.recommendation, ConnectionRecommendation::None);
+    }
+
+    #[test]
+    fn test_single_origin_http2_reuses_connection() {
+        let requests: Vec<_> = (0..30).map(|_| make_request("example.com", "h2")).collect();
+        let result = ConnectionAnalytics::compute(&requests);
+
+        assert_eq!(result.origin_count, 1);
+        assert_eq!(result.http1_origin_count, 0);
+        assert_eq!(result.estimated_handshakes, 1);
+        assert_eq!(result.recommendation, ConnectionRecommendation::None);
+    }
+
+    #[test]
+    fn test_many_http1_origins_need_many_handshakes() {
+        let mut requests = Vec::new();
+        for i in 0..12 {
+            for _ in 0..8 {
+                requests.push(make_request(&format!("cdn{i}.example.com"), "http/1.1"));
+            }
+        }
+        let result = ConnectionAnalytics::compute(&requests);
+
+        assert_eq!(result.origin_count, 12);
+        assert_eq!(result.http1_origin_count, 12);
+        // Capped at 6 parallel connections per origin
+        assert_eq!(result.estimated_handshakes, 72);
+        assert_eq!(result.origins[0].estimated_handshakes, 6);
+        assert_eq!(
+            result.recommendation,
+            ConnectionRecommendation::EnableMultiplexingAndReduceOrigins
+        );
+    }
+
+    #[test]
+    fn test_cached_requests_ignored() {
+        let mut cached = make_request("old.example.com", "http/1.1");
+        cached.from_cache = true;
+        let requests = vec![
+            make_request("example.com", "h3"),
+            make_request("static.example.com", "HTTP/1.1"),
+            make_request("static.example.com", "HTTP/1.1"),
+            cached,
+        ];
+        let result = ConnectionAnalytics::compute(&requests);
+
+        assert_eq!(result.origin_count, 2);
+        assert_eq!(result.estimated_handshakes, 3);
+        assert_eq!(
+            result.recommendation,
+            ConnectionRecommendation::EnableMultiplexing
+        );
+    }
+}
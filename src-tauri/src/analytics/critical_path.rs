@@ -0,0 +1,238 @@
+//! Critical path timing heuristic.
+//!
+//! Approximates first-paint readiness without Lighthouse: the main HTML
+//! document plus the render-critical CSS/JS discovered while it is parsed.
+
+use super::PriorityLevel;
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+
+/// Critical resources must start within this delay after the document finished (ms).
+const DISCOVERY_WINDOW_MS: f64 = 500.0;
+
+/// Render-critical resource on the critical path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalResource {
+    /// Full URL of the resource.
+    pub url: String,
+    /// Resource type (Stylesheet or Script).
+    pub resource_type: String,
+    /// Start time in milliseconds.
+    pub start_time: f64,
+    /// End time in milliseconds.
+    pub end_time: f64,
+}
+
+/// Time until the main document and its render-critical resources are loaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPathTiming {
+    /// URL of the main HTML document (after redirects).
+    pub document_url: String,
+    /// Whether the document URL differs from the requested page URL.
+    pub redirected: bool,
+    /// Time at which the main document finished loading (ms).
+    pub document_end_time: f64,
+    /// First render-critical CSS/JS requested by the document.
+    pub first_critical_resource: Option<String>,
+    /// Render-critical resources, in start order.
+    pub critical_resources: Vec<CriticalResource>,
+    /// Time at which the whole critical path is loaded (ms).
+    pub critical_path_ms: f64,
+}
+
+impl CriticalPathTiming {
+    /// Compute critical path timing from requests.
+    ///
+    /// The document is the `Document` request matching `page_url`, following
+    /// redirects to the next successful document. Without a match (or without
+    /// `page_url`), the first successful document is used.
+    #[must_use]
+    pub fn compute(requests: &[RequestDetail], page_url: Option<&str>) -> Option<Self> {
+        let document = Self::find_document(requests, page_url)?;
+
+        let mut critical_resources: Vec<CriticalResource> = requests
+            .iter()
+            .filter(|r| Self::is_render_critical(r))
+            .filter(|r| {
+                r.start_time >= document.start_time
+                    && r.start_time <= document.end_time + DISCOVERY_WINDOW_MS
+            })
+            .map(|r| CriticalResource {
+                url: r.url.clone(),
+                resource_type: r.resource_type.clone(),
+                start_time: r.start_time,
+                end_time: r.end_time,
+            })
+            .collect();
+        critical_resources.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+        let critical_path_ms = critical_resources
+            .iter()
+            .map(|r| r.end_time)
+            .fold(document.end_time, f64::max);
+
+        Some(Self {
+            document_url: document.url.clone(),
+            redirected: page_url
+                .is_some_and(|url| normalize_url(url) != normalize_url(&document.url)),
+            document_end_time: document.end_time,
+            first_critical_resource: critical_resources.first().map(|r| r.url.clone()),
+            critical_resources,
+            critical_path_ms,
+        })
+    }
+
+    fn find_document<'a>(
+        requests: &'a [RequestDetail],
+        page_url: Option<&str>,
+    ) -> Option<&'a RequestDetail> {
+        let mut documents: Vec<&RequestDetail> = requests
+            .iter()
+            .filter(|r| r.resource_type.eq_ignore_ascii_case("document"))
+            .collect();
+        documents.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+        let matched = page_url.and_then(|url| {
+            let target = normalize_url(url);
+            documents
+                .iter()
+                .position(|d| normalize_url(&d.url) == target)
+        });
+
+        // Follow redirects: first successful document from the matched one
+        documents[matched.unwrap_or(0)..]
+            .iter()
+            .find(|r| (200..300).contains(&r.status_code))
+            .copied()
+    }
+
+    /// Stylesheets and high-priority (parser-blocking) scripts.
+    fn is_render_critical(request: &RequestDetail) -> bool {
+        let resource_type = request.resource_type.to_lowercase();
+        match resource_type.as_str() {
+            "stylesheet" => true,
+            "script" => matches!(
+                PriorityLevel::parse(&request.priority),
+                Some(PriorityLevel::VeryHigh | PriorityLevel::High)
+            ),
+            _ => false,
+        }
+    }
+}
+
+/// Normalize a URL for comparison (no fragment, canonical root path).
+fn normalize_url(url: &str) -> String {
+    url::Url::parse(url).map_or_else(
+        |_| url.trim_end_matches('/').to_string(),
+        |mut parsed| {
+            parsed.set_fragment(None);
+            parsed.to_string()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(
+        url: &str,
+        resource_type: &str,
+        status_code: u16,
+        start_time: f64,
+        end_time: f64,
+    ) -> RequestDetail {
+        RequestDetail {
+            url: url.to_string(),
+            status_code,
+            mime_type: "text/html".to_string(),
+            resource_type: resource_type.to_string(),
+            priority: "VeryHigh".to_string(),
+            start_time,
+            end_time,
+            duration: end_time - start_time,
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_no_document() {
+        assert!(CriticalPathTiming::compute(&[], Some("https://example.com")).is_none());
+    }
+
+    #[test]
+    fn test_document_and_critical_css() {
+        let mut async_script =
+            make_request("https://example.com/async.js", "Script", 200, 320.0, 900.0);
+        async_script.priority = "Low".to_string();
+        let requests = vec![
+            make_request("https://example.com/", "Document", 200, 0.0, 300.0),
+            make_request(
+                "https://example.com/main.css",
+                "Stylesheet",
+                200,
+                250.0,
+                600.0,
+            ),
+            make_request("https://example.com/app.js", "Script", 200, 260.0, 700.0),
+            async_script,
+            make_request("https://example.com/hero.jpg", "Image", 200, 400.0, 1500.0),
+            // Discovered long after the document (lazy CSS)
+            make_request(
+                "https://example.com/late.css",
+                "Stylesheet",
+                200,
+                3000.0,
+                3200.0,
+            ),
+        ];
+        let timing = CriticalPathTiming::compute(&requests, Some("https://example.com"));
+        assert!(timing.is_some());
+        let Some(timing) = timing else { return };
+
+        assert_eq!(timing.document_url, "https://example.com/");
+        assert!(!timing.redirected);
+        assert!((timing.document_end_time - 300.0).abs() < f64::EPSILON);
+        assert_eq!(
+            timing.first_critical_resource.as_deref(),
+            Some("https://example.com/main.css")
+        );
+        assert_eq!(timing.critical_resources.len(), 2);
+        assert!((timing.critical_path_ms - 700.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_redirect_to_final_document() {
+        let requests = vec![
+            make_request("http://example.com/", "Document", 301, 0.0, 50.0),
+            make_request("https://www.example.com/home", "Document", 200, 60.0, 400.0),
+            make_request(
+                "https://www.example.com/style.css",
+                "Stylesheet",
+                200,
+                380.0,
+                500.0,
+            ),
+        ];
+        let timing = CriticalPathTiming::compute(&requests, Some("http://example.com"));
+        assert!(timing.is_some());
+        let Some(timing) = timing else { return };
+
+        assert_eq!(timing.document_url, "https://www.example.com/home");
+        assert!(timing.redirected);
+        assert!((timing.critical_path_ms - 500.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_falls_back_to_first_document_without_url() {
+        let requests = vec![
+            make_request("https://example.com/frame", "Document", 200, 500.0, 800.0),
+            make_request("https://example.com/", "Document", 200, 0.0, 200.0),
+        ];
+        let timing = CriticalPathTiming::compute(&requests, None);
+        assert!(timing.is_some_and(|t| t.document_url == "https://example.com/" && !t.redirected));
+    }
+}
@@ -1,15 +1,19 @@
 //! Domain statistics computation.
 
+use super::registrable::{registrable_domain, split_domain, DomainParts};
+use super::{safe_percentage, Locale};
 use crate::sidecar::RequestDetail;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Statistics for a single domain.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", from = "StoredDomainStat")]
 pub struct DomainStat {
     /// Domain name.
     pub domain: String,
+    /// `domain` split for display (subdomain dimmed, registrable domain highlighted).
+    pub parts: DomainParts,
     /// Number of requests to this domain.
     pub request_count: u32,
     /// Total transfer size in bytes.
@@ -20,8 +24,46 @@ pub struct DomainStat {
     pub color: String,
 }
 
+/// `DomainStat` as stored in the history, where older records have no `parts`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredDomainStat {
+    domain: String,
+    #[serde(default)]
+    parts: Option<DomainParts>,
+    request_count: u32,
+    total_transfer_size: u64,
+    percentage: f64,
+    color: String,
+}
+
+impl From<StoredDomainStat> for DomainStat {
+    fn from(stored: StoredDomainStat) -> Self {
+        Self {
+            parts: stored.parts.unwrap_or_else(|| split_domain(&stored.domain)),
+            domain: stored.domain,
+            request_count: stored.request_count,
+            total_transfer_size: stored.total_transfer_size,
+            percentage: stored.percentage,
+            color: stored.color,
+        }
+    }
+}
+
+/// How requests are grouped into domains.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DomainGrouping {
+    /// Group by exact host (`a.cdn.com` and `b.cdn.com` are separate).
+    #[default]
+    Host,
+    /// Group by registrable domain / eTLD+1 (`a.cdn.com` and `b.cdn.com` → `cdn.com`),
+    /// per the Public Suffix List: `a.user.github.io` groups under `user.github.io`.
+    RegistrableDomain,
+}
+
 /// Aggregated domain analytics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DomainAnalytics {
     /// Statistics per domain, sorted by request count.
@@ -43,11 +85,41 @@ const COLORS: [&str; 8] = [
     "#84cc16", // lime
 ];
 
+impl DomainStat {
+    /// Stat for `domain`, colored by its rank in the sorted list.
+    fn new(domain: String, request_count: u32, size: u64, total: u32, rank: usize) -> Self {
+        Self {
+            parts: split_domain(&domain),
+            domain,
+            request_count,
+            total_transfer_size: size,
+            percentage: safe_percentage(f64::from(request_count), f64::from(total)),
+            color: (*COLORS.get(rank % COLORS.len()).unwrap_or(&"#6b7280")).to_string(),
+        }
+    }
+}
+
 impl DomainAnalytics {
     /// Compute domain analytics from requests.
     #[must_use]
-    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
     pub fn compute(requests: &[RequestDetail]) -> Self {
+        Self::compute_with_locale(requests, Locale::default())
+    }
+
+    /// Compute domain analytics from requests with labels in the given locale.
+    #[must_use]
+    pub fn compute_with_locale(requests: &[RequestDetail], locale: Locale) -> Self {
+        Self::compute_grouped(requests, locale, DomainGrouping::default())
+    }
+
+    /// Compute domain analytics, grouping requests with the given strategy.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn compute_grouped(
+        requests: &[RequestDetail],
+        locale: Locale,
+        grouping: DomainGrouping,
+    ) -> Self {
         if requests.is_empty() {
             return Self {
                 domains: vec![],
@@ -59,7 +131,11 @@ impl DomainAnalytics {
         let mut stats_map: HashMap<String, (u32, u64)> = HashMap::new();
 
         for req in requests {
-            let entry = stats_map.entry(req.domain.clone()).or_insert((0, 0));
+            let key = match grouping {
+                DomainGrouping::Host => req.domain.clone(),
+                DomainGrouping::RegistrableDomain => registrable_domain(&req.domain),
+            };
+            let entry = stats_map.entry(key).or_insert((0, 0));
             entry.0 += 1;
             entry.1 += req.transfer_size;
         }
@@ -68,25 +144,19 @@ impl DomainAnalytics {
         let total_size: u64 = stats_map.values().map(|(_, size)| size).sum();
 
         let mut sorted: Vec<_> = stats_map.into_iter().collect();
-        sorted.sort_by(|a, b| b.1 .0.cmp(&a.1 .0)); // Sort by request count descending
+        // Request count descending, then domain name for a reproducible order
+        sorted.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then_with(|| a.0.cmp(&b.0)));
 
         let domains = sorted
             .into_iter()
             .enumerate()
-            .map(|(i, (domain, (count, size)))| DomainStat {
-                domain: if domain.is_empty() {
-                    "(inconnu)".to_string()
+            .map(|(i, (domain, (count, size)))| {
+                let domain = if domain.is_empty() {
+                    locale.unknown_domain_label().to_string()
                 } else {
                     domain
-                },
-                request_count: count,
-                total_transfer_size: size,
-                percentage: if total > 0 {
-                    (f64::from(count) / f64::from(total)) * 100.0
-                } else {
-                    0.0
-                },
-                color: (*COLORS.get(i % COLORS.len()).unwrap_or(&"#6b7280")).to_string(),
+                };
+                DomainStat::new(domain, count, size, total, i)
             })
             .collect();
 
@@ -96,28 +166,57 @@ impl DomainAnalytics {
             total_size,
         }
     }
+
+    /// Combine the statistics of two pages.
+    ///
+    /// Domains with the same label are summed; percentages and colors are
+    /// recomputed from the merged totals.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut merged: Vec<(String, u32, u64)> = Vec::new();
+        for stat in self.domains.iter().chain(&other.domains) {
+            if let Some(entry) = merged.iter_mut().find(|(d, _, _)| *d == stat.domain) {
+                entry.1 += stat.request_count;
+                entry.2 += stat.total_transfer_size;
+            } else {
+                merged.push((
+                    stat.domain.clone(),
+                    stat.request_count,
+                    stat.total_transfer_size,
+                ));
+            }
+        }
+        merged.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let total = self.total_requests + other.total_requests;
+        let domains = merged
+            .into_iter()
+            .enumerate()
+            .map(|(i, (domain, count, size))| DomainStat::new(domain, count, size, total, i))
+            .collect();
+
+        Self {
+            domains,
+            total_requests: total,
+            total_size: self.total_size + other.total_size,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analytics::test_support::request;
 
     fn make_request(domain: &str, transfer_size: u64) -> RequestDetail {
         RequestDetail {
             url: format!("https://{domain}/test"),
             domain: domain.to_string(),
-            protocol: "h2".to_string(),
-            status_code: 200,
             mime_type: "text/html".to_string(),
             resource_type: "Document".to_string(),
             transfer_size,
             resource_size: transfer_size,
-            priority: "High".to_string(),
-            start_time: 0.0,
-            end_time: 100.0,
-            duration: 100.0,
-            from_cache: false,
-            cache_lifetime_ms: 0,
+            ..request()
         }
     }
 
@@ -165,4 +264,91 @@ mod tests {
         assert_eq!(result.domains[2].domain, "few.com");
         assert_eq!(result.domains[2].request_count, 1);
     }
+
+    #[test]
+    fn test_tied_domains_sorted_by_name() {
+        let requests = vec![
+            make_request("c.com", 100),
+            make_request("a.com", 100),
+            make_request("top.com", 100),
+            make_request("b.com", 100),
+            make_request("top.com", 100),
+        ];
+        let mut reversed = requests.clone();
+        reversed.reverse();
+
+        for result in [
+            DomainAnalytics::compute(&requests),
+            DomainAnalytics::compute(&reversed),
+        ] {
+            let names: Vec<&str> = result.domains.iter().map(|d| d.domain.as_str()).collect();
+            assert_eq!(names, vec!["top.com", "a.com", "b.com", "c.com"]);
+        }
+    }
+
+    #[test]
+    fn test_unknown_domain_english_label() {
+        let requests = vec![make_request("", 100)];
+
+        let fr = DomainAnalytics::compute(&requests);
+        assert_eq!(fr.domains[0].domain, "(inconnu)");
+
+        let en = DomainAnalytics::compute_with_locale(&requests, Locale::En);
+        assert_eq!(en.domains[0].domain, "(unknown)");
+    }
+
+    #[test]
+    fn test_host_grouping_is_default() {
+        let requests = vec![
+            make_request("a.cdn.com", 100),
+            make_request("b.cdn.com", 200),
+        ];
+        let result = DomainAnalytics::compute(&requests);
+        assert_eq!(result.domains.len(), 2);
+    }
+
+    #[test]
+    fn test_registrable_domain_grouping_collapses_subdomains() {
+        let requests = vec![
+            make_request("a.cdn.com", 100),
+            make_request("b.cdn.com", 200),
+            make_request("cdn.com", 300),
+            make_request("www.example.co.uk", 50),
+            make_request("img.example.co.uk", 50),
+        ];
+        let result = DomainAnalytics::compute_grouped(
+            &requests,
+            Locale::default(),
+            DomainGrouping::RegistrableDomain,
+        );
+
+        assert_eq!(result.domains.len(), 2);
+        assert_eq!(result.domains[0].domain, "cdn.com");
+        assert_eq!(result.domains[0].request_count, 3);
+        assert_eq!(result.domains[0].total_transfer_size, 600);
+        assert_eq!(result.domains[1].domain, "example.co.uk");
+        assert_eq!(result.domains[1].request_count, 2);
+        assert_eq!(result.total_requests, 5);
+    }
+
+    #[test]
+    fn test_domain_parts_for_display() {
+        let requests = vec![
+            make_request("cdn.example.com", 100),
+            make_request("localhost", 100),
+        ];
+        let result = DomainAnalytics::compute(&requests);
+
+        let cdn = result
+            .domains
+            .iter()
+            .find(|d| d.domain == "cdn.example.com");
+        assert_eq!(cdn.map(|d| d.parts.subdomain.as_str()), Some("cdn."));
+        assert_eq!(
+            cdn.map(|d| d.parts.registrable.as_str()),
+            Some("example.com")
+        );
+        let local = result.domains.iter().find(|d| d.domain == "localhost");
+        assert_eq!(local.map(|d| d.parts.subdomain.as_str()), Some(""));
+    }
 }
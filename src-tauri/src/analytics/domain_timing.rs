@@ -0,0 +1,226 @@
+//! Per-domain request timing rollup.
+//!
+//! Complements the bytes-focused [`DomainAnalytics`](super::DomainAnalytics)
+//! with a time dimension: which origin the page spends the most time waiting on.
+
+use super::registrable::registrable_domain;
+use super::{DomainGrouping, Locale};
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Cached requests faster than this were not fetched at all and are not timed (ms).
+const NEAR_ZERO_MS: f64 = 1.0;
+
+/// Request timing of a single domain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DomainTiming {
+    /// Domain name.
+    pub domain: String,
+    /// Number of requests to this domain.
+    pub request_count: u32,
+    /// Requests counted in the durations (near-instant cache hits excluded).
+    pub timed_count: u32,
+    /// Sum of the request durations (ms).
+    pub total_duration_ms: f64,
+    /// Average duration of the timed requests (ms).
+    pub average_duration_ms: f64,
+}
+
+/// Request timing per domain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DomainTimingAnalytics {
+    /// Domains sorted slowest first (by total duration).
+    pub domains: Vec<DomainTiming>,
+}
+
+impl DomainTimingAnalytics {
+    /// Compute request timing per host.
+    #[must_use]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        Self::compute_grouped(requests, Locale::default(), DomainGrouping::default())
+    }
+
+    /// Compute request timing, grouping requests like the domain statistics.
+    ///
+    /// Cached requests with a near-zero duration never hit the network and
+    /// would drag the average down; they are counted but not timed. Negative
+    /// or non-finite durations are not timed either.
+    #[must_use]
+    pub fn compute_grouped(
+        requests: &[RequestDetail],
+        locale: Locale,
+        grouping: DomainGrouping,
+    ) -> Self {
+        // domain -> (count, timed count, total duration)
+        let mut by_domain: HashMap<String, (u32, u32, f64)> = HashMap::new();
+        for req in requests {
+            let key = match grouping {
+                DomainGrouping::Host => req.domain.clone(),
+                DomainGrouping::RegistrableDomain => registrable_domain(&req.domain),
+            };
+            let entry = by_domain.entry(key).or_default();
+            entry.0 += 1;
+            let instant_cache_hit = req.from_cache && req.duration < NEAR_ZERO_MS;
+            if req.duration.is_finite() && req.duration >= 0.0 && !instant_cache_hit {
+                entry.1 += 1;
+                entry.2 += req.duration;
+            }
+        }
+
+        let domains = by_domain
+            .into_iter()
+            .map(|(domain, (count, timed_count, total))| {
+                let domain = if domain.is_empty() {
+                    locale.unknown_domain_label().to_string()
+                } else {
+                    domain
+                };
+                DomainTiming::new(domain, count, timed_count, total)
+            })
+            .collect();
+
+        Self::sorted(domains)
+    }
+
+    /// Combine the timing of two pages, summing domains with the same label.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut domains: Vec<DomainTiming> = Vec::new();
+        for timing in self.domains.iter().chain(&other.domains) {
+            if let Some(existing) = domains.iter_mut().find(|d| d.domain == timing.domain) {
+                *existing = DomainTiming::new(
+                    timing.domain.clone(),
+                    existing.request_count + timing.request_count,
+                    existing.timed_count + timing.timed_count,
+                    existing.total_duration_ms + timing.total_duration_ms,
+                );
+            } else {
+                domains.push(timing.clone());
+            }
+        }
+        Self::sorted(domains)
+    }
+
+    fn sorted(mut domains: Vec<DomainTiming>) -> Self {
+        domains.sort_by(|a, b| {
+            b.total_duration_ms
+                .total_cmp(&a.total_duration_ms)
+                .then_with(|| b.average_duration_ms.total_cmp(&a.average_duration_ms))
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+
+        Self { domains }
+    }
+}
+
+impl DomainTiming {
+    fn new(domain: String, request_count: u32, timed_count: u32, total_duration_ms: f64) -> Self {
+        Self {
+            domain,
+            request_count,
+            timed_count,
+            total_duration_ms,
+            average_duration_ms: if timed_count > 0 {
+                total_duration_ms / f64::from(timed_count)
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(domain: &str, duration: f64, from_cache: bool) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://{domain}/{duration}"),
+            domain: domain.to_string(),
+            mime_type: "image/png".to_string(),
+            resource_type: "Image".to_string(),
+            priority: "Low".to_string(),
+            end_time: duration,
+            duration,
+            from_cache,
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_slowest_domain_first() {
+        let requests = vec![
+            // Many fast requests
+            make_request("cdn.example.com", 20.0, false),
+            make_request("cdn.example.com", 30.0, false),
+            make_request("cdn.example.com", 10.0, false),
+            // One slow API call
+            make_request("api.example.com", 900.0, false),
+            make_request("fonts.example.net", 200.0, false),
+            make_request("fonts.example.net", 300.0, false),
+        ];
+        let result = DomainTimingAnalytics::compute(&requests);
+
+        let order: Vec<&str> = result.domains.iter().map(|d| d.domain.as_str()).collect();
+        assert_eq!(
+            order,
+            vec!["api.example.com", "fonts.example.net", "cdn.example.com"]
+        );
+        assert!((result.domains[0].average_duration_ms - 900.0).abs() < 1e-9);
+        assert!((result.domains[1].total_duration_ms - 500.0).abs() < 1e-9);
+        assert!((result.domains[1].average_duration_ms - 250.0).abs() < 1e-9);
+        assert_eq!(result.domains[2].request_count, 3);
+        assert!((result.domains[2].average_duration_ms - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_instant_cache_hits_excluded_from_average() {
+        let requests = vec![
+            make_request("static.example.com", 100.0, false),
+            make_request("static.example.com", 0.0, true),
+            make_request("static.example.com", 0.2, true),
+            // Cached but revalidated over the network: still timed
+            make_request("static.example.com", 50.0, true),
+            make_request("static.example.com", f64::NAN, false),
+        ];
+        let result = DomainTimingAnalytics::compute(&requests);
+
+        let timing = &result.domains[0];
+        assert_eq!(timing.request_count, 5);
+        assert_eq!(timing.timed_count, 2);
+        assert!((timing.total_duration_ms - 150.0).abs() < 1e-9);
+        assert!((timing.average_duration_ms - 75.0).abs() < 1e-9);
+
+        // Only instant hits: nothing to average
+        let cached = DomainTimingAnalytics::compute(&[make_request("a.com", 0.0, true)]);
+        assert!(cached.domains[0].average_duration_ms.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_grouped_and_merged() {
+        let requests = vec![
+            make_request("a.example.com", 100.0, false),
+            make_request("b.example.com", 300.0, false),
+        ];
+        let grouped = DomainTimingAnalytics::compute_grouped(
+            &requests,
+            Locale::En,
+            DomainGrouping::RegistrableDomain,
+        );
+        assert_eq!(grouped.domains.len(), 1);
+        assert_eq!(grouped.domains[0].domain, "example.com");
+
+        let merged = grouped.merge(&DomainTimingAnalytics::compute_grouped(
+            &[make_request("example.com", 400.0, false)],
+            Locale::En,
+            DomainGrouping::RegistrableDomain,
+        ));
+        assert_eq!(merged.domains[0].timed_count, 3);
+        assert!((merged.domains[0].total_duration_ms - 800.0).abs() < 1e-9);
+        assert!((merged.domains[0].average_duration_ms - 800.0 / 3.0).abs() < 1e-9);
+    }
+}
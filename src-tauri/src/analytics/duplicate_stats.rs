@@ -1,9 +1,29 @@
 //! Duplicate resource detection.
 
+use super::filename::url_filename;
 use crate::sidecar::RequestDetail;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Thresholds for reporting a duplicate group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DuplicateOptions {
+    /// Resources smaller than this (bytes) are ignored, e.g. 1x1 tracking pixels.
+    pub min_size_bytes: u64,
+    /// Minimum number of loads for a resource to count as duplicated.
+    pub min_occurrences: usize,
+}
+
+impl Default for DuplicateOptions {
+    fn default() -> Self {
+        Self {
+            min_size_bytes: 1024,
+            min_occurrences: 2,
+        }
+    }
+}
+
 /// Group of duplicate resources.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -23,7 +43,7 @@ pub struct DuplicateGroup {
 }
 
 /// Aggregated duplicate analytics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DuplicateAnalytics {
     /// Detected duplicate groups, sorted by wasted bytes.
@@ -35,10 +55,18 @@ pub struct DuplicateAnalytics {
 }
 
 impl DuplicateAnalytics {
-    /// Compute duplicate analytics from requests.
+    /// Compute duplicate analytics from requests with the default thresholds.
     #[must_use]
-    #[allow(clippy::cast_possible_truncation)]
     pub fn compute(requests: &[RequestDetail]) -> Self {
+        Self::compute_with_options(requests, DuplicateOptions::default())
+    }
+
+    /// Compute duplicate analytics, reporting only groups that pass `options`.
+    ///
+    /// `total_wasted_bytes` covers the reported groups only.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn compute_with_options(requests: &[RequestDetail], options: DuplicateOptions) -> Self {
         if requests.is_empty() {
             return Self {
                 duplicates: vec![],
@@ -47,31 +75,32 @@ impl DuplicateAnalytics {
             };
         }
 
-        // Group by composite key: filename:size
+        // Group by composite key (filename, size)
         // Same filename AND same size = likely the same resource
-        let mut groups: HashMap<String, (Vec<String>, String, u64)> = HashMap::new();
+        let mut groups: HashMap<(String, u64), (Vec<String>, String)> = HashMap::new();
 
         for req in requests {
             let filename = Self::extract_filename(&req.url);
-            if filename.is_empty() || filename == "index.html" {
+            if filename.is_empty()
+                || filename == "index.html"
+                || req.resource_size < options.min_size_bytes
+            {
                 continue;
             }
 
-            // Key = filename:size
-            let key = format!("{}:{}", filename, req.resource_size);
+            // A tuple key: decoded names may contain `:`
             let entry = groups
-                .entry(key)
-                .or_insert_with(|| (vec![], req.resource_type.clone(), req.resource_size));
+                .entry((filename, req.resource_size))
+                .or_insert_with(|| (vec![], req.resource_type.clone()));
             entry.0.push(req.url.clone());
         }
 
-        // Filter to keep only groups with 2+ occurrences
+        // Keep only groups loaded often enough (never fewer than 2 loads)
+        let min_occurrences = options.min_occurrences.max(2);
         let mut duplicates: Vec<DuplicateGroup> = groups
             .into_iter()
-            .filter(|(_, (urls, _, _))| urls.len() > 1)
-            .map(|(key, (urls, resource_type, resource_size))| {
-                let filename = key.split(':').next().unwrap_or("").to_string();
-
+            .filter(|(_, (urls, _))| urls.len() >= min_occurrences)
+            .map(|((filename, resource_size), (urls, resource_type))| {
                 // Extract unique domains
                 let mut domains: Vec<String> = urls
                     .iter()
@@ -97,8 +126,7 @@ impl DuplicateAnalytics {
             })
             .collect();
 
-        // Sort by wasted bytes descending
-        duplicates.sort_by(|a, b| b.wasted_bytes.cmp(&a.wasted_bytes));
+        Self::sort_groups(&mut duplicates);
 
         let total_wasted_bytes: u64 = duplicates.iter().map(|d| d.wasted_bytes).sum();
         let duplicate_count = duplicates.len() as u32;
@@ -110,19 +138,59 @@ impl DuplicateAnalytics {
         }
     }
 
+    /// Combine the duplicates of two pages.
+    ///
+    /// Groups with the same filename and size are joined: every load is kept, so
+    /// wasted bytes add up across pages.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut duplicates = self.duplicates.clone();
+        let mut shared = 0;
+        for group in &other.duplicates {
+            let Some(existing) = duplicates
+                .iter_mut()
+                .find(|d| d.filename == group.filename && d.resource_size == group.resource_size)
+            else {
+                duplicates.push(group.clone());
+                continue;
+            };
+            shared += 1;
+            existing.urls.extend(group.urls.iter().cloned());
+            existing.domains.extend(group.domains.iter().cloned());
+            existing.domains.sort();
+            existing.domains.dedup();
+            existing.wasted_bytes += group.wasted_bytes;
+        }
+        Self::sort_groups(&mut duplicates);
+
+        Self {
+            duplicates,
+            total_wasted_bytes: self.total_wasted_bytes + other.total_wasted_bytes,
+            duplicate_count: self.duplicate_count + other.duplicate_count - shared,
+        }
+    }
+
+    /// Sort by wasted bytes descending, ties by filename then size.
+    fn sort_groups(duplicates: &mut [DuplicateGroup]) {
+        duplicates.sort_by(|a, b| {
+            b.wasted_bytes
+                .cmp(&a.wasted_bytes)
+                .then_with(|| a.filename.cmp(&b.filename))
+                .then_with(|| a.resource_size.cmp(&b.resource_size))
+        });
+    }
+
     /// Extract filename from URL.
     fn extract_filename(url: &str) -> String {
-        url::Url::parse(url)
-            .ok()
-            .and_then(|u| u.path_segments()?.next_back().map(str::to_string))
-            .filter(|s| !s.is_empty())
-            .unwrap_or_default()
+        url_filename(url).unwrap_or_default()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analytics::test_support::request;
 
     fn make_request(url: &str, resource_size: u64) -> RequestDetail {
         RequestDetail {
@@ -131,18 +199,9 @@ mod tests {
                 .ok()
                 .and_then(|u| u.host_str().map(str::to_string))
                 .unwrap_or_default(),
-            protocol: "h2".to_string(),
-            status_code: 200,
-            mime_type: "application/javascript".to_string(),
-            resource_type: "Script".to_string(),
             transfer_size: resource_size,
             resource_size,
-            priority: "High".to_string(),
-            start_time: 0.0,
-            end_time: 100.0,
-            duration: 100.0,
-            from_cache: false,
-            cache_lifetime_ms: 0,
+            ..request()
         }
     }
 
@@ -194,8 +253,8 @@ mod tests {
     #[test]
     fn test_multiple_duplicates_sorted_by_waste() {
         let requests = vec![
-            make_request("https://cdn1.com/small.js", 1000),
-            make_request("https://cdn2.com/small.js", 1000),
+            make_request("https://cdn1.com/small.js", 2000),
+            make_request("https://cdn2.com/small.js", 2000),
             make_request("https://cdn1.com/large.js", 10000),
             make_request("https://cdn2.com/large.js", 10000),
             make_request("https://cdn3.com/large.js", 10000),
@@ -207,7 +266,101 @@ mod tests {
         assert_eq!(result.duplicates[0].filename, "large.js");
         assert_eq!(result.duplicates[0].wasted_bytes, 20000); // (3-1) * 10000
         assert_eq!(result.duplicates[1].filename, "small.js");
-        assert_eq!(result.duplicates[1].wasted_bytes, 1000);
-        assert_eq!(result.total_wasted_bytes, 21000);
+        assert_eq!(result.duplicates[1].wasted_bytes, 2000);
+        assert_eq!(result.total_wasted_bytes, 22000);
+    }
+
+    #[test]
+    fn test_tied_duplicates_sorted_by_filename() {
+        let requests = vec![
+            make_request("https://cdn1.com/zeta.js", 4000),
+            make_request("https://cdn2.com/zeta.js", 4000),
+            make_request("https://cdn1.com/alpha.js", 4000),
+            make_request("https://cdn2.com/alpha.js", 4000),
+            make_request("https://cdn1.com/mid.css", 4000),
+            make_request("https://cdn2.com/mid.css", 4000),
+        ];
+        let mut reversed = requests.clone();
+        reversed.reverse();
+
+        let forward = DuplicateAnalytics::compute(&requests);
+        let backward = DuplicateAnalytics::compute(&reversed);
+        for result in [&forward, &backward, &backward.merge(&forward)] {
+            let names: Vec<&str> = result
+                .duplicates
+                .iter()
+                .map(|d| d.filename.as_str())
+                .collect();
+            assert_eq!(names, vec!["alpha.js", "mid.css", "zeta.js"]);
+        }
+    }
+
+    #[test]
+    fn test_percent_encoded_names_grouped() {
+        let requests = vec![
+            make_request("https://cdn1.com/my%20file.js", 4000),
+            make_request("https://cdn2.com/my file.js", 4000),
+            make_request("https://cdn1.com/app%3Av2.js", 4000),
+            make_request("https://cdn2.com/app:v2.js", 4000),
+        ];
+        let result = DuplicateAnalytics::compute(&requests);
+
+        let names: Vec<&str> = result
+            .duplicates
+            .iter()
+            .map(|d| d.filename.as_str())
+            .collect();
+        // The decoded `:` does not truncate the name
+        assert_eq!(names, vec!["app:v2.js", "my file.js"]);
+        assert!(result.duplicates.iter().all(|d| d.resource_size == 4000));
+        assert_eq!(result.total_wasted_bytes, 8000);
+    }
+
+    #[test]
+    fn test_small_duplicates_filtered_by_default() {
+        let requests = vec![
+            make_request("https://t1.com/pixel.gif", 43),
+            make_request("https://t2.com/pixel.gif", 43),
+            make_request("https://t3.com/pixel.gif", 43),
+            make_request("https://cdn1.com/app.js", 5000),
+            make_request("https://cdn2.com/app.js", 5000),
+        ];
+        let result = DuplicateAnalytics::compute(&requests);
+
+        assert_eq!(result.duplicate_count, 1);
+        assert_eq!(result.duplicates[0].filename, "app.js");
+        assert_eq!(result.total_wasted_bytes, 5000);
+
+        let all = DuplicateAnalytics::compute_with_options(
+            &requests,
+            DuplicateOptions {
+                min_size_bytes: 0,
+                ..DuplicateOptions::default()
+            },
+        );
+        assert_eq!(all.duplicate_count, 2);
+        assert_eq!(all.total_wasted_bytes, 5086);
+    }
+
+    #[test]
+    fn test_min_occurrences() {
+        let requests = vec![
+            make_request("https://cdn1.com/a.js", 5000),
+            make_request("https://cdn2.com/a.js", 5000),
+            make_request("https://cdn1.com/b.js", 3000),
+            make_request("https://cdn2.com/b.js", 3000),
+            make_request("https://cdn3.com/b.js", 3000),
+        ];
+        let result = DuplicateAnalytics::compute_with_options(
+            &requests,
+            DuplicateOptions {
+                min_occurrences: 3,
+                ..DuplicateOptions::default()
+            },
+        );
+
+        assert_eq!(result.duplicate_count, 1);
+        assert_eq!(result.duplicates[0].filename, "b.js");
+        assert_eq!(result.total_wasted_bytes, 6000);
     }
 }
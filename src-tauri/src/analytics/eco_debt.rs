@@ -0,0 +1,217 @@
+//! Custom "eco debt" score from organization-defined weights.
+//!
+//! Unlike the official `EcoIndex`, which only counts DOM elements, requests
+//! and bytes, the debt weighs each transferred kilobyte by its resource type
+//! and by whether it comes from the site itself or a third party. Teams can
+//! encode their own priorities (e.g. third-party JS is twice as bad) without
+//! touching the official score.
+
+use super::registrable_domain;
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Multipliers applied to transferred kilobytes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DebtWeights {
+    /// Weight per resource type (`Script`, `Image`, `Font`...), matched case-insensitively.
+    pub resource_types: HashMap<String, f64>,
+    /// Weight of resource types missing from `resource_types`.
+    pub default_type_weight: f64,
+    /// Weight of requests to the analyzed site (same registrable domain).
+    pub first_party: f64,
+    /// Weight of requests to other sites.
+    pub third_party: f64,
+}
+
+impl Default for DebtWeights {
+    /// Every kilobyte weighs 1: the debt equals the page weight in KB.
+    fn default() -> Self {
+        Self {
+            resource_types: HashMap::new(),
+            default_type_weight: 1.0,
+            first_party: 1.0,
+            third_party: 1.0,
+        }
+    }
+}
+
+impl DebtWeights {
+    /// Combined weight of a request (negative weights count as 0).
+    #[must_use]
+    pub fn weight(&self, resource_type: &str, third_party: bool) -> f64 {
+        let type_weight = self
+            .resource_types
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(resource_type))
+            .map_or(self.default_type_weight, |(_, weight)| *weight);
+        let party_weight = if third_party {
+            self.third_party
+        } else {
+            self.first_party
+        };
+        type_weight.max(0.0) * party_weight.max(0.0)
+    }
+}
+
+/// Debt of the requests sharing a domain and resource type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebtContributor {
+    /// Domain/hostname.
+    pub domain: String,
+    /// Resource type.
+    pub resource_type: String,
+    /// Whether the domain belongs to another site than the analyzed page.
+    pub third_party: bool,
+    /// Number of requests.
+    pub request_count: u32,
+    /// Bytes transferred.
+    pub transfer_size: u64,
+    /// Weighted kilobytes.
+    pub debt: f64,
+}
+
+/// Weighted page weight, with its main contributors.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EcoDebt {
+    /// Total debt (weighted kilobytes).
+    pub debt: f64,
+    /// Transferred kilobytes before weighting.
+    pub size_kb: f64,
+    /// Debt per domain and resource type, largest first.
+    pub contributors: Vec<DebtContributor>,
+}
+
+impl EcoDebt {
+    /// Weigh the requests of the page at `page_url` with `weights`.
+    ///
+    /// Without a parsable `page_url`, every request counts as first-party.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn compute(requests: &[RequestDetail], page_url: &str, weights: &DebtWeights) -> Self {
+        let site = url::Url::parse(page_url)
+            .ok()
+            .and_then(|url| url.host_str().map(registrable_domain));
+
+        // (domain, type) -> (third party, requests, bytes)
+        let mut groups: HashMap<(&str, &str), (bool, u32, u64)> = HashMap::new();
+        for req in requests {
+            let third_party = site
+                .as_ref()
+                .is_some_and(|site| registrable_domain(&req.domain) != *site);
+            let entry = groups
+                .entry((req.domain.as_str(), req.resource_type.as_str()))
+                .or_insert((third_party, 0, 0));
+            entry.1 += 1;
+            entry.2 += req.transfer_size;
+        }
+
+        let mut contributors: Vec<DebtContributor> = groups
+            .into_iter()
+            .map(
+                |((domain, resource_type), (third_party, request_count, transfer_size))| {
+                    DebtContributor {
+                        domain: domain.to_string(),
+                        resource_type: resource_type.to_string(),
+                        third_party,
+                        request_count,
+                        transfer_size,
+                        debt: transfer_size as f64 / 1024.0
+                            * weights.weight(resource_type, third_party),
+                    }
+                },
+            )
+            .collect();
+        contributors.sort_by(|a, b| {
+            b.debt
+                .total_cmp(&a.debt)
+                .then_with(|| a.domain.cmp(&b.domain))
+                .then_with(|| a.resource_type.cmp(&b.resource_type))
+        });
+
+        Self {
+            debt: contributors.iter().map(|c| c.debt).sum(),
+            size_kb: requests.iter().map(|r| r.transfer_size).sum::<u64>() as f64 / 1024.0,
+            contributors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(domain: &str, resource_type: &str, transfer_size: u64) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://{domain}/{resource_type}"),
+            domain: domain.to_string(),
+            mime_type: String::new(),
+            resource_type: resource_type.to_string(),
+            transfer_size,
+            resource_size: transfer_size,
+            priority: "Low".to_string(),
+            ..request()
+        }
+    }
+
+    fn requests() -> Vec<RequestDetail> {
+        vec![
+            make_request("img.example.com", "Image", 300 * 1024),
+            make_request("ads.vendor.net", "Script", 100 * 1024),
+            make_request("ads.vendor.net", "Script", 50 * 1024),
+            make_request("www.example.com", "Document", 50 * 1024),
+        ]
+    }
+
+    #[test]
+    fn test_default_weights_equal_page_weight() {
+        let debt = EcoDebt::compute(
+            &requests(),
+            "https://www.example.com/",
+            &DebtWeights::default(),
+        );
+
+        assert!((debt.debt - 500.0).abs() < 1e-9);
+        assert!((debt.size_kb - 500.0).abs() < 1e-9);
+        assert_eq!(debt.contributors[0].domain, "img.example.com");
+        assert!(!debt.contributors[0].third_party);
+        assert_eq!(debt.contributors[1].domain, "ads.vendor.net");
+        assert!(debt.contributors[1].third_party);
+        assert_eq!(debt.contributors[1].request_count, 2);
+    }
+
+    #[test]
+    fn test_heavy_third_party_js_weight_changes_ranking() {
+        let weights = DebtWeights {
+            resource_types: HashMap::from([("script".to_string(), 2.0)]),
+            third_party: 1.5,
+            ..DebtWeights::default()
+        };
+        let debt = EcoDebt::compute(&requests(), "https://www.example.com/", &weights);
+
+        // 150 KB of third-party JS x 3 now outweighs 300 KB of first-party images
+        assert_eq!(debt.contributors[0].domain, "ads.vendor.net");
+        assert!((debt.contributors[0].debt - 450.0).abs() < 1e-9);
+        assert_eq!(debt.contributors[1].domain, "img.example.com");
+        assert!((debt.debt - 800.0).abs() < 1e-9);
+        assert!((debt.size_kb - 500.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unknown_page_counts_everything_first_party() {
+        let weights = DebtWeights {
+            third_party: 10.0,
+            ..DebtWeights::default()
+        };
+        let debt = EcoDebt::compute(&requests(), "not a url", &weights);
+        assert!(debt.contributors.iter().all(|c| !c.third_party));
+        assert!((debt.debt - 500.0).abs() < 1e-9);
+        assert!(EcoDebt::compute(&[], "https://example.com", &weights)
+            .contributors
+            .is_empty());
+    }
+}
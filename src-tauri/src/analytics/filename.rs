@@ -0,0 +1,81 @@
+//! File names of request URLs, for display and grouping.
+
+use std::borrow::Cow;
+
+use percent_encoding::percent_decode_str;
+
+/// Last path segment of `url`, percent-decoded (`my%20file.js` -> `my file.js`).
+///
+/// The raw segment is kept when decoding would produce a path separator
+/// (`%2F`, `%5C`), a control character or invalid UTF-8. Returns `None` for
+/// unparsable URLs and URLs ending with `/`.
+pub(super) fn url_filename(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url).ok()?;
+    let segment = parsed.path_segments()?.next_back()?;
+    if segment.is_empty() {
+        return None;
+    }
+    Some(decode_segment(segment).into_owned())
+}
+
+/// Percent-decode a path segment, falling back to the raw text when unsafe.
+fn decode_segment(segment: &str) -> Cow<'_, str> {
+    match percent_decode_str(segment).decode_utf8() {
+        Ok(decoded) if !decoded.contains(['/', '\\']) && !decoded.contains(char::is_control) => {
+            decoded
+        },
+        _ => Cow::Borrowed(segment),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_and_decoded_names() {
+        assert_eq!(
+            url_filename("https://example.com/js/main.js").as_deref(),
+            Some("main.js")
+        );
+        assert_eq!(
+            url_filename("https://example.com/js/my%20file.js?v=1").as_deref(),
+            Some("my file.js")
+        );
+        // The url crate encodes spaces on parse: both spellings give the same name
+        assert_eq!(
+            url_filename("https://example.com/js/my file.js").as_deref(),
+            Some("my file.js")
+        );
+        assert_eq!(
+            url_filename("https://example.com/caf%C3%A9.css").as_deref(),
+            Some("café.css")
+        );
+    }
+
+    #[test]
+    fn test_unsafe_decodings_keep_raw_segment() {
+        assert_eq!(
+            url_filename("https://example.com/a%2F..%2Fb.js").as_deref(),
+            Some("a%2F..%2Fb.js")
+        );
+        assert_eq!(
+            url_filename("https://example.com/a%5Cb.js").as_deref(),
+            Some("a%5Cb.js")
+        );
+        assert_eq!(
+            url_filename("https://example.com/bad%FF.js").as_deref(),
+            Some("bad%FF.js")
+        );
+        assert_eq!(
+            url_filename("https://example.com/nul%00.js").as_deref(),
+            Some("nul%00.js")
+        );
+    }
+
+    #[test]
+    fn test_no_filename() {
+        assert!(url_filename("https://example.com/js/").is_none());
+        assert!(url_filename("not a url").is_none());
+    }
+}
@@ -0,0 +1,225 @@
+//! Response header overhead from captured response headers.
+
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+
+/// Responses with more header bytes than this are flagged.
+const LARGE_HEADERS_BYTES: u64 = 4096;
+
+/// Maximum number of flagged responses reported.
+const MAX_OFFENDERS: usize = 10;
+
+/// Response whose headers are unusually large.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OversizedHeaders {
+    /// Full URL of the resource.
+    pub url: String,
+    /// Domain/hostname.
+    pub domain: String,
+    /// Total header bytes of the response.
+    pub header_bytes: u64,
+    /// Name of the largest header (e.g. `set-cookie`, `content-security-policy`).
+    pub largest_header: String,
+    /// Bytes of the largest header.
+    pub largest_header_bytes: u64,
+}
+
+/// Aggregated response header analytics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderSizeAnalytics {
+    /// Total response header bytes across checked requests.
+    pub total_header_bytes: u64,
+    /// Number of requests with captured headers.
+    pub checked_count: u32,
+    /// Number of responses above the large-header threshold.
+    pub oversized_count: u32,
+    /// Largest offenders, sorted by header bytes (at most 10).
+    pub oversized: Vec<OversizedHeaders>,
+}
+
+impl HeaderSizeAnalytics {
+    /// Compute header size analytics from requests.
+    ///
+    /// Sizes follow the HTTP/1.1 wire format (`name: value\r\n`); HTTP/2 and
+    /// HTTP/3 compress headers, so this is an upper bound for those protocols.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        let checked: Vec<&RequestDetail> = requests
+            .iter()
+            .filter(|r| !r.response_headers.is_empty())
+            .collect();
+
+        let total_header_bytes = checked.iter().map(|r| Self::header_bytes(r)).sum();
+
+        let mut oversized: Vec<OversizedHeaders> = checked
+            .iter()
+            .filter_map(|r| {
+                let header_bytes = Self::header_bytes(r);
+                if header_bytes <= LARGE_HEADERS_BYTES {
+                    return None;
+                }
+                let (largest_header, largest_header_bytes) = r
+                    .response_headers
+                    .iter()
+                    .map(|(name, value)| (name, Self::line_bytes(name, value)))
+                    .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))?;
+                Some(OversizedHeaders {
+                    url: r.url.clone(),
+                    domain: r.domain.clone(),
+                    header_bytes,
+                    largest_header: largest_header.clone(),
+                    largest_header_bytes,
+                })
+            })
+            .collect();
+
+        oversized.sort_by(|a, b| {
+            b.header_bytes
+                .cmp(&a.header_bytes)
+                .then_with(|| a.url.cmp(&b.url))
+        });
+        let oversized_count = oversized.len() as u32;
+        oversized.truncate(MAX_OFFENDERS);
+
+        Self {
+            total_header_bytes,
+            checked_count: checked.len() as u32,
+            oversized_count,
+            oversized,
+        }
+    }
+
+    /// Combine the analytics of two pages, keeping the largest offenders overall.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut oversized: Vec<OversizedHeaders> = self
+            .oversized
+            .iter()
+            .chain(&other.oversized)
+            .cloned()
+            .collect();
+        oversized.sort_by(|a, b| {
+            b.header_bytes
+                .cmp(&a.header_bytes)
+                .then_with(|| a.url.cmp(&b.url))
+        });
+        oversized.truncate(MAX_OFFENDERS);
+
+        Self {
+            total_header_bytes: self.total_header_bytes + other.total_header_bytes,
+            checked_count: self.checked_count + other.checked_count,
+            oversized_count: self.oversized_count + other.oversized_count,
+            oversized,
+        }
+    }
+
+    /// Bytes of all response headers of a request.
+    fn header_bytes(request: &RequestDetail) -> u64 {
+        request
+            .response_headers
+            .iter()
+            .map(|(name, value)| Self::line_bytes(name, value))
+            .sum()
+    }
+
+    /// Bytes of one header line: `name: value\r\n`.
+    const fn line_bytes(name: &str, value: &str) -> u64 {
+        (name.len() + value.len() + 4) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+    use std::collections::HashMap;
+
+    fn make_request(url: &str, headers: &[(&str, &str)]) -> RequestDetail {
+        RequestDetail {
+            url: url.to_string(),
+            mime_type: "text/html".to_string(),
+            resource_type: "Document".to_string(),
+            response_headers: headers
+                .iter()
+                .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+                .collect::<HashMap<_, _>>(),
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_empty_requests() {
+        let result = HeaderSizeAnalytics::compute(&[]);
+        assert_eq!(result.total_header_bytes, 0);
+        assert_eq!(result.checked_count, 0);
+        assert!(result.oversized.is_empty());
+    }
+
+    #[test]
+    fn test_header_bytes_total() {
+        let requests = vec![
+            // "content-type: text/html\r\n" = 12 + 9 + 4 = 25
+            make_request("https://example.com/", &[("content-type", "text/html")]),
+            // "age: 10\r\n" = 3 + 2 + 4 = 9, "etag: abc\r\n" = 4 + 3 + 4 = 11
+            make_request(
+                "https://example.com/a.js",
+                &[("age", "10"), ("etag", "abc")],
+            ),
+            // Headers not captured: ignored
+            make_request("https://example.com/b.js", &[]),
+        ];
+        let result = HeaderSizeAnalytics::compute(&requests);
+
+        assert_eq!(result.checked_count, 2);
+        assert_eq!(result.total_header_bytes, 45);
+        assert_eq!(result.oversized_count, 0);
+    }
+
+    #[test]
+    fn test_large_headers_flagged() {
+        let cookie = "x".repeat(6000);
+        let csp = "y".repeat(5000);
+        let requests = vec![
+            make_request(
+                "https://example.com/",
+                &[("set-cookie", &cookie), ("content-type", "text/html")],
+            ),
+            make_request(
+                "https://example.com/page",
+                &[("content-security-policy", &csp)],
+            ),
+            make_request("https://example.com/a.js", &[("etag", "abc")]),
+        ];
+        let result = HeaderSizeAnalytics::compute(&requests);
+
+        assert_eq!(result.oversized_count, 2);
+        assert_eq!(result.oversized[0].url, "https://example.com/");
+        assert_eq!(result.oversized[0].largest_header, "set-cookie");
+        assert_eq!(result.oversized[0].largest_header_bytes, 6014);
+        assert_eq!(result.oversized[0].header_bytes, 6014 + 25);
+        assert_eq!(
+            result.oversized[1].largest_header,
+            "content-security-policy"
+        );
+    }
+
+    #[test]
+    fn test_offenders_capped() {
+        let cookie = "x".repeat(5000);
+        let requests: Vec<RequestDetail> = (0..15)
+            .map(|i| {
+                make_request(
+                    &format!("https://example.com/{i}"),
+                    &[("set-cookie", &cookie)],
+                )
+            })
+            .collect();
+        let result = HeaderSizeAnalytics::compute(&requests);
+
+        assert_eq!(result.oversized_count, 15);
+        assert_eq!(result.oversized.len(), MAX_OFFENDERS);
+    }
+}
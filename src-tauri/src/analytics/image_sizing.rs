@@ -0,0 +1,257 @@
+//! Oversized image detection (natural size vs displayed size).
+//!
+//! Approximates Lighthouse's "properly size images" audit for the fast path,
+//! from image dimensions measured in the page.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Images with at least this many times the needed pixels are flagged.
+const MIN_OVERSIZE_RATIO: f64 = 1.5;
+
+/// Image dimensions measured in the page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageMeasurement {
+    /// Image URL (`currentSrc`).
+    pub url: String,
+    /// Intrinsic width in pixels.
+    pub natural_width: u32,
+    /// Intrinsic height in pixels.
+    pub natural_height: u32,
+    /// Rendered width in CSS pixels.
+    pub display_width: f64,
+    /// Rendered height in CSS pixels.
+    pub display_height: f64,
+    /// Device pixel ratio of the page.
+    pub device_pixel_ratio: f64,
+    /// Encoded size in bytes (0 when unknown, e.g. cross-origin without timing access).
+    pub transfer_size: u64,
+}
+
+impl ImageMeasurement {
+    /// Pixels needed to render the image sharply.
+    fn displayed_pixels(&self) -> f64 {
+        let dpr = if self.device_pixel_ratio > 0.0 {
+            self.device_pixel_ratio
+        } else {
+            1.0
+        };
+        (self.display_width * dpr) * (self.display_height * dpr)
+    }
+
+    fn natural_pixels(&self) -> f64 {
+        f64::from(self.natural_width) * f64::from(self.natural_height)
+    }
+}
+
+/// Image served larger than displayed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OversizedImage {
+    /// Image URL.
+    pub url: String,
+    /// Intrinsic width in pixels.
+    pub natural_width: u32,
+    /// Intrinsic height in pixels.
+    pub natural_height: u32,
+    /// Rendered width in CSS pixels.
+    pub display_width: f64,
+    /// Rendered height in CSS pixels.
+    pub display_height: f64,
+    /// Natural pixels divided by needed pixels.
+    pub oversize_ratio: f64,
+    /// Encoded size in bytes (0 when unknown).
+    pub transfer_size: u64,
+    /// Estimated bytes saved by serving the displayed size.
+    pub wasted_bytes: u64,
+}
+
+/// Aggregated image sizing analytics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageSizingAnalytics {
+    /// Oversized images, sorted by wasted bytes.
+    pub oversized_images: Vec<OversizedImage>,
+    /// Number of visible, loaded images checked.
+    pub checked_count: u32,
+    /// Number of oversized images.
+    pub oversized_count: u32,
+    /// Total estimated wasted bytes.
+    pub total_wasted_bytes: u64,
+}
+
+impl ImageSizingAnalytics {
+    /// Compare natural and displayed sizes of measured images.
+    ///
+    /// Hidden or not-yet-loaded images are ignored. An image displayed several
+    /// times is judged on its largest rendering. Wasted bytes are estimated
+    /// proportionally to the unneeded pixels.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn compute(images: &[ImageMeasurement]) -> Self {
+        let mut largest: HashMap<&str, &ImageMeasurement> = HashMap::new();
+        for image in images
+            .iter()
+            .filter(|i| i.natural_pixels() > 0.0 && i.displayed_pixels() > 0.0)
+        {
+            largest
+                .entry(image.url.as_str())
+                .and_modify(|current| {
+                    if image.displayed_pixels() > current.displayed_pixels() {
+                        *current = image;
+                    }
+                })
+                .or_insert(image);
+        }
+
+        let checked_count = largest.len() as u32;
+        let mut oversized_images: Vec<OversizedImage> = largest
+            .into_values()
+            .filter_map(|image| {
+                let ratio = image.natural_pixels() / image.displayed_pixels();
+                if ratio < MIN_OVERSIZE_RATIO {
+                    return None;
+                }
+                let wasted_bytes = (image.transfer_size as f64 * (1.0 - 1.0 / ratio)) as u64;
+                Some(OversizedImage {
+                    url: image.url.clone(),
+                    natural_width: image.natural_width,
+                    natural_height: image.natural_height,
+                    display_width: image.display_width,
+                    display_height: image.display_height,
+                    oversize_ratio: ratio,
+                    transfer_size: image.transfer_size,
+                    wasted_bytes,
+                })
+            })
+            .collect();
+        oversized_images.sort_by(|a, b| {
+            b.wasted_bytes
+                .cmp(&a.wasted_bytes)
+                .then_with(|| a.url.cmp(&b.url))
+        });
+
+        Self {
+            oversized_count: oversized_images.len() as u32,
+            total_wasted_bytes: oversized_images.iter().map(|i| i.wasted_bytes).sum(),
+            oversized_images,
+            checked_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_image(
+        url: &str,
+        natural: (u32, u32),
+        display: (f64, f64),
+        transfer_size: u64,
+    ) -> ImageMeasurement {
+        ImageMeasurement {
+            url: url.to_string(),
+            natural_width: natural.0,
+            natural_height: natural.1,
+            display_width: display.0,
+            display_height: display.1,
+            device_pixel_ratio: 1.0,
+            transfer_size,
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let result = ImageSizingAnalytics::compute(&[]);
+        assert_eq!(result.checked_count, 0);
+        assert_eq!(result.oversized_count, 0);
+    }
+
+    #[test]
+    fn test_oversized_image_wasted_bytes() {
+        // 2000x1000 displayed at 500x250: 16x the needed pixels
+        let images = vec![make_image(
+            "https://example.com/hero.jpg",
+            (2000, 1000),
+            (500.0, 250.0),
+            160_000,
+        )];
+        let result = ImageSizingAnalytics::compute(&images);
+
+        assert_eq!(result.oversized_count, 1);
+        let image = &result.oversized_images[0];
+        assert!((image.oversize_ratio - 16.0).abs() < 1e-9);
+        assert_eq!(image.wasted_bytes, 150_000);
+        assert_eq!(result.total_wasted_bytes, 150_000);
+    }
+
+    #[test]
+    fn test_properly_sized_and_hidden_images_ignored() {
+        let images = vec![
+            make_image(
+                "https://example.com/ok.png",
+                (400, 300),
+                (400.0, 300.0),
+                20_000,
+            ),
+            make_image(
+                "https://example.com/slightly.png",
+                (440, 330),
+                (400.0, 300.0),
+                20_000,
+            ),
+            make_image(
+                "https://example.com/hidden.png",
+                (2000, 2000),
+                (0.0, 0.0),
+                20_000,
+            ),
+            make_image("https://example.com/broken.png", (0, 0), (100.0, 100.0), 0),
+        ];
+        let result = ImageSizingAnalytics::compute(&images);
+
+        assert_eq!(result.checked_count, 2);
+        assert_eq!(result.oversized_count, 0);
+    }
+
+    #[test]
+    fn test_device_pixel_ratio_accounted() {
+        // 800x600 displayed at 400x300 on a 2x screen is exactly right
+        let mut image = make_image(
+            "https://example.com/retina.png",
+            (800, 600),
+            (400.0, 300.0),
+            50_000,
+        );
+        image.device_pixel_ratio = 2.0;
+        let result = ImageSizingAnalytics::compute(&[image]);
+        assert_eq!(result.oversized_count, 0);
+    }
+
+    #[test]
+    fn test_largest_rendering_wins() {
+        let images = vec![
+            make_image(
+                "https://example.com/logo.png",
+                (1000, 1000),
+                (100.0, 100.0),
+                10_000,
+            ),
+            make_image(
+                "https://example.com/logo.png",
+                (1000, 1000),
+                (1000.0, 1000.0),
+                10_000,
+            ),
+        ];
+        let result = ImageSizingAnalytics::compute(&images);
+        assert_eq!(result.checked_count, 1);
+        assert_eq!(result.oversized_count, 0);
+    }
+}
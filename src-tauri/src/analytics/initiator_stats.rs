@@ -0,0 +1,175 @@
+//! What triggered each request: the HTML parser or JavaScript.
+//!
+//! Script-initiated requests are discovered late (after the script that issues
+//! them has downloaded and run), which delays loading and keeps the main
+//! thread busy.
+
+use super::safe_percentage;
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+
+/// Kind of request initiator, from the CDP `Network.Initiator.type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InitiatorKind {
+    /// Discovered by the HTML parser.
+    Parser,
+    /// Issued by JavaScript (`fetch`, injected tags, ...).
+    Script,
+    /// Preload or prefetch hint.
+    Preload,
+    /// Anything else (user navigation, redirects, preflights, ...).
+    Other,
+    /// Initiator not captured.
+    Unknown,
+}
+
+impl InitiatorKind {
+    /// Classify a CDP initiator type (`parser`, `script`, `preload`, ...).
+    #[must_use]
+    pub fn classify(initiator: &str) -> Self {
+        match initiator.trim().to_lowercase().as_str() {
+            "" => Self::Unknown,
+            "parser" => Self::Parser,
+            "script" => Self::Script,
+            "preload" => Self::Preload,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Aggregated initiator analytics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitiatorAnalytics {
+    /// Requests discovered by the HTML parser.
+    pub parser_count: u32,
+    /// Requests issued by JavaScript.
+    pub script_count: u32,
+    /// Requests triggered by preload hints.
+    pub preload_count: u32,
+    /// Requests with another initiator.
+    pub other_count: u32,
+    /// Requests whose initiator was not captured.
+    pub unknown_count: u32,
+    /// Transfer size of script-initiated requests in bytes.
+    pub script_bytes: u64,
+    /// Share of script-initiated requests among those with a known initiator.
+    pub script_percentage: f64,
+}
+
+impl InitiatorAnalytics {
+    /// Count requests per initiator kind.
+    #[must_use]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        let mut analytics = Self::default();
+        for req in requests {
+            match InitiatorKind::classify(&req.initiator) {
+                InitiatorKind::Parser => analytics.parser_count += 1,
+                InitiatorKind::Script => {
+                    analytics.script_count += 1;
+                    analytics.script_bytes += req.transfer_size;
+                },
+                InitiatorKind::Preload => analytics.preload_count += 1,
+                InitiatorKind::Other => analytics.other_count += 1,
+                InitiatorKind::Unknown => analytics.unknown_count += 1,
+            }
+        }
+        analytics.with_percentage()
+    }
+
+    /// Combine the analytics of two pages, recomputing the script share.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        Self {
+            parser_count: self.parser_count + other.parser_count,
+            script_count: self.script_count + other.script_count,
+            preload_count: self.preload_count + other.preload_count,
+            other_count: self.other_count + other.other_count,
+            unknown_count: self.unknown_count + other.unknown_count,
+            script_bytes: self.script_bytes + other.script_bytes,
+            script_percentage: 0.0,
+        }
+        .with_percentage()
+    }
+
+    fn with_percentage(mut self) -> Self {
+        let known = self.parser_count + self.script_count + self.preload_count + self.other_count;
+        self.script_percentage = safe_percentage(f64::from(self.script_count), f64::from(known));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+    use chromiumoxide::cdp::browser_protocol::network::{Initiator, InitiatorType};
+
+    fn make_request(initiator: &str, transfer_size: u64) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://example.com/{initiator}-{transfer_size}"),
+            transfer_size,
+            resource_size: transfer_size,
+            priority: "Medium".to_string(),
+            initiator: initiator.to_string(),
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_classify_cdp_initiators() {
+        // Initiators as they appear in `Network.requestWillBeSent` events
+        let cases = [
+            (InitiatorType::Parser, InitiatorKind::Parser),
+            (InitiatorType::Script, InitiatorKind::Script),
+            (InitiatorType::Preload, InitiatorKind::Preload),
+            (InitiatorType::SignedExchange, InitiatorKind::Other),
+            (InitiatorType::Preflight, InitiatorKind::Other),
+            (InitiatorType::Other, InitiatorKind::Other),
+        ];
+        for (cdp_type, expected) in cases {
+            let initiator = Initiator::new(cdp_type);
+            assert_eq!(
+                InitiatorKind::classify(initiator.r#type.as_ref()),
+                expected,
+                "{initiator:?}"
+            );
+        }
+        assert_eq!(InitiatorKind::classify(""), InitiatorKind::Unknown);
+        assert_eq!(InitiatorKind::classify(" Script "), InitiatorKind::Script);
+    }
+
+    #[test]
+    fn test_counts_script_vs_parser() {
+        let requests = vec![
+            make_request("parser", 1000),
+            make_request("parser", 2000),
+            make_request("script", 5000),
+            make_request("preload", 300),
+            make_request("", 10),
+        ];
+        let result = InitiatorAnalytics::compute(&requests);
+
+        assert_eq!(result.parser_count, 2);
+        assert_eq!(result.script_count, 1);
+        assert_eq!(result.preload_count, 1);
+        assert_eq!(result.unknown_count, 1);
+        assert_eq!(result.script_bytes, 5000);
+        assert!((result.script_percentage - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_and_merge() {
+        let empty = InitiatorAnalytics::compute(&[]);
+        assert_eq!(empty.script_count, 0);
+        assert!(empty.script_percentage.abs() < f64::EPSILON);
+
+        let a = InitiatorAnalytics::compute(&[make_request("script", 100)]);
+        let b = InitiatorAnalytics::compute(&[make_request("parser", 100)]);
+        let merged = a.merge(&b);
+        assert_eq!(merged.script_count, 1);
+        assert_eq!(merged.parser_count, 1);
+        assert!((merged.script_percentage - 50.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,219 @@
+//! Repeated inline content detection (SVG, script and style blocks).
+//!
+//! Complements [`super::DuplicateAnalytics`], which only sees network requests:
+//! an icon `<svg>` pasted in every component is never downloaded twice but
+//! still bloats the HTML.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Inline elements smaller than this (bytes) are ignored.
+///
+/// Used by the collector script, which does not send smaller elements.
+pub const MIN_INLINE_BYTES: u64 = 256;
+
+/// Length of the content preview sent for each element, in characters.
+pub const INLINE_PREVIEW_CHARS: usize = 80;
+
+/// Maximum number of groups reported.
+const MAX_GROUPS: usize = 20;
+
+/// Digest of an inline element, computed in the page.
+///
+/// Contents are hashed by the collector script so that whole scripts and
+/// stylesheets do not cross the `DevTools` connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineElement {
+    /// Element kind (`svg`, `script` or `style`).
+    pub kind: String,
+    /// 53-bit hash of the trimmed content (markup of an `<svg>`, text of a
+    /// `<script>` or `<style>`).
+    pub hash: u64,
+    /// Size of the trimmed content in bytes.
+    pub bytes: u64,
+    /// Start of the trimmed content, to help locate it.
+    pub preview: String,
+}
+
+/// Inline content repeated several times in the page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineDuplicateGroup {
+    /// Element kind (`svg`, `script` or `style`).
+    pub kind: String,
+    /// Size of one copy in bytes.
+    pub bytes: u64,
+    /// Number of copies in the page.
+    pub occurrences: u32,
+    /// Bytes of the copies beyond the first.
+    pub wasted_bytes: u64,
+    /// Start of the content, to help locate it.
+    pub preview: String,
+}
+
+/// Aggregated inline duplicate analytics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineDuplicateAnalytics {
+    /// Repeated contents, sorted by wasted bytes (at most 20).
+    pub groups: Vec<InlineDuplicateGroup>,
+    /// Number of inline elements checked.
+    pub checked_count: u32,
+    /// Number of redundant copies (occurrences beyond the first, all groups).
+    pub repeat_count: u32,
+    /// Total bytes of redundant copies.
+    pub total_wasted_bytes: u64,
+}
+
+impl InlineDuplicateAnalytics {
+    /// Group inline elements by kind, content hash and size.
+    #[must_use]
+    pub fn compute(elements: &[InlineElement]) -> Self {
+        // (kind, content hash, bytes) -> (occurrences, first preview)
+        let mut by_content: HashMap<(&str, u64, u64), (u32, &str)> = HashMap::new();
+        let mut checked_count = 0;
+        for element in elements.iter().filter(|e| e.bytes >= MIN_INLINE_BYTES) {
+            checked_count += 1;
+            let key = (element.kind.as_str(), element.hash, element.bytes);
+            by_content
+                .entry(key)
+                .or_insert((0, element.preview.as_str()))
+                .0 += 1;
+        }
+
+        let mut groups: Vec<InlineDuplicateGroup> = by_content
+            .into_iter()
+            .filter(|(_, (occurrences, _))| *occurrences > 1)
+            .map(
+                |((kind, _, bytes), (occurrences, preview))| InlineDuplicateGroup {
+                    kind: kind.to_string(),
+                    bytes,
+                    occurrences,
+                    wasted_bytes: bytes * u64::from(occurrences - 1),
+                    preview: preview.chars().take(INLINE_PREVIEW_CHARS).collect(),
+                },
+            )
+            .collect();
+        groups.sort_by(|a, b| {
+            b.wasted_bytes
+                .cmp(&a.wasted_bytes)
+                .then_with(|| a.kind.cmp(&b.kind))
+                .then_with(|| a.preview.cmp(&b.preview))
+        });
+
+        let repeat_count = groups.iter().map(|g| g.occurrences - 1).sum();
+        let total_wasted_bytes = groups.iter().map(|g| g.wasted_bytes).sum();
+        groups.truncate(MAX_GROUPS);
+
+        Self {
+            groups,
+            checked_count,
+            repeat_count,
+            total_wasted_bytes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    /// Digest of `content` as the collector script builds it.
+    fn make_element(kind: &str, content: &str) -> InlineElement {
+        let content = content.trim();
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        InlineElement {
+            kind: kind.to_string(),
+            hash: hasher.finish() >> 11,
+            bytes: content.len() as u64,
+            preview: content.chars().take(INLINE_PREVIEW_CHARS).collect(),
+        }
+    }
+
+    fn icon(name: &str) -> String {
+        format!(
+            r#"<svg viewBox="0 0 24 24"><path d="{}"/></svg>"#,
+            name.repeat(256)
+        )
+    }
+
+    #[test]
+    fn test_empty() {
+        let result = InlineDuplicateAnalytics::compute(&[]);
+        assert_eq!(result.checked_count, 0);
+        assert_eq!(result.repeat_count, 0);
+        assert!(result.groups.is_empty());
+    }
+
+    #[test]
+    fn test_repeated_svg_is_grouped() {
+        let elements = vec![
+            make_element("svg", &icon("a")),
+            make_element("svg", &icon("a")),
+            make_element("svg", &icon("a")),
+            make_element("svg", &icon("b")),
+        ];
+        let result = InlineDuplicateAnalytics::compute(&elements);
+
+        let bytes = icon("a").len() as u64;
+        assert_eq!(result.checked_count, 4);
+        assert_eq!(result.groups.len(), 1);
+        assert_eq!(result.groups[0].kind, "svg");
+        assert_eq!(result.groups[0].occurrences, 3);
+        assert_eq!(result.groups[0].bytes, bytes);
+        assert_eq!(result.repeat_count, 2);
+        assert_eq!(result.total_wasted_bytes, 2 * bytes);
+        assert!(result.groups[0].preview.starts_with("<svg"));
+        assert_eq!(result.groups[0].preview.len(), INLINE_PREVIEW_CHARS);
+    }
+
+    #[test]
+    fn test_kinds_and_whitespace() {
+        let script = "x".repeat(256);
+        let elements = vec![
+            make_element("script", &script),
+            make_element("script", &format!("\n  {script}\n")),
+            // Same text in a <style> is not a repeat of the script
+            make_element("style", &script),
+        ];
+        let result = InlineDuplicateAnalytics::compute(&elements);
+
+        assert_eq!(result.groups.len(), 1);
+        assert_eq!(result.groups[0].kind, "script");
+        assert_eq!(result.groups[0].occurrences, 2);
+        assert_eq!(result.repeat_count, 1);
+    }
+
+    #[test]
+    fn test_small_elements_ignored() {
+        let small = "y".repeat(255);
+        let elements = vec![make_element("svg", &small), make_element("svg", &small)];
+        let result = InlineDuplicateAnalytics::compute(&elements);
+
+        assert_eq!(result.checked_count, 0);
+        assert!(result.groups.is_empty());
+    }
+
+    #[test]
+    fn test_groups_sorted_by_waste() {
+        let small = "s".repeat(256);
+        let large = "l".repeat(1024);
+        let elements = vec![
+            make_element("style", &small),
+            make_element("style", &small),
+            make_element("style", &small),
+            make_element("script", &large),
+            make_element("script", &large),
+        ];
+        let result = InlineDuplicateAnalytics::compute(&elements);
+
+        assert_eq!(result.groups.len(), 2);
+        assert_eq!(result.groups[0].kind, "script");
+        assert_eq!(result.groups[1].kind, "style");
+        assert_eq!(result.repeat_count, 3);
+    }
+}
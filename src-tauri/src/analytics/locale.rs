@@ -0,0 +1,70 @@
+//! Display language for analytics labels.
+
+use serde::{Deserialize, Serialize};
+
+/// Language used for human-readable analytics labels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// French (default, matches the application UI).
+    #[default]
+    Fr,
+    /// English.
+    En,
+}
+
+impl Locale {
+    /// Label for a missing value ("Aucun" / "None").
+    #[must_use]
+    pub const fn none_label(self) -> &'static str {
+        match self {
+            Self::Fr => "Aucun",
+            Self::En => "None",
+        }
+    }
+
+    /// Label for an unknown domain.
+    #[must_use]
+    pub const fn unknown_domain_label(self) -> &'static str {
+        match self {
+            Self::Fr => "(inconnu)",
+            Self::En => "(unknown)",
+        }
+    }
+
+    /// Label for an unrecognized protocol.
+    #[must_use]
+    pub const fn other_label(self) -> &'static str {
+        match self {
+            Self::Fr => "Autre",
+            Self::En => "Other",
+        }
+    }
+
+    /// Abbreviation for days (used in TTL labels and badges).
+    #[must_use]
+    pub const fn day_suffix(self) -> &'static str {
+        match self {
+            Self::Fr => "j",
+            Self::En => "d",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_french() {
+        assert_eq!(Locale::default(), Locale::Fr);
+    }
+
+    #[test]
+    fn test_serde_lowercase() {
+        let json = serde_json::to_string(&Locale::En).unwrap_or_default();
+        assert_eq!(json, "\"en\"");
+        let locale: Locale = serde_json::from_str("\"fr\"").unwrap_or(Locale::En);
+        assert_eq!(locale, Locale::Fr);
+    }
+}
@@ -4,18 +4,205 @@
 //! calculated in the frontend, improving performance and ensuring
 //! consistent calculations across platforms.
 
+mod cache_busting;
+mod cache_health;
 mod cache_stats;
+mod cdn_stats;
+mod compression_stats;
+mod connection_stats;
+mod critical_path;
 mod domain_stats;
+mod domain_timing;
 mod duplicate_stats;
+mod eco_debt;
+mod filename;
+mod header_size;
+mod image_sizing;
+mod initiator_stats;
+mod inline_duplicates;
+mod locale;
+mod post_onload;
+mod preconnect;
+mod priority_stats;
+mod protocol_downgrade;
 mod protocol_stats;
+mod redirect_chains;
+mod registrable;
+mod start_time_histogram;
 
-pub use cache_stats::{CacheAnalytics, CacheGroup, ProblematicResource};
-pub use domain_stats::{DomainAnalytics, DomainStat};
-pub use duplicate_stats::{DuplicateAnalytics, DuplicateGroup};
+pub use cache_busting::{is_versioned_url, CacheBustingAnalytics};
+pub use cache_health::{CacheHealthAnalytics, DomainCacheHealth};
+pub use cache_stats::{CacheAnalytics, CacheBucket, CacheGroup, ProblematicResource};
+pub use cdn_stats::{CdnAnalytics, CdnDomain};
+pub use compression_stats::{TextCompressionAnalytics, UncompressedResource};
+pub use connection_stats::{ConnectionAnalytics, ConnectionRecommendation, OriginConnections};
+pub use critical_path::{CriticalPathTiming, CriticalResource};
+pub use domain_stats::{DomainAnalytics, DomainGrouping, DomainStat};
+pub use domain_timing::{DomainTiming, DomainTimingAnalytics};
+pub use duplicate_stats::{DuplicateAnalytics, DuplicateGroup, DuplicateOptions};
+pub use eco_debt::{DebtContributor, DebtWeights, EcoDebt};
+pub use header_size::{HeaderSizeAnalytics, OversizedHeaders};
+pub use image_sizing::{ImageMeasurement, ImageSizingAnalytics, OversizedImage};
+pub use initiator_stats::{InitiatorAnalytics, InitiatorKind};
+pub use inline_duplicates::{
+    InlineDuplicateAnalytics, InlineDuplicateGroup, InlineElement, INLINE_PREVIEW_CHARS,
+    MIN_INLINE_BYTES,
+};
+pub use locale::Locale;
+pub use post_onload::PostOnloadAnalytics;
+pub use preconnect::{PreconnectAnalytics, PreconnectCandidate, ResourceHint};
+pub use priority_stats::{PriorityAnalytics, PriorityIssue, PriorityLevel, PriorityStat};
+pub use protocol_downgrade::{DowngradedOrigin, ProtocolDowngradeAnalytics};
 pub use protocol_stats::{ProtocolAnalytics, ProtocolStat};
+pub use redirect_chains::{RedirectAnalytics, RedirectChain};
+pub use registrable::{registrable_domain, split_domain, DomainParts};
+pub use start_time_histogram::{StartTimeBucket, StartTimeHistogram, DEFAULT_BUCKET_MS};
 
-use crate::sidecar::RequestDetail;
+use crate::sidecar::{CacheItem, RequestDetail};
 use serde::{Deserialize, Serialize};
+use std::panic::{self, AssertUnwindSafe};
+
+/// Analytics that need per-request details, left empty without them.
+const REQUEST_ONLY_ANALYTICS: [&str; 17] = [
+    "domain_stats",
+    "domain_timing",
+    "protocol_stats",
+    "protocol_downgrade",
+    "cache_busting",
+    "cdn_stats",
+    "duplicate_stats",
+    "compression_stats",
+    "header_stats",
+    "priority_stats",
+    "initiator_stats",
+    "connection_stats",
+    "preconnect",
+    "redirect_stats",
+    "start_time_histogram",
+    "critical_path",
+    "post_onload",
+];
+
+/// Options for computing request analytics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AnalyticsOptions {
+    /// Language of human-readable labels.
+    pub locale: Locale,
+    /// How requests are grouped in domain statistics.
+    pub domain_grouping: DomainGrouping,
+    /// Resources smaller than this (bytes) are left out of detail lists.
+    /// Totals, counts and percentages still include them. 0 keeps everything.
+    pub min_detail_bytes: u64,
+    /// Thresholds for reporting duplicate resources.
+    pub duplicates: DuplicateOptions,
+    /// Bucket width of the request start-time histogram in ms (0 = 500 ms).
+    pub histogram_bucket_ms: u32,
+}
+
+/// Data-quality problem found while computing analytics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum AnalyticsWarning {
+    /// Requests whose URL could not be parsed (no filename or path analysis).
+    UnparseableUrls {
+        /// Number of requests affected.
+        count: u32,
+    },
+    /// Requests without a domain (grouped under the "unknown" label).
+    MissingDomain {
+        /// Number of requests affected.
+        count: u32,
+    },
+    /// Requests with non-finite or reversed start/end times.
+    InvalidTimings {
+        /// Number of requests affected.
+        count: u32,
+    },
+    /// An analytic failed on this data and was left empty.
+    AnalyticFailed {
+        /// Field name of the analytic (`domain_stats`, `cache_stats`...).
+        name: String,
+    },
+}
+
+impl AnalyticsWarning {
+    /// Warnings about malformed request details.
+    #[allow(clippy::cast_possible_truncation)]
+    fn detect(requests: &[RequestDetail]) -> Vec<Self> {
+        let count = |malformed: fn(&RequestDetail) -> bool| {
+            requests.iter().filter(|r| malformed(r)).count() as u32
+        };
+        let invalid_timing = |r: &RequestDetail| {
+            !r.start_time.is_finite() || !r.end_time.is_finite() || r.end_time < r.start_time
+        };
+        [
+            Self::UnparseableUrls {
+                count: count(|r| url::Url::parse(&r.url).is_err()),
+            },
+            Self::MissingDomain {
+                count: count(|r| r.domain.trim().is_empty()),
+            },
+            Self::InvalidTimings {
+                count: count(invalid_timing),
+            },
+        ]
+        .into_iter()
+        .filter(|w| w.affected_requests() > 0)
+        .collect()
+    }
+
+    /// Warnings of two pages combined: request counts of the same kind are
+    /// summed, each failed analytic is listed once.
+    fn merge(left: &[Self], right: &[Self]) -> Vec<Self> {
+        let mut merged = left.to_vec();
+        for warning in right {
+            if matches!(warning, Self::AnalyticFailed { .. }) && merged.contains(warning) {
+                continue;
+            }
+            let same_kind = merged
+                .iter_mut()
+                .find(|w| std::mem::discriminant(*w) == std::mem::discriminant(warning));
+            if let Some(
+                Self::UnparseableUrls { count }
+                | Self::MissingDomain { count }
+                | Self::InvalidTimings { count },
+            ) = same_kind
+            {
+                *count += warning.affected_requests();
+            } else {
+                merged.push(warning.clone());
+            }
+        }
+        merged
+    }
+
+    /// Number of requests the warning is about (0 for a failed analytic).
+    #[must_use]
+    pub const fn affected_requests(&self) -> u32 {
+        match self {
+            Self::UnparseableUrls { count }
+            | Self::MissingDomain { count }
+            | Self::InvalidTimings { count } => *count,
+            Self::AnalyticFailed { .. } => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for AnalyticsWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnparseableUrls { count } => write!(f, "{count} requests had unparseable URLs"),
+            Self::MissingDomain { count } => write!(f, "domain missing for {count} requests"),
+            Self::InvalidTimings { count } => write!(f, "{count} requests had invalid timings"),
+            Self::AnalyticFailed { name } => write!(f, "{name} could not be computed"),
+        }
+    }
+}
 
 /// Pre-computed analytics for the results page.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,23 +210,753 @@ use serde::{Deserialize, Serialize};
 pub struct RequestAnalytics {
     /// Domain statistics (grouped by domain).
     pub domain_stats: DomainAnalytics,
+    /// Total and average request duration per domain, slowest first.
+    #[serde(default)]
+    pub domain_timing: DomainTimingAnalytics,
     /// Protocol distribution (HTTP/1.1, HTTP/2, HTTP/3).
     pub protocol_stats: ProtocolAnalytics,
+    /// Origins mixing HTTP/1.1 with HTTP/2 or HTTP/3.
+    #[serde(default)]
+    pub protocol_downgrade: ProtocolDowngradeAnalytics,
     /// Cache TTL categories.
     pub cache_stats: CacheAnalytics,
+    /// Share of well-cached resources per domain, worst first.
+    #[serde(default)]
+    pub cache_health: CacheHealthAnalytics,
+    /// Versioned (cache-busted) vs raw static resource URLs.
+    #[serde(default)]
+    pub cache_busting: CacheBustingAnalytics,
+    /// Edge-cache hit ratio per CDN domain (from the `Age` header).
+    #[serde(default)]
+    pub cdn_stats: CdnAnalytics,
     /// Duplicate resource detection.
     pub duplicate_stats: DuplicateAnalytics,
+    /// Uncompressed text resources (from captured headers).
+    pub compression_stats: TextCompressionAnalytics,
+    /// Response header overhead (from captured headers).
+    #[serde(default)]
+    pub header_stats: HeaderSizeAnalytics,
+    /// Request priority distribution and loading-order issues.
+    pub priority_stats: PriorityAnalytics,
+    /// Requests discovered by the HTML parser vs issued by JavaScript.
+    #[serde(default)]
+    pub initiator_stats: InitiatorAnalytics,
+    /// Estimated connection handshakes (connection reuse).
+    pub connection_stats: ConnectionAnalytics,
+    /// Late third-party origins worth a preconnect or dns-prefetch hint.
+    #[serde(default)]
+    pub preconnect: PreconnectAnalytics,
+    /// Redirect chains longer than one hop.
+    #[serde(default)]
+    pub redirect_stats: RedirectAnalytics,
+    /// Requests started per time interval (loading bursts and long tails).
+    #[serde(default)]
+    pub start_time_histogram: StartTimeHistogram,
+    /// Time until the main document and render-critical CSS/JS are loaded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub critical_path: Option<CriticalPathTiming>,
+    /// Requests started after the load event (needs the load event time).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_onload: Option<PostOnloadAnalytics>,
+    /// Analytics that could not be computed for lack of input (left empty).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unavailable: Vec<String>,
+    /// Data-quality problems found while computing the analytics (malformed
+    /// requests, failed analytics), empty when the data was clean.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<AnalyticsWarning>,
 }
 
 impl RequestAnalytics {
     /// Compute all analytics from request details.
     #[must_use]
     pub fn compute(requests: &[RequestDetail]) -> Self {
+        Self::compute_with_locale(requests, Locale::default())
+    }
+
+    /// Compute all analytics from request details with labels in the given locale.
+    #[must_use]
+    pub fn compute_with_locale(requests: &[RequestDetail], locale: Locale) -> Self {
+        Self::compute_with_options(
+            requests,
+            AnalyticsOptions {
+                locale,
+                ..AnalyticsOptions::default()
+            },
+        )
+    }
+
+    /// Compute all analytics of the page loaded from `page_url`.
+    ///
+    /// Same as [`Self::compute_with_locale`], plus the critical path, which
+    /// needs the requested URL to follow redirects to the main document, and
+    /// the post-load requests when the load event time (`load_event_ms`) is known.
+    #[must_use]
+    pub fn compute_for_page(
+        requests: &[RequestDetail],
+        page_url: &str,
+        load_event_ms: Option<f64>,
+        locale: Locale,
+    ) -> Self {
+        let mut analytics = Self::compute_with_locale(requests, locale);
+        analytics.critical_path = CriticalPathTiming::compute(requests, Some(page_url));
+        analytics.post_onload =
+            load_event_ms.and_then(|load| PostOnloadAnalytics::compute(requests, load));
+        analytics
+    }
+
+    /// Compute the analytics available without request details.
+    ///
+    /// Only the cache analytics can be rebuilt, from the Lighthouse cache audit
+    /// `items`; the other analytics are empty and listed in `unavailable`. The
+    /// audit only lists resources with a short cache lifetime, so the cache
+    /// totals cover those resources, not the whole page.
+    #[must_use]
+    pub fn compute_from_cache_items(items: &[CacheItem], locale: Locale) -> Self {
+        let requests: Vec<RequestDetail> = items.iter().map(cache_item_request).collect();
+        let mut analytics = Self::compute_with_locale(&[], locale);
+        analytics.cache_stats = CacheAnalytics::compute_with_locale(&requests, locale);
+        analytics.cache_health =
+            CacheHealthAnalytics::compute_grouped(&requests, locale, DomainGrouping::default());
+        analytics.unavailable = REQUEST_ONLY_ANALYTICS
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        analytics
+    }
+
+    /// Compute all analytics from request details with the given options.
+    ///
+    /// Each analytic is computed in isolation: one that panics on malformed data
+    /// is logged, replaced by its empty result instead of failing the whole set
+    /// and reported in `warnings`. Malformed requests are kept: their warnings
+    /// only tell how far the numbers can be trusted.
+    #[must_use]
+    pub fn compute_with_options(requests: &[RequestDetail], options: AnalyticsOptions) -> Self {
+        let mut failed = Vec::new();
+        let locale = options.locale;
+        let grouping = options.domain_grouping;
+        let mut analytics = Self {
+            domain_stats: guarded("domain_stats", requests, &mut failed, |r| {
+                DomainAnalytics::compute_grouped(r, locale, grouping)
+            }),
+            domain_timing: guarded("domain_timing", requests, &mut failed, |r| {
+                DomainTimingAnalytics::compute_grouped(r, locale, grouping)
+            }),
+            protocol_stats: guarded("protocol_stats", requests, &mut failed, |r| {
+                ProtocolAnalytics::compute_with_locale(r, locale)
+            }),
+            protocol_downgrade: guarded(
+                "protocol_downgrade",
+                requests,
+                &mut failed,
+                ProtocolDowngradeAnalytics::compute,
+            ),
+            cache_stats: guarded("cache_stats", requests, &mut failed, |r| {
+                CacheAnalytics::compute_with_locale(r, locale)
+            }),
+            cache_health: guarded("cache_health", requests, &mut failed, |r| {
+                CacheHealthAnalytics::compute_grouped(r, locale, grouping)
+            }),
+            cache_busting: guarded(
+                "cache_busting",
+                requests,
+                &mut failed,
+                CacheBustingAnalytics::compute,
+            ),
+            cdn_stats: guarded("cdn_stats", requests, &mut failed, CdnAnalytics::compute),
+            duplicate_stats: guarded("duplicate_stats", requests, &mut failed, |r| {
+                DuplicateAnalytics::compute_with_options(r, options.duplicates)
+            }),
+            compression_stats: guarded(
+                "compression_stats",
+                requests,
+                &mut failed,
+                TextCompressionAnalytics::compute,
+            ),
+            header_stats: guarded(
+                "header_stats",
+                requests,
+                &mut failed,
+                HeaderSizeAnalytics::compute,
+            ),
+            priority_stats: guarded(
+                "priority_stats",
+                requests,
+                &mut failed,
+                PriorityAnalytics::compute,
+            ),
+            initiator_stats: guarded(
+                "initiator_stats",
+                requests,
+                &mut failed,
+                InitiatorAnalytics::compute,
+            ),
+            connection_stats: guarded(
+                "connection_stats",
+                requests,
+                &mut failed,
+                ConnectionAnalytics::compute,
+            ),
+            preconnect: guarded(
+                "preconnect",
+                requests,
+                &mut failed,
+                PreconnectAnalytics::compute,
+            ),
+            redirect_stats: guarded(
+                "redirect_stats",
+                requests,
+                &mut failed,
+                RedirectAnalytics::compute,
+            ),
+            start_time_histogram: guarded("start_time_histogram", requests, &mut failed, |r| {
+                StartTimeHistogram::compute(r, options.histogram_bucket_ms)
+            }),
+            critical_path: guarded("critical_path", requests, &mut failed, |r| {
+                CriticalPathTiming::compute(r, None)
+            }),
+            post_onload: None,
+            unavailable: Vec::new(),
+            warnings: AnalyticsWarning::detect(requests),
+        };
+        analytics.retain_details(options.min_detail_bytes);
+        analytics.warnings.extend(failed.into_iter().map(|name| {
+            AnalyticsWarning::AnalyticFailed {
+                name: name.to_string(),
+            }
+        }));
+        analytics
+    }
+
+    /// Combine the analytics of two pages into a site-wide view.
+    ///
+    /// Counts and byte totals are summed, percentages are recomputed from the
+    /// merged totals and detail lists are re-sorted (and truncated) like a single
+    /// page's. Both sides should use the same options: entries are matched by
+    /// their (possibly localized) labels. The critical path of the slower page is kept.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let critical_path = match (&self.critical_path, &other.critical_path) {
+            (Some(a), Some(b)) if b.critical_path_ms > a.critical_path_ms => Some(b.clone()),
+            (Some(a), _) => Some(a.clone()),
+            (None, b) => b.clone(),
+        };
+        let post_onload = match (&self.post_onload, &other.post_onload) {
+            (Some(a), Some(b)) => Some(a.merge(b)),
+            (a, b) => a.clone().or_else(|| b.clone()),
+        };
+        // Missing for any page: the merged figures are incomplete
+        let mut unavailable: Vec<String> = self
+            .unavailable
+            .iter()
+            .chain(&other.unavailable)
+            .cloned()
+            .collect();
+        unavailable.sort_unstable();
+        unavailable.dedup();
+
         Self {
-            domain_stats: DomainAnalytics::compute(requests),
-            protocol_stats: ProtocolAnalytics::compute(requests),
-            cache_stats: CacheAnalytics::compute(requests),
-            duplicate_stats: DuplicateAnalytics::compute(requests),
+            domain_stats: self.domain_stats.merge(&other.domain_stats),
+            domain_timing: self.domain_timing.merge(&other.domain_timing),
+            protocol_stats: self.protocol_stats.merge(&other.protocol_stats),
+            protocol_downgrade: self.protocol_downgrade.merge(&other.protocol_downgrade),
+            cache_stats: self.cache_stats.merge(&other.cache_stats),
+            cache_health: self.cache_health.merge(&other.cache_health),
+            cache_busting: self.cache_busting.merge(&other.cache_busting),
+            cdn_stats: self.cdn_stats.merge(&other.cdn_stats),
+            duplicate_stats: self.duplicate_stats.merge(&other.duplicate_stats),
+            compression_stats: self.compression_stats.merge(&other.compression_stats),
+            header_stats: self.header_stats.merge(&other.header_stats),
+            priority_stats: self.priority_stats.merge(&other.priority_stats),
+            initiator_stats: self.initiator_stats.merge(&other.initiator_stats),
+            connection_stats: self.connection_stats.merge(&other.connection_stats),
+            preconnect: self.preconnect.merge(&other.preconnect),
+            redirect_stats: self.redirect_stats.merge(&other.redirect_stats),
+            start_time_histogram: self.start_time_histogram.merge(&other.start_time_histogram),
+            critical_path,
+            post_onload,
+            unavailable,
+            warnings: AnalyticsWarning::merge(&self.warnings, &other.warnings),
+        }
+    }
+
+    /// Drop detail-list entries smaller than `min_bytes`, leaving aggregates untouched.
+    fn retain_details(&mut self, min_bytes: u64) {
+        if min_bytes == 0 {
+            return;
+        }
+        self.cache_stats
+            .problematic_resources
+            .retain(|r| r.resource_size >= min_bytes);
+        self.duplicate_stats
+            .duplicates
+            .retain(|d| d.resource_size >= min_bytes);
+        self.compression_stats
+            .uncompressed_resources
+            .retain(|r| r.resource_size >= min_bytes);
+        self.priority_stats
+            .early_low_priority
+            .retain(|i| i.transfer_size >= min_bytes);
+        self.priority_stats
+            .non_critical_high_priority
+            .retain(|i| i.transfer_size >= min_bytes);
+    }
+}
+
+/// Request standing for a cache audit item (URL, size and cache lifetime only).
+fn cache_item_request(item: &CacheItem) -> RequestDetail {
+    let domain = url::Url::parse(&item.url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_lowercase))
+        .unwrap_or_default();
+    RequestDetail {
+        url: item.url.clone(),
+        domain,
+        status_code: 200,
+        resource_type: "Other".to_string(),
+        transfer_size: item.total_bytes,
+        resource_size: item.total_bytes,
+        cache_lifetime_ms: item.cache_lifetime_ms,
+        ..RequestDetail::default()
+    }
+}
+
+/// Run one analytic, falling back to its result for no requests if it panics
+/// (or to its default value if that panics too).
+///
+/// The name of a failed analytic is added to `failed`.
+fn guarded<T: Default>(
+    name: &'static str,
+    requests: &[RequestDetail],
+    failed: &mut Vec<&'static str>,
+    compute: impl Fn(&[RequestDetail]) -> T,
+) -> T {
+    let run = |input: &[RequestDetail]| {
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            #[cfg(test)]
+            tests::maybe_fail(name);
+            compute(input)
+        }))
+    };
+    run(requests).unwrap_or_else(|_| {
+        failed.push(name);
+        log::warn!(
+            "Analytic {name} failed on {} requests, left empty",
+            requests.len()
+        );
+        run(&[]).unwrap_or_default()
+    })
+}
+
+/// Share of `part` in `total` as a percentage, 0 when `total` is zero.
+///
+/// Never NaN nor infinite, which `serde_json` would serialize as `null`.
+#[must_use]
+pub fn safe_percentage(part: f64, total: f64) -> f64 {
+    let percentage = part / total * 100.0;
+    if total > 0.0 && percentage.is_finite() {
+        percentage
+    } else {
+        0.0
+    }
+}
+
+/// Test fixtures shared by the analytics tests.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use crate::sidecar::RequestDetail;
+
+    /// A 1000-byte `h2` script on `example.com`, served in 100 ms.
+    ///
+    /// Tests override the fields they care about with struct update syntax.
+    pub fn request() -> RequestDetail {
+        RequestDetail {
+            url: "https://example.com/app.js".to_string(),
+            domain: "example.com".to_string(),
+            protocol: "h2".to_string(),
+            status_code: 200,
+            mime_type: "application/javascript".to_string(),
+            resource_type: "Script".to_string(),
+            transfer_size: 1000,
+            resource_size: 1000,
+            priority: "High".to_string(),
+            end_time: 100.0,
+            duration: 100.0,
+            ..RequestDetail::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+    use std::cell::Cell;
+    use std::collections::HashMap;
+
+    thread_local! {
+        /// Analytic made to panic by [`maybe_fail`] (fault injection).
+        static FAILING_ANALYTIC: Cell<Option<&'static str>> = const { Cell::new(None) };
+    }
+
+    pub(super) fn maybe_fail(name: &str) {
+        assert_ne!(
+            FAILING_ANALYTIC.with(Cell::get),
+            Some(name),
+            "injected failure in {name}"
+        );
+    }
+
+    fn make_request(path: &str, size: u64) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://example.com/{path}"),
+            protocol: "http/1.1".to_string(),
+            transfer_size: size,
+            resource_size: size,
+            priority: "Low".to_string(),
+            response_headers: HashMap::from([(
+                "content-type".to_string(),
+                "application/javascript".to_string(),
+            )]),
+            ..request()
+        }
+    }
+
+    fn requests() -> Vec<RequestDetail> {
+        vec![
+            make_request("a/big.js", 200_000),
+            make_request("b/big.js", 200_000),
+            make_request("a/small.js", 2_000),
+            make_request("b/small.js", 2_000),
+        ]
+    }
+
+    #[test]
+    fn test_default_options_keep_all_details() {
+        let all = RequestAnalytics::compute(&requests());
+        assert_eq!(all.cache_stats.problematic_resources.len(), 4);
+        assert_eq!(all.duplicate_stats.duplicates.len(), 2);
+        assert_eq!(all.compression_stats.uncompressed_resources.len(), 4);
+    }
+
+    #[test]
+    fn test_min_detail_bytes_shrinks_lists_only() {
+        let all = RequestAnalytics::compute(&requests());
+        let filtered = RequestAnalytics::compute_with_options(
+            &requests(),
+            AnalyticsOptions {
+                min_detail_bytes: 10_000,
+                ..AnalyticsOptions::default()
+            },
+        );
+
+        // Detail lists shrink
+        assert_eq!(filtered.cache_stats.problematic_resources.len(), 2);
+        assert_eq!(filtered.duplicate_stats.duplicates.len(), 1);
+        assert_eq!(filtered.compression_stats.uncompressed_resources.len(), 2);
+        assert_eq!(filtered.priority_stats.early_low_priority.len(), 2);
+
+        // Aggregates are computed over all requests
+        assert_eq!(filtered.domain_stats.total_requests, 4);
+        assert_eq!(
+            filtered.domain_stats.total_size,
+            all.domain_stats.total_size
+        );
+        assert_eq!(filtered.cache_stats.problematic_count, 4);
+        assert_eq!(filtered.duplicate_stats.duplicate_count, 2);
+        assert_eq!(
+            filtered.duplicate_stats.total_wasted_bytes,
+            all.duplicate_stats.total_wasted_bytes
+        );
+        assert_eq!(filtered.compression_stats.uncompressed_count, 4);
+        assert_eq!(
+            filtered.compression_stats.potential_savings,
+            all.compression_stats.potential_savings
+        );
+        assert!(
+            (filtered.protocol_stats.protocols[0].percentage
+                - all.protocol_stats.protocols[0].percentage)
+                .abs()
+                < f64::EPSILON
+        );
+    }
+
+    fn other_page() -> Vec<RequestDetail> {
+        let mut cached = make_request("c/app.css", 5_000);
+        cached.url = "https://cdn.example.org/app.css".to_string();
+        cached.domain = "cdn.example.org".to_string();
+        cached.protocol = "h2".to_string();
+        cached.cache_lifetime_ms = 30 * 86_400_000;
+        vec![cached, make_request("a/big.js", 200_000)]
+    }
+
+    #[test]
+    fn test_merge_sums_totals() {
+        let first = RequestAnalytics::compute(&requests());
+        let second = RequestAnalytics::compute(&other_page());
+        let merged = first.merge(&second);
+
+        assert_eq!(merged.domain_stats.total_requests, 6);
+        assert_eq!(
+            merged.domain_stats.total_size,
+            first.domain_stats.total_size + second.domain_stats.total_size
+        );
+        assert_eq!(merged.domain_stats.domains.len(), 2);
+        assert_eq!(merged.domain_stats.domains[0].domain, "example.com");
+        assert_eq!(merged.domain_stats.domains[0].request_count, 5);
+        assert_eq!(merged.cache_stats.total_resources, 6);
+        assert_eq!(merged.cache_stats.problematic_count, 5);
+        assert_eq!(merged.cache_stats.problematic_resources.len(), 5);
+        assert_eq!(
+            merged.duplicate_stats.total_wasted_bytes,
+            first.duplicate_stats.total_wasted_bytes
+        );
+        assert_eq!(merged.connection_stats.origin_count, 2);
+        assert_eq!(
+            merged.start_time_histogram.buckets[0].count,
+            first.start_time_histogram.buckets[0].count
+                + second.start_time_histogram.buckets[0].count
+        );
+    }
+
+    #[test]
+    fn test_merge_recomputes_percentages() {
+        let merged =
+            RequestAnalytics::compute(&requests()).merge(&RequestAnalytics::compute(&other_page()));
+        let all: Vec<RequestDetail> = requests().into_iter().chain(other_page()).collect();
+        let combined = RequestAnalytics::compute(&all);
+
+        let pairs = merged
+            .domain_stats
+            .domains
+            .iter()
+            .map(|d| d.percentage)
+            .zip(combined.domain_stats.domains.iter().map(|d| d.percentage))
+            .chain(
+                merged
+                    .protocol_stats
+                    .protocols
+                    .iter()
+                    .map(|p| p.percentage)
+                    .zip(
+                        combined
+                            .protocol_stats
+                            .protocols
+                            .iter()
+                            .map(|p| p.percentage),
+                    ),
+            )
+            .chain(
+                merged
+                    .cache_stats
+                    .groups
+                    .iter()
+                    .map(|g| g.percentage)
+                    .zip(combined.cache_stats.groups.iter().map(|g| g.percentage)),
+            );
+        for (merged, combined) in pairs {
+            assert!((merged - combined).abs() < 1e-9);
+        }
+        assert_eq!(merged.protocol_stats.protocols[0].protocol, "HTTP/2");
+        assert!((merged.cache_busting.versioned_percentage).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_merge_with_empty_is_identity() {
+        let page = RequestAnalytics::compute(&requests());
+        let merged = page.merge(&RequestAnalytics::compute(&[]));
+
+        assert_eq!(merged.domain_stats.total_requests, 4);
+        assert!((merged.domain_stats.domains[0].percentage - 100.0).abs() < f64::EPSILON);
+        assert_eq!(merged.duplicate_stats.duplicate_count, 2);
+        assert_eq!(
+            merged.start_time_histogram.buckets.len(),
+            page.start_time_histogram.buckets.len()
+        );
+    }
+
+    #[test]
+    fn test_guarded_returns_empty_result_on_panic() {
+        // Fails on real requests only, like an analytic choking on bad data
+        let fragile = |r: &[RequestDetail]| {
+            assert!(r.is_empty(), "analytic failed");
+            DomainAnalytics::compute(r)
+        };
+        let mut failed = Vec::new();
+        let stats = guarded("domain_stats", &requests(), &mut failed, fragile);
+        assert_eq!(stats.total_requests, 0);
+        assert!(stats.domains.is_empty());
+        assert_eq!(failed, vec!["domain_stats"]);
+
+        let stats = guarded(
+            "domain_stats",
+            &requests(),
+            &mut failed,
+            DomainAnalytics::compute,
+        );
+        assert_eq!(stats.total_requests, 4);
+        assert_eq!(failed.len(), 1);
+    }
+
+    #[test]
+    fn test_guarded_falls_back_to_default_when_empty_input_panics() {
+        // The injected failure hits the fallback run on no requests too
+        FAILING_ANALYTIC.with(|f| f.set(Some("critical_path")));
+        let mut failed = Vec::new();
+        let count = guarded(
+            "critical_path",
+            &requests(),
+            &mut failed,
+            <[RequestDetail]>::len,
+        );
+        FAILING_ANALYTIC.with(|f| f.set(None));
+        assert_eq!(count, 0);
+        assert_eq!(failed, vec!["critical_path"]);
+    }
+
+    #[test]
+    fn test_panicking_analytic_leaves_others_populated() {
+        FAILING_ANALYTIC.with(|f| f.set(Some("domain_stats")));
+        let analytics = RequestAnalytics::compute(&requests());
+        FAILING_ANALYTIC.with(|f| f.set(None));
+
+        // The failing analytic is empty...
+        assert_eq!(analytics.domain_stats.total_requests, 0);
+        assert!(analytics.domain_stats.domains.is_empty());
+        // ...the others are computed as usual
+        assert_eq!(analytics.protocol_stats.total_requests, 4);
+        assert_eq!(analytics.cache_stats.total_resources, 4);
+        assert_eq!(analytics.duplicate_stats.duplicates.len(), 2);
+        assert_eq!(analytics.connection_stats.origin_count, 1);
+    }
+
+    #[test]
+    fn test_checked_analytics_warn_about_malformed_requests() {
+        let clean = RequestAnalytics::compute(&requests());
+        assert!(clean.warnings.is_empty());
+
+        let mut no_url = make_request("x.js", 1_000);
+        no_url.url = "not a url".to_string();
+        let mut no_domain = make_request("y.js", 1_000);
+        no_domain.domain = String::new();
+        let mut reversed = make_request("z.js", 1_000);
+        reversed.end_time = -1.0;
+        let mut all = requests();
+        all.extend([no_url, no_domain.clone(), no_domain, reversed]);
+
+        let checked = RequestAnalytics::compute(&all);
+        assert_eq!(
+            checked.warnings,
+            vec![
+                AnalyticsWarning::UnparseableUrls { count: 1 },
+                AnalyticsWarning::MissingDomain { count: 2 },
+                AnalyticsWarning::InvalidTimings { count: 1 },
+            ]
+        );
+        assert_eq!(
+            checked.warnings[1].to_string(),
+            "domain missing for 2 requests"
+        );
+        // Malformed requests are still counted
+        assert_eq!(checked.domain_stats.total_requests, 8);
+        assert_eq!(checked.cache_stats.total_resources, 8);
+
+        // Site-wide, request counts add up and failed analytics are listed once
+        let failed = AnalyticsWarning::AnalyticFailed {
+            name: "cache_stats".to_string(),
+        };
+        let mut page = checked;
+        page.warnings.push(failed.clone());
+        assert_eq!(
+            page.merge(&page).warnings,
+            vec![
+                AnalyticsWarning::UnparseableUrls { count: 2 },
+                AnalyticsWarning::MissingDomain { count: 4 },
+                AnalyticsWarning::InvalidTimings { count: 2 },
+                failed,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checked_analytics_report_failed_analytic() {
+        FAILING_ANALYTIC.with(|f| f.set(Some("cache_stats")));
+        let analytics = RequestAnalytics::compute(&requests());
+        FAILING_ANALYTIC.with(|f| f.set(None));
+
+        assert_eq!(
+            analytics.warnings,
+            vec![AnalyticsWarning::AnalyticFailed {
+                name: "cache_stats".to_string()
+            }]
+        );
+        assert_eq!(analytics.domain_stats.total_requests, 4);
+
+        // Shipped with the result, for the UI to flag the empty analytic
+        let json = serde_json::to_value(&analytics).unwrap_or_default();
+        assert_eq!(
+            json["warnings"],
+            serde_json::json!([{ "kind": "analyticFailed", "name": "cache_stats" }])
+        );
+    }
+
+    #[test]
+    fn test_safe_percentage() {
+        assert!((safe_percentage(1.0, 4.0) - 25.0).abs() < f64::EPSILON);
+        assert!(safe_percentage(0.0, 0.0).abs() < f64::EPSILON);
+        assert!(safe_percentage(3.0, 0.0).abs() < f64::EPSILON);
+        assert!(safe_percentage(3.0, -1.0).abs() < f64::EPSILON);
+        assert!(safe_percentage(f64::NAN, 10.0).abs() < f64::EPSILON);
+        assert!(safe_percentage(1.0, f64::NAN).abs() < f64::EPSILON);
+    }
+
+    /// Paths of the percentage and ratio fields serialized as `null` (NaN or infinite).
+    fn null_shares(value: &serde_json::Value, path: &str, found: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                for (key, field) in fields {
+                    let path = format!("{path}.{key}");
+                    let key = key.to_ascii_lowercase();
+                    if field.is_null() && (key.contains("percentage") || key.contains("ratio")) {
+                        found.push(path.clone());
+                    }
+                    null_shares(field, &path, found);
+                }
+            },
+            serde_json::Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    null_shares(item, &format!("{path}[{i}]"), found);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    #[test]
+    fn test_zero_denominators_never_serialize_null() {
+        // Everything from cache: no bytes transferred, no timing
+        let cached: Vec<RequestDetail> = requests()
+            .into_iter()
+            .map(|mut r| {
+                r.from_cache = true;
+                r.transfer_size = 0;
+                r.resource_size = 0;
+                r.end_time = 0.0;
+                r.duration = 0.0;
+                r.response_headers
+                    .insert("age".to_string(), "0".to_string());
+                r
+            })
+            .collect();
+        let empty = RequestAnalytics::compute(&[]);
+        let page = RequestAnalytics::compute(&cached);
+
+        for analytics in [&empty, &page, &page.merge(&empty), &empty.merge(&empty)] {
+            let json = serde_json::to_value(analytics).unwrap_or_default();
+            let mut found = Vec::new();
+            null_shares(&json, "", &mut found);
+            assert!(found.is_empty(), "NaN shares: {found:?}");
         }
     }
 }
@@ -0,0 +1,164 @@
+//! Resources loaded after the document's load event.
+//!
+//! Requests started after `onload` (analytics beacons, deferred widgets,
+//! polling) do not delay the page but still transfer bytes and keep the radio
+//! and CPU busy.
+
+use super::safe_percentage;
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+
+/// Weight of the requests started after the load event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostOnloadAnalytics {
+    /// Load event time (ms since navigation start; the latest page's once merged).
+    pub load_event_ms: f64,
+    /// Requests started after the load event.
+    pub request_count: u32,
+    /// Bytes transferred by those requests.
+    pub transfer_size: u64,
+    /// Share of all requests started after the load event.
+    pub request_percentage: f64,
+    /// Share of all transferred bytes loaded after the load event.
+    pub size_percentage: f64,
+    /// Total number of requests.
+    pub total_requests: u32,
+    /// Total transferred bytes.
+    pub total_transfer_size: u64,
+}
+
+impl PostOnloadAnalytics {
+    /// Measure the requests started strictly after `load_event_ms`.
+    ///
+    /// Returns `None` for a negative or non-finite load time (load event never fired).
+    /// Requests without a finite start time count in the totals only.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn compute(requests: &[RequestDetail], load_event_ms: f64) -> Option<Self> {
+        if !load_event_ms.is_finite() || load_event_ms < 0.0 {
+            return None;
+        }
+        let (request_count, transfer_size) = requests
+            .iter()
+            .filter(|r| r.start_time.is_finite() && r.start_time > load_event_ms)
+            .fold((0, 0), |(count, bytes), r| {
+                (count + 1, bytes + r.transfer_size)
+            });
+
+        Some(Self::new(
+            load_event_ms,
+            request_count,
+            transfer_size,
+            requests.len() as u32,
+            requests.iter().map(|r| r.transfer_size).sum(),
+        ))
+    }
+
+    /// Combine the post-load weight of two pages, recomputing the shares.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        Self::new(
+            self.load_event_ms.max(other.load_event_ms),
+            self.request_count + other.request_count,
+            self.transfer_size + other.transfer_size,
+            self.total_requests + other.total_requests,
+            self.total_transfer_size + other.total_transfer_size,
+        )
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn new(
+        load_event_ms: f64,
+        request_count: u32,
+        transfer_size: u64,
+        total_requests: u32,
+        total_transfer_size: u64,
+    ) -> Self {
+        Self {
+            load_event_ms,
+            request_count,
+            transfer_size,
+            request_percentage: safe_percentage(
+                f64::from(request_count),
+                f64::from(total_requests),
+            ),
+            size_percentage: safe_percentage(transfer_size as f64, total_transfer_size as f64),
+            total_requests,
+            total_transfer_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(start_time: f64, transfer_size: u64) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://example.com/{start_time}"),
+            transfer_size,
+            resource_size: transfer_size,
+            priority: "Low".to_string(),
+            start_time,
+            end_time: start_time + 100.0,
+            ..request()
+        }
+    }
+
+    fn page() -> Vec<RequestDetail> {
+        vec![
+            make_request(0.0, 20_000),
+            make_request(300.0, 50_000),
+            // Started exactly at the load event: still part of the load
+            make_request(1200.0, 10_000),
+            // Beacon and deferred widget
+            make_request(1500.0, 500),
+            make_request(4000.0, 19_500),
+        ]
+    }
+
+    #[test]
+    fn test_requests_after_load_event() {
+        let result = PostOnloadAnalytics::compute(&page(), 1200.0);
+        assert!(result.is_some(), "valid load event");
+        let Some(result) = result else { return };
+
+        assert_eq!(result.request_count, 2);
+        assert_eq!(result.transfer_size, 20_000);
+        assert_eq!(result.total_requests, 5);
+        assert_eq!(result.total_transfer_size, 100_000);
+        assert!((result.request_percentage - 40.0).abs() < 1e-9);
+        assert!((result.size_percentage - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_everything_before_load_event() {
+        let result = PostOnloadAnalytics::compute(&page(), 10_000.0);
+        assert!(result.is_some_and(|r| r.request_count == 0 && r.size_percentage == 0.0));
+
+        let empty = PostOnloadAnalytics::compute(&[], 1000.0);
+        assert!(empty.is_some_and(|r| r.request_percentage == 0.0 && r.total_requests == 0));
+    }
+
+    #[test]
+    fn test_missing_load_event() {
+        assert!(PostOnloadAnalytics::compute(&page(), f64::NAN).is_none());
+        assert!(PostOnloadAnalytics::compute(&page(), -1.0).is_none());
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = PostOnloadAnalytics::compute(&page(), 1200.0);
+        let b = PostOnloadAnalytics::compute(&[make_request(0.0, 50_000)], 800.0);
+        assert!(a.is_some() && b.is_some(), "valid load events");
+        let (Some(a), Some(b)) = (a, b) else { return };
+        let merged = a.merge(&b);
+
+        assert_eq!(merged.request_count, 2);
+        assert_eq!(merged.total_requests, 6);
+        assert!((merged.load_event_ms - 1200.0).abs() < f64::EPSILON);
+        assert!((merged.size_percentage - 40.0 / 3.0).abs() < 1e-9);
+    }
+}
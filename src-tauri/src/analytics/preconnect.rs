@@ -0,0 +1,271 @@
+//! Third-party origins that would benefit from a connection hint.
+//!
+//! An origin first contacted after the main document has loaded pays its
+//! DNS, TCP and TLS setup on the critical path. Declaring it in the document
+//! head (`<link rel=preconnect>` or `dns-prefetch`) lets the browser do that
+//! work while the document is still downloading.
+
+use super::registrable_domain;
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Typical DNS + TCP + TLS setup time a hint can save (ms).
+const ESTIMATED_SETUP_MS: f64 = 300.0;
+
+/// Origins contacted less than this after the document loaded gain too little (ms).
+const MIN_SAVINGS_MS: f64 = 50.0;
+
+/// Bytes below which an origin is not worth a hint at all.
+const MIN_HINT_BYTES: u64 = 10 * 1024;
+
+/// Bytes from which a full preconnect (not just DNS) is worth its cost.
+const PRECONNECT_BYTES: u64 = 50 * 1024;
+
+/// Resource hint to declare for an origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResourceHint {
+    /// `<link rel=dns-prefetch>`: resolve the name only.
+    DnsPrefetch,
+    /// `<link rel=preconnect>`: open the connection (DNS, TCP, TLS).
+    Preconnect,
+}
+
+/// Third-party origin contacted late.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreconnectCandidate {
+    /// Domain/hostname.
+    pub domain: String,
+    /// Start time of its first request (ms).
+    pub first_request_ms: f64,
+    /// Delay between the end of the main document and the first request (ms).
+    pub delay_after_document_ms: f64,
+    /// Number of network requests (cache hits excluded).
+    pub request_count: u32,
+    /// Bytes transferred from this origin.
+    pub transfer_size: u64,
+    /// Connection setup time the hint could take off the critical path (ms).
+    pub estimated_savings_ms: f64,
+    /// Suggested hint.
+    pub hint: ResourceHint,
+}
+
+/// Preconnect / dns-prefetch opportunities.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreconnectAnalytics {
+    /// Candidate origins, highest potential savings first.
+    pub candidates: Vec<PreconnectCandidate>,
+    /// Bytes transferred from the candidate origins.
+    pub candidate_bytes: u64,
+}
+
+impl PreconnectAnalytics {
+    /// Find third-party origins first contacted after the main document loaded.
+    ///
+    /// The main document is the first successful `Document` request; origins
+    /// sharing its registrable domain are first-party and left out.
+    #[must_use]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        let Some(document) = main_document(requests) else {
+            return Self::default();
+        };
+        let site = registrable_domain(&document.domain);
+
+        // domain -> (first start, requests, bytes)
+        let mut by_origin: HashMap<&str, (f64, u32, u64)> = HashMap::new();
+        for req in requests
+            .iter()
+            .filter(|r| !r.from_cache && !r.domain.is_empty())
+        {
+            if registrable_domain(&req.domain) == site {
+                continue;
+            }
+            let entry = by_origin
+                .entry(req.domain.as_str())
+                .or_insert((req.start_time, 0, 0));
+            entry.0 = entry.0.min(req.start_time);
+            entry.1 += 1;
+            entry.2 += req.transfer_size;
+        }
+
+        let candidates = by_origin
+            .into_iter()
+            .filter_map(|(domain, (first_start, request_count, transfer_size))| {
+                let delay = first_start - document.end_time;
+                let savings = delay.min(ESTIMATED_SETUP_MS);
+                (savings >= MIN_SAVINGS_MS && transfer_size >= MIN_HINT_BYTES).then(|| {
+                    PreconnectCandidate {
+                        domain: domain.to_string(),
+                        first_request_ms: first_start,
+                        delay_after_document_ms: delay,
+                        request_count,
+                        transfer_size,
+                        estimated_savings_ms: savings,
+                        hint: hint_for(transfer_size),
+                    }
+                })
+            })
+            .collect();
+        Self::sorted(candidates)
+    }
+
+    /// Combine the candidates of two pages, summing origins with the same name.
+    ///
+    /// The hint of a summed origin is chosen again from its combined bytes.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut candidates: Vec<PreconnectCandidate> = Vec::new();
+        for candidate in self.candidates.iter().chain(&other.candidates) {
+            if let Some(existing) = candidates.iter_mut().find(|c| c.domain == candidate.domain) {
+                existing.request_count += candidate.request_count;
+                existing.transfer_size += candidate.transfer_size;
+                existing.estimated_savings_ms = existing
+                    .estimated_savings_ms
+                    .max(candidate.estimated_savings_ms);
+                existing.hint = hint_for(existing.transfer_size);
+            } else {
+                candidates.push(candidate.clone());
+            }
+        }
+        Self::sorted(candidates)
+    }
+
+    fn sorted(mut candidates: Vec<PreconnectCandidate>) -> Self {
+        candidates.sort_by(|a, b| {
+            b.estimated_savings_ms
+                .total_cmp(&a.estimated_savings_ms)
+                .then_with(|| b.transfer_size.cmp(&a.transfer_size))
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+        Self {
+            candidate_bytes: candidates.iter().map(|c| c.transfer_size).sum(),
+            candidates,
+        }
+    }
+}
+
+/// First successful HTML document of the page.
+fn main_document(requests: &[RequestDetail]) -> Option<&RequestDetail> {
+    requests
+        .iter()
+        .filter(|r| r.resource_type == "Document" && (200..300).contains(&r.status_code))
+        .min_by(|a, b| a.start_time.total_cmp(&b.start_time))
+}
+
+const fn hint_for(transfer_size: u64) -> ResourceHint {
+    if transfer_size >= PRECONNECT_BYTES {
+        ResourceHint::Preconnect
+    } else {
+        ResourceHint::DnsPrefetch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(
+        domain: &str,
+        resource_type: &str,
+        start_time: f64,
+        transfer_size: u64,
+    ) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://{domain}/{start_time}"),
+            domain: domain.to_string(),
+            resource_type: resource_type.to_string(),
+            transfer_size,
+            resource_size: transfer_size,
+            priority: "Low".to_string(),
+            start_time,
+            end_time: start_time + 100.0,
+            ..request()
+        }
+    }
+
+    fn page(third_parties: Vec<RequestDetail>) -> Vec<RequestDetail> {
+        // Document loaded at 100 ms
+        let mut requests = vec![
+            make_request("www.example.com", "Document", 0.0, 20_000),
+            make_request("static.example.com", "Script", 900.0, 300_000),
+        ];
+        requests.extend(third_parties);
+        requests
+    }
+
+    #[test]
+    fn test_late_heavy_third_party_is_flagged() {
+        let requests = page(vec![
+            make_request("widgets.vendor.com", "Script", 1200.0, 80_000),
+            make_request("widgets.vendor.com", "Script", 1500.0, 40_000),
+            // Early: contacted while the document was still loading
+            make_request("fonts.gstatic.com", "Font", 50.0, 90_000),
+            // Late but tiny
+            make_request("pixel.tracker.net", "Image", 2000.0, 500),
+            // Late and mid-weight: DNS only
+            make_request("cdn.analytics.io", "Script", 250.0, 20_000),
+        ]);
+        let result = PreconnectAnalytics::compute(&requests);
+
+        let domains: Vec<&str> = result
+            .candidates
+            .iter()
+            .map(|c| c.domain.as_str())
+            .collect();
+        assert_eq!(domains, vec!["widgets.vendor.com", "cdn.analytics.io"]);
+
+        let vendor = &result.candidates[0];
+        assert_eq!(vendor.request_count, 2);
+        assert_eq!(vendor.transfer_size, 120_000);
+        assert!((vendor.first_request_ms - 1200.0).abs() < 1e-9);
+        assert!((vendor.delay_after_document_ms - 1100.0).abs() < 1e-9);
+        assert!((vendor.estimated_savings_ms - ESTIMATED_SETUP_MS).abs() < 1e-9);
+        assert_eq!(vendor.hint, ResourceHint::Preconnect);
+
+        let analytics = &result.candidates[1];
+        assert!((analytics.estimated_savings_ms - 150.0).abs() < 1e-9);
+        assert_eq!(analytics.hint, ResourceHint::DnsPrefetch);
+        assert_eq!(result.candidate_bytes, 140_000);
+    }
+
+    #[test]
+    fn test_first_party_and_cached_origins_ignored() {
+        let mut cached = make_request("widgets.vendor.com", "Script", 1200.0, 80_000);
+        cached.from_cache = true;
+        let result = PreconnectAnalytics::compute(&page(vec![cached]));
+        assert!(result.candidates.is_empty());
+
+        // No document: nothing to compare against
+        let orphan = vec![make_request("widgets.vendor.com", "Script", 1200.0, 80_000)];
+        assert!(PreconnectAnalytics::compute(&orphan).candidates.is_empty());
+    }
+
+    #[test]
+    fn test_merge_sums_bytes_and_recomputes_hint() {
+        let a = PreconnectAnalytics::compute(&page(vec![make_request(
+            "cdn.analytics.io",
+            "Script",
+            1000.0,
+            30_000,
+        )]));
+        let b = PreconnectAnalytics::compute(&page(vec![make_request(
+            "cdn.analytics.io",
+            "Script",
+            1000.0,
+            30_000,
+        )]));
+        assert_eq!(a.candidates[0].hint, ResourceHint::DnsPrefetch);
+        let merged = a.merge(&b);
+
+        assert_eq!(merged.candidates.len(), 1);
+        assert_eq!(merged.candidates[0].transfer_size, 60_000);
+        assert_eq!(merged.candidates[0].request_count, 2);
+        // Neither page alone was worth a preconnect, both together are
+        assert_eq!(merged.candidates[0].hint, ResourceHint::Preconnect);
+        assert_eq!(merged.candidate_bytes, 60_000);
+    }
+}
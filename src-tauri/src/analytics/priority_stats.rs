@@ -0,0 +1,347 @@
+//! Request priority distribution and loading-order issues.
+
+use super::safe_percentage;
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+
+/// Transfer size above which a low-priority resource competes for bandwidth.
+const LARGE_RESOURCE_BYTES: u64 = 100 * 1024;
+
+/// Requests starting before this time (ms) are considered part of the initial load.
+const EARLY_START_MS: f64 = 1000.0;
+
+/// Requests starting after this time (ms) are not needed for the first render.
+const LATE_START_MS: f64 = 3000.0;
+
+/// Chrome network priority level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PriorityLevel {
+    /// Highest priority (main document, critical CSS).
+    VeryHigh,
+    /// High priority (fonts, blocking scripts, LCP image).
+    High,
+    /// Medium priority.
+    Medium,
+    /// Low priority (async scripts, images).
+    Low,
+    /// Lowest priority (prefetch).
+    VeryLow,
+}
+
+impl PriorityLevel {
+    /// All levels, from highest to lowest.
+    pub const ALL: [Self; 5] = [
+        Self::VeryHigh,
+        Self::High,
+        Self::Medium,
+        Self::Low,
+        Self::VeryLow,
+    ];
+
+    /// Parse a priority string case-insensitively (`VeryHigh`, `veryhigh`, `very_high`...).
+    #[must_use]
+    pub fn parse(priority: &str) -> Option<Self> {
+        let normalized: String = priority
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "veryhigh" | "highest" => Some(Self::VeryHigh),
+            "high" => Some(Self::High),
+            "medium" => Some(Self::Medium),
+            "low" => Some(Self::Low),
+            "verylow" | "lowest" => Some(Self::VeryLow),
+            _ => None,
+        }
+    }
+
+    const fn is_high(self) -> bool {
+        matches!(self, Self::VeryHigh | Self::High)
+    }
+
+    const fn is_low(self) -> bool {
+        matches!(self, Self::Low | Self::VeryLow)
+    }
+}
+
+/// Statistics for a single priority level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityStat {
+    /// Priority level.
+    pub priority: PriorityLevel,
+    /// Number of requests at this level.
+    pub request_count: u32,
+    /// Total transfer size in bytes.
+    pub total_transfer_size: u64,
+    /// Percentage of total transfer size.
+    pub percentage: f64,
+}
+
+/// Resource whose priority does not match its role in the page load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityIssue {
+    /// Full URL of the resource.
+    pub url: String,
+    /// Resource type (Script, Image, ...).
+    pub resource_type: String,
+    /// Priority level.
+    pub priority: PriorityLevel,
+    /// Transfer size in bytes.
+    pub transfer_size: u64,
+    /// Start time in milliseconds.
+    pub start_time: f64,
+}
+
+impl PriorityIssue {
+    fn from_request(request: &RequestDetail, priority: PriorityLevel) -> Self {
+        Self {
+            url: request.url.clone(),
+            resource_type: request.resource_type.clone(),
+            priority,
+            transfer_size: request.transfer_size,
+            start_time: request.start_time,
+        }
+    }
+}
+
+/// Aggregated priority analytics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityAnalytics {
+    /// Statistics per level, from `VeryHigh` to `VeryLow` (all five always present).
+    pub levels: Vec<PriorityStat>,
+    /// Number of requests with an unrecognized priority.
+    pub unknown_count: u32,
+    /// Large low-priority resources loaded during the initial load (bandwidth contention).
+    pub early_low_priority: Vec<PriorityIssue>,
+    /// High-priority resources that are not needed for the first render.
+    pub non_critical_high_priority: Vec<PriorityIssue>,
+}
+
+impl PriorityAnalytics {
+    /// Compute priority analytics from requests.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        let mut counts = [(0u32, 0u64); PriorityLevel::ALL.len()];
+        let mut unknown_count = 0;
+        let mut early_low_priority = Vec::new();
+        let mut non_critical_high_priority = Vec::new();
+
+        for req in requests {
+            let Some(priority) = PriorityLevel::parse(&req.priority) else {
+                unknown_count += 1;
+                continue;
+            };
+            let index = PriorityLevel::ALL
+                .iter()
+                .position(|p| *p == priority)
+                .unwrap_or_default();
+            counts[index].0 += 1;
+            counts[index].1 += req.transfer_size;
+
+            if priority.is_low()
+                && req.transfer_size >= LARGE_RESOURCE_BYTES
+                && req.start_time < EARLY_START_MS
+            {
+                early_low_priority.push(PriorityIssue::from_request(req, priority));
+            }
+            if priority.is_high()
+                && req.start_time >= LATE_START_MS
+                && Self::is_non_critical_type(&req.resource_type)
+            {
+                non_critical_high_priority.push(PriorityIssue::from_request(req, priority));
+            }
+        }
+
+        let total_size: u64 = counts.iter().map(|(_, size)| size).sum();
+        let levels = PriorityLevel::ALL
+            .iter()
+            .zip(counts)
+            .map(|(&priority, (count, size))| PriorityStat {
+                priority,
+                request_count: count,
+                total_transfer_size: size,
+                percentage: safe_percentage(size as f64, total_size as f64),
+            })
+            .collect();
+
+        early_low_priority.sort_by_key(|i| std::cmp::Reverse(i.transfer_size));
+        non_critical_high_priority.sort_by_key(|i| std::cmp::Reverse(i.transfer_size));
+
+        Self {
+            levels,
+            unknown_count,
+            early_low_priority,
+            non_critical_high_priority,
+        }
+    }
+
+    /// Combine the analytics of two pages, recomputing level percentages from merged sizes.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn merge(&self, other: &Self) -> Self {
+        let sizes: Vec<(u32, u64)> = PriorityLevel::ALL
+            .iter()
+            .map(|&priority| {
+                self.levels
+                    .iter()
+                    .chain(&other.levels)
+                    .filter(|l| l.priority == priority)
+                    .fold((0, 0), |(count, size), l| {
+                        (count + l.request_count, size + l.total_transfer_size)
+                    })
+            })
+            .collect();
+        let total_size: u64 = sizes.iter().map(|(_, size)| size).sum();
+        let levels = PriorityLevel::ALL
+            .iter()
+            .zip(sizes)
+            .map(|(&priority, (count, size))| PriorityStat {
+                priority,
+                request_count: count,
+                total_transfer_size: size,
+                percentage: safe_percentage(size as f64, total_size as f64),
+            })
+            .collect();
+
+        let merge_issues = |a: &[PriorityIssue], b: &[PriorityIssue]| {
+            let mut issues: Vec<PriorityIssue> = a.iter().chain(b).cloned().collect();
+            issues.sort_by_key(|i| std::cmp::Reverse(i.transfer_size));
+            issues
+        };
+
+        Self {
+            levels,
+            unknown_count: self.unknown_count + other.unknown_count,
+            early_low_priority: merge_issues(&self.early_low_priority, &other.early_low_priority),
+            non_critical_high_priority: merge_issues(
+                &self.non_critical_high_priority,
+                &other.non_critical_high_priority,
+            ),
+        }
+    }
+
+    /// Resource types that never block the first render.
+    fn is_non_critical_type(resource_type: &str) -> bool {
+        !matches!(
+            resource_type.to_lowercase().as_str(),
+            "document" | "stylesheet" | "font"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(
+        priority: &str,
+        resource_type: &str,
+        transfer_size: u64,
+        start_time: f64,
+    ) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://example.com/{resource_type}-{start_time}"),
+            mime_type: "application/octet-stream".to_string(),
+            resource_type: resource_type.to_string(),
+            transfer_size,
+            resource_size: transfer_size,
+            priority: priority.to_string(),
+            start_time,
+            end_time: start_time + 100.0,
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_empty_requests() {
+        let result = PriorityAnalytics::compute(&[]);
+        assert_eq!(result.levels.len(), 5);
+        assert!(result.levels.iter().all(|l| l.request_count == 0));
+        assert_eq!(result.unknown_count, 0);
+    }
+
+    #[test]
+    fn test_five_levels_counted() {
+        let requests = vec![
+            make_request("VeryHigh", "Document", 1000, 0.0),
+            make_request("High", "Font", 2000, 100.0),
+            make_request("High", "Script", 2000, 100.0),
+            make_request("Medium", "Script", 1000, 200.0),
+            make_request("Low", "Image", 3000, 500.0),
+            make_request("VeryLow", "Other", 1000, 500.0),
+        ];
+        let result = PriorityAnalytics::compute(&requests);
+
+        let counts: Vec<u32> = result.levels.iter().map(|l| l.request_count).collect();
+        assert_eq!(counts, vec![1, 2, 1, 1, 1]);
+        assert_eq!(result.levels[1].priority, PriorityLevel::High);
+        assert_eq!(result.levels[1].total_transfer_size, 4000);
+        assert!((result.levels[1].percentage - 40.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_priority_parsing_is_case_insensitive() {
+        assert_eq!(
+            PriorityLevel::parse("VERYHIGH"),
+            Some(PriorityLevel::VeryHigh)
+        );
+        assert_eq!(
+            PriorityLevel::parse("very_low"),
+            Some(PriorityLevel::VeryLow)
+        );
+        assert_eq!(
+            PriorityLevel::parse(" medium "),
+            Some(PriorityLevel::Medium)
+        );
+        assert_eq!(PriorityLevel::parse("urgent"), None);
+
+        let requests = vec![
+            make_request("low", "Image", 10, 0.0),
+            make_request("", "Image", 10, 0.0),
+        ];
+        let result = PriorityAnalytics::compute(&requests);
+        assert_eq!(result.levels[3].request_count, 1);
+        assert_eq!(result.unknown_count, 1);
+    }
+
+    #[test]
+    fn test_early_low_priority_flagged() {
+        let requests = vec![
+            make_request("Low", "Image", 200 * 1024, 300.0),
+            make_request("VeryLow", "Script", 150 * 1024, 800.0),
+            // Small, or loaded late: no contention
+            make_request("Low", "Image", 10 * 1024, 300.0),
+            make_request("Low", "Image", 200 * 1024, 5000.0),
+        ];
+        let result = PriorityAnalytics::compute(&requests);
+
+        assert_eq!(result.early_low_priority.len(), 2);
+        assert_eq!(result.early_low_priority[0].transfer_size, 200 * 1024);
+        assert_eq!(
+            result.early_low_priority[1].priority,
+            PriorityLevel::VeryLow
+        );
+    }
+
+    #[test]
+    fn test_non_critical_high_priority_flagged() {
+        let requests = vec![
+            make_request("High", "Image", 50_000, 4000.0),
+            make_request("VeryHigh", "Fetch", 5_000, 3500.0),
+            // Render-critical types or early requests are expected to be high
+            make_request("High", "Font", 30_000, 4000.0),
+            make_request("High", "Image", 50_000, 200.0),
+        ];
+        let result = PriorityAnalytics::compute(&requests);
+
+        assert_eq!(result.non_critical_high_priority.len(), 2);
+        assert_eq!(result.non_critical_high_priority[0].resource_type, "Image");
+        assert_eq!(result.non_critical_high_priority[1].resource_type, "Fetch");
+    }
+}
@@ -0,0 +1,194 @@
+//! Protocol downgrade detection (HTTP/1.1 assets on HTTP/2+ origins).
+
+use super::ProtocolAnalytics;
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// Origin serving some resources over HTTP/1.1 despite supporting HTTP/2 or HTTP/3.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DowngradedOrigin {
+    /// Domain/hostname.
+    pub domain: String,
+    /// Normalized protocols observed on this origin, sorted.
+    pub protocols: Vec<String>,
+    /// Requests served over HTTP/2 or HTTP/3.
+    pub multiplexed_requests: u32,
+    /// Requests served over HTTP/1.1.
+    pub http1_requests: u32,
+    /// Example HTTP/1.1 resource URLs (up to 5).
+    pub http1_examples: Vec<String>,
+}
+
+/// Origins with inconsistent protocols (often a misconfigured CDN or origin).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolDowngradeAnalytics {
+    /// Origins mixing HTTP/1.1 with HTTP/2 or HTTP/3, most HTTP/1.1 requests first.
+    pub origins: Vec<DowngradedOrigin>,
+    /// Total HTTP/1.1 requests on those origins.
+    pub downgraded_request_count: u32,
+}
+
+/// Maximum example URLs reported per origin.
+const MAX_EXAMPLES: usize = 5;
+
+impl ProtocolDowngradeAnalytics {
+    /// Detect origins serving resources over both HTTP/1.1 and HTTP/2 or HTTP/3.
+    ///
+    /// Cache hits and unrecognized protocols are ignored.
+    #[must_use]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        let mut by_origin: HashMap<&str, Vec<(String, &RequestDetail)>> = HashMap::new();
+        for req in requests.iter().filter(|r| !r.from_cache) {
+            let protocol = ProtocolAnalytics::normalize_protocol(&req.protocol);
+            if matches!(protocol.as_str(), "HTTP/1.1" | "HTTP/2" | "HTTP/3") {
+                by_origin
+                    .entry(req.domain.as_str())
+                    .or_default()
+                    .push((protocol, req));
+            }
+        }
+
+        let origins: Vec<DowngradedOrigin> = by_origin
+            .into_iter()
+            .filter_map(|(domain, entries)| {
+                let protocols: BTreeSet<&str> = entries.iter().map(|(p, _)| p.as_str()).collect();
+                if protocols.len() < 2 || !protocols.contains("HTTP/1.1") {
+                    return None;
+                }
+
+                let http1: Vec<&RequestDetail> = entries
+                    .iter()
+                    .filter(|(p, _)| p == "HTTP/1.1")
+                    .map(|(_, r)| *r)
+                    .collect();
+
+                #[allow(clippy::cast_possible_truncation)]
+                Some(DowngradedOrigin {
+                    domain: domain.to_string(),
+                    protocols: protocols.iter().map(|p| (*p).to_string()).collect(),
+                    multiplexed_requests: (entries.len() - http1.len()) as u32,
+                    http1_requests: http1.len() as u32,
+                    http1_examples: http1
+                        .iter()
+                        .take(MAX_EXAMPLES)
+                        .map(|r| r.url.clone())
+                        .collect(),
+                })
+            })
+            .collect();
+
+        Self::from_origins(origins)
+    }
+
+    /// Combine the downgraded origins of two pages.
+    ///
+    /// Origins are matched by domain: protocols are united, request counts summed
+    /// and examples kept up to the usual limit.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut origins: Vec<DowngradedOrigin> = Vec::new();
+        for origin in self.origins.iter().chain(&other.origins) {
+            let Some(existing) = origins.iter_mut().find(|o| o.domain == origin.domain) else {
+                origins.push(origin.clone());
+                continue;
+            };
+            let protocols: BTreeSet<String> = existing
+                .protocols
+                .iter()
+                .chain(&origin.protocols)
+                .cloned()
+                .collect();
+            existing.protocols = protocols.into_iter().collect();
+            existing.multiplexed_requests += origin.multiplexed_requests;
+            existing.http1_requests += origin.http1_requests;
+            for url in &origin.http1_examples {
+                if existing.http1_examples.len() < MAX_EXAMPLES
+                    && !existing.http1_examples.contains(url)
+                {
+                    existing.http1_examples.push(url.clone());
+                }
+            }
+        }
+        Self::from_origins(origins)
+    }
+
+    fn from_origins(mut origins: Vec<DowngradedOrigin>) -> Self {
+        origins.sort_by(|a, b| {
+            b.http1_requests
+                .cmp(&a.http1_requests)
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+        let downgraded_request_count = origins.iter().map(|o| o.http1_requests).sum();
+
+        Self {
+            origins,
+            downgraded_request_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(domain: &str, path: &str, protocol: &str) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://{domain}/{path}"),
+            domain: domain.to_string(),
+            protocol: protocol.to_string(),
+            mime_type: "text/html".to_string(),
+            resource_type: "Document".to_string(),
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_origin_mixing_h2_and_http1_is_flagged() {
+        let requests = vec![
+            make_request("cdn.example.com", "index.html", "h2"),
+            make_request("cdn.example.com", "app.js", "h2"),
+            make_request("cdn.example.com", "legacy.js", "http/1.1"),
+            make_request("cdn.example.com", "old.css", "http/1.1"),
+        ];
+        let result = ProtocolDowngradeAnalytics::compute(&requests);
+
+        assert_eq!(result.origins.len(), 1);
+        let origin = &result.origins[0];
+        assert_eq!(origin.domain, "cdn.example.com");
+        assert_eq!(origin.protocols, vec!["HTTP/1.1", "HTTP/2"]);
+        assert_eq!(origin.multiplexed_requests, 2);
+        assert_eq!(origin.http1_requests, 2);
+        assert_eq!(origin.http1_examples.len(), 2);
+        assert_eq!(result.downgraded_request_count, 2);
+    }
+
+    #[test]
+    fn test_consistent_origins_are_not_flagged() {
+        let requests = vec![
+            make_request("a.com", "1", "h2"),
+            make_request("a.com", "2", "h3"),
+            make_request("b.com", "1", "http/1.1"),
+            make_request("b.com", "2", "http/1.1"),
+        ];
+        let result = ProtocolDowngradeAnalytics::compute(&requests);
+        assert!(result.origins.is_empty());
+        assert_eq!(result.downgraded_request_count, 0);
+    }
+
+    #[test]
+    fn test_cache_hits_and_unknown_protocols_ignored() {
+        let mut cached = make_request("a.com", "cached.js", "http/1.1");
+        cached.from_cache = true;
+        let requests = vec![
+            make_request("a.com", "index.html", "h2"),
+            cached,
+            make_request("a.com", "data", ""),
+        ];
+        let result = ProtocolDowngradeAnalytics::compute(&requests);
+        assert!(result.origins.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 //! Protocol distribution computation.
 
+use super::{safe_percentage, Locale};
 use crate::sidecar::RequestDetail;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -8,75 +9,154 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProtocolStat {
-    /// Protocol name (HTTP/3, HTTP/2, HTTP/1.1, Autre).
+    /// Protocol name (HTTP/3, HTTP/2, HTTP/1.1, or the localized "other" label).
     pub protocol: String,
     /// Number of requests using this protocol.
     pub count: u32,
     /// Percentage of total requests.
     pub percentage: f64,
+    /// Bytes transferred over this protocol.
+    #[serde(default)]
+    pub transfer_size: u64,
+    /// Percentage of the total transferred bytes.
+    #[serde(default)]
+    pub size_percentage: f64,
     /// Color for visualization.
     pub color: String,
 }
 
 /// Aggregated protocol analytics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProtocolAnalytics {
     /// Statistics per protocol.
     pub protocols: Vec<ProtocolStat>,
     /// Total number of requests.
     pub total_requests: u32,
+    /// Total transferred bytes.
+    #[serde(default)]
+    pub total_transfer_size: u64,
 }
 
+/// Internal key of unrecognized protocols, localized only in the output.
+const OTHER_PROTOCOL: &str = "other";
+
+/// Display order of the normalized protocols.
+const PROTOCOL_ORDER: [&str; 4] = ["HTTP/3", "HTTP/2", "HTTP/1.1", OTHER_PROTOCOL];
+
 impl ProtocolAnalytics {
     /// Compute protocol analytics from requests.
     #[must_use]
-    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
     pub fn compute(requests: &[RequestDetail]) -> Self {
+        Self::compute_with_locale(requests, Locale::default())
+    }
+
+    /// Compute protocol analytics from requests with labels in the given locale.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn compute_with_locale(requests: &[RequestDetail], locale: Locale) -> Self {
         let total = requests.len() as u32;
         if total == 0 {
             return Self {
                 protocols: vec![],
                 total_requests: 0,
+                total_transfer_size: 0,
             };
         }
 
-        let mut counts: HashMap<String, u32> = HashMap::new();
+        // protocol -> (requests, bytes)
+        let mut counts: HashMap<String, (u32, u64)> = HashMap::new();
         for req in requests {
             let proto = Self::normalize_protocol(&req.protocol);
-            *counts.entry(proto).or_insert(0) += 1;
+            let entry = counts.entry(proto).or_insert((0, 0));
+            entry.0 += 1;
+            entry.1 += req.transfer_size;
         }
+        let total_transfer_size = requests.iter().map(|r| r.transfer_size).sum();
 
         // Fixed order for consistent display
-        let order = ["HTTP/3", "HTTP/2", "HTTP/1.1", "Autre"];
+        let order = PROTOCOL_ORDER;
         let colors: HashMap<&str, &str> = [
-            ("HTTP/3", "#10b981"),   // green
-            ("HTTP/2", "#3b82f6"),   // blue
-            ("HTTP/1.1", "#f59e0b"), // amber
-            ("Autre", "#6b7280"),    // gray
+            ("HTTP/3", "#10b981"),       // green
+            ("HTTP/2", "#3b82f6"),       // blue
+            ("HTTP/1.1", "#f59e0b"),     // amber
+            (OTHER_PROTOCOL, "#6b7280"), // gray
         ]
         .into();
 
         let protocols = order
             .iter()
             .filter_map(|&proto| {
-                counts.get(proto).map(|&count| ProtocolStat {
-                    protocol: proto.to_string(),
-                    count,
-                    percentage: (f64::from(count) / f64::from(total)) * 100.0,
-                    color: (*colors.get(proto).unwrap_or(&"#6b7280")).to_string(),
-                })
+                counts
+                    .get(proto)
+                    .map(|&(count, transfer_size)| ProtocolStat {
+                        protocol: if proto == OTHER_PROTOCOL {
+                            locale.other_label().to_string()
+                        } else {
+                            proto.to_string()
+                        },
+                        count,
+                        percentage: safe_percentage(f64::from(count), f64::from(total)),
+                        transfer_size,
+                        size_percentage: safe_percentage(
+                            transfer_size as f64,
+                            total_transfer_size as f64,
+                        ),
+                        color: (*colors.get(proto).unwrap_or(&"#6b7280")).to_string(),
+                    })
             })
             .collect();
 
         Self {
             protocols,
             total_requests: total,
+            total_transfer_size,
+        }
+    }
+
+    /// Combine the distributions of two pages, recomputing percentages from the merged total.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut protocols: Vec<ProtocolStat> = Vec::new();
+        for stat in self.protocols.iter().chain(&other.protocols) {
+            if let Some(existing) = protocols.iter_mut().find(|p| p.protocol == stat.protocol) {
+                existing.count += stat.count;
+                existing.transfer_size += stat.transfer_size;
+            } else {
+                protocols.push(stat.clone());
+            }
+        }
+        // Localized "other" labels are not in the fixed order and go last, by name
+        let rank = |p: &ProtocolStat| {
+            PROTOCOL_ORDER
+                .iter()
+                .position(|o| *o == p.protocol)
+                .unwrap_or(PROTOCOL_ORDER.len())
+        };
+        protocols.sort_by(|a, b| {
+            rank(a)
+                .cmp(&rank(b))
+                .then_with(|| a.protocol.cmp(&b.protocol))
+        });
+
+        let total = self.total_requests + other.total_requests;
+        let total_transfer_size = self.total_transfer_size + other.total_transfer_size;
+        for stat in &mut protocols {
+            stat.percentage = safe_percentage(f64::from(stat.count), f64::from(total));
+            stat.size_percentage =
+                safe_percentage(stat.transfer_size as f64, total_transfer_size as f64);
+        }
+
+        Self {
+            protocols,
+            total_requests: total,
+            total_transfer_size,
         }
     }
 
     /// Normalize protocol string to canonical name.
-    fn normalize_protocol(protocol: &str) -> String {
+    pub(super) fn normalize_protocol(protocol: &str) -> String {
         let p = protocol.to_lowercase();
         if p.starts_with("h3") || p.contains("quic") {
             "HTTP/3".to_string()
@@ -85,7 +165,7 @@ impl ProtocolAnalytics {
         } else if p.starts_with("http/1") || p == "http/1.1" || p == "http/1.0" {
             "HTTP/1.1".to_string()
         } else {
-            "Autre".to_string()
+            OTHER_PROTOCOL.to_string()
         }
     }
 }
@@ -93,23 +173,21 @@ impl ProtocolAnalytics {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analytics::test_support::request;
 
     fn make_request(protocol: &str) -> RequestDetail {
+        make_sized_request(protocol, 1000)
+    }
+
+    fn make_sized_request(protocol: &str, transfer_size: u64) -> RequestDetail {
         RequestDetail {
             url: "https://example.com/test".to_string(),
-            domain: "example.com".to_string(),
             protocol: protocol.to_string(),
-            status_code: 200,
             mime_type: "text/html".to_string(),
             resource_type: "Document".to_string(),
-            transfer_size: 1000,
-            resource_size: 1000,
-            priority: "High".to_string(),
-            start_time: 0.0,
-            end_time: 100.0,
-            duration: 100.0,
-            from_cache: false,
-            cache_lifetime_ms: 0,
+            transfer_size,
+            resource_size: transfer_size,
+            ..request()
         }
     }
 
@@ -131,7 +209,10 @@ mod tests {
             ProtocolAnalytics::normalize_protocol("http/1.1"),
             "HTTP/1.1"
         );
-        assert_eq!(ProtocolAnalytics::normalize_protocol("unknown"), "Autre");
+        assert_eq!(
+            ProtocolAnalytics::normalize_protocol("unknown"),
+            OTHER_PROTOCOL
+        );
     }
 
     #[test]
@@ -155,4 +236,76 @@ mod tests {
         assert_eq!(result.protocols[2].protocol, "HTTP/1.1");
         assert_eq!(result.protocols[2].count, 1);
     }
+
+    #[test]
+    fn test_bytes_per_protocol() {
+        // Most requests are HTTP/2, but most bytes still flow over HTTP/1.1
+        let requests = vec![
+            make_sized_request("h2", 100),
+            make_sized_request("h2", 100),
+            make_sized_request("h2", 100),
+            make_sized_request("h2", 100),
+            make_sized_request("http/1.1", 600),
+        ];
+        let result = ProtocolAnalytics::compute(&requests);
+
+        assert_eq!(result.total_transfer_size, 1000);
+        let h2 = &result.protocols[0];
+        assert_eq!(h2.protocol, "HTTP/2");
+        assert!((h2.percentage - 80.0).abs() < 1e-9);
+        assert_eq!(h2.transfer_size, 400);
+        assert!((h2.size_percentage - 40.0).abs() < 1e-9);
+        let h1 = &result.protocols[1];
+        assert_eq!(h1.transfer_size, 600);
+        assert!((h1.size_percentage - 60.0).abs() < 1e-9);
+
+        let merged = result.merge(&ProtocolAnalytics::compute(&[make_sized_request(
+            "h2", 1000,
+        )]));
+        assert_eq!(merged.total_transfer_size, 2000);
+        assert_eq!(merged.protocols[0].transfer_size, 1400);
+        assert!((merged.protocols[0].size_percentage - 70.0).abs() < 1e-9);
+        assert!((merged.protocols[1].size_percentage - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tied_protocols_keep_fixed_order() {
+        let requests = vec![
+            make_request("spdy"),
+            make_request("http/1.1"),
+            make_request("h2"),
+            make_request("h3"),
+        ];
+        let expected = vec!["HTTP/3", "HTTP/2", "HTTP/1.1", "Autre"];
+        let labels = |result: &ProtocolAnalytics| -> Vec<String> {
+            result
+                .protocols
+                .iter()
+                .map(|p| p.protocol.clone())
+                .collect()
+        };
+
+        let mut reversed = requests.clone();
+        reversed.reverse();
+        assert_eq!(labels(&ProtocolAnalytics::compute(&requests)), expected);
+        assert_eq!(labels(&ProtocolAnalytics::compute(&reversed)), expected);
+
+        // Mixed-locale merges put both "other" labels last, whatever the merge order
+        let fr = ProtocolAnalytics::compute(&requests);
+        let en = ProtocolAnalytics::compute_with_locale(&requests, Locale::En);
+        assert_eq!(labels(&fr.merge(&en)), labels(&en.merge(&fr)));
+    }
+
+    #[test]
+    fn test_other_protocol_english_label() {
+        let requests = vec![make_request("h2"), make_request("spdy")];
+
+        let fr = ProtocolAnalytics::compute(&requests);
+        assert_eq!(fr.protocols[1].protocol, "Autre");
+
+        let en = ProtocolAnalytics::compute_with_locale(&requests, Locale::En);
+        assert_eq!(en.protocols[0].protocol, "HTTP/2");
+        assert_eq!(en.protocols[1].protocol, "Other");
+        assert_eq!(en.protocols[1].color, "#6b7280");
+    }
 }
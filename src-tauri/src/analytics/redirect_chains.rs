@@ -0,0 +1,250 @@
+//! Redirect chain detection.
+//!
+//! Links each 3xx response to the request it redirected to, using the
+//! captured `location` header when available and otherwise inferring the
+//! target from status and timing (next request of the same type starting once
+//! the redirect finished).
+
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Tolerance when matching a redirect target's start to the redirect's end (ms).
+const TIMING_TOLERANCE_MS: f64 = 1.0;
+
+/// Chain of redirects ending at a final resource.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedirectChain {
+    /// URLs in order, from the first requested URL to the final resource.
+    pub urls: Vec<String>,
+    /// Number of redirects (hops) in the chain.
+    pub hops: u32,
+    /// Time spent on redirects before the final resource started (ms).
+    pub wasted_time_ms: f64,
+    /// Whether at least one hop was inferred from timing (no `location` header).
+    pub inferred: bool,
+}
+
+/// Redirect chains longer than one hop.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedirectAnalytics {
+    /// Chains with more than one hop, most wasted time first.
+    pub chains: Vec<RedirectChain>,
+    /// Total time wasted in the reported chains (ms).
+    pub total_wasted_time_ms: f64,
+}
+
+impl RedirectAnalytics {
+    /// Reconstruct redirect chains from requests.
+    #[must_use]
+    pub fn compute(requests: &[RequestDetail]) -> Self {
+        let links = link_redirects(requests);
+        let targets: Vec<usize> = links.values().map(|(to, _)| *to).collect();
+
+        let chains: Vec<RedirectChain> = links
+            .keys()
+            .filter(|from| !targets.contains(from))
+            .filter_map(|&start| {
+                let mut urls = vec![requests[start].url.clone()];
+                let mut inferred = false;
+                let mut current = start;
+                while let Some(&(next, by_timing)) = links.get(&current) {
+                    // Guard against cycles in malformed data
+                    if urls.len() > requests.len() {
+                        break;
+                    }
+                    inferred |= by_timing;
+                    urls.push(requests[next].url.clone());
+                    current = next;
+                }
+
+                #[allow(clippy::cast_possible_truncation)]
+                let hops = (urls.len() - 1) as u32;
+                (hops > 1).then(|| RedirectChain {
+                    urls,
+                    hops,
+                    wasted_time_ms: (requests[current].start_time - requests[start].start_time)
+                        .max(0.0),
+                    inferred,
+                })
+            })
+            .collect();
+
+        Self::from_chains(chains)
+    }
+
+    /// Combine the redirect chains of two pages.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        Self::from_chains(self.chains.iter().chain(&other.chains).cloned().collect())
+    }
+
+    fn from_chains(mut chains: Vec<RedirectChain>) -> Self {
+        chains.sort_by(|a, b| {
+            b.wasted_time_ms
+                .total_cmp(&a.wasted_time_ms)
+                .then_with(|| a.urls.cmp(&b.urls))
+        });
+        let total_wasted_time_ms = chains.iter().map(|c| c.wasted_time_ms).sum();
+
+        Self {
+            chains,
+            total_wasted_time_ms,
+        }
+    }
+}
+
+/// Map each redirect (by index) to its target index and whether the link was inferred.
+fn link_redirects(requests: &[RequestDetail]) -> HashMap<usize, (usize, bool)> {
+    let mut order: Vec<usize> = (0..requests.len()).collect();
+    order.sort_by(|&a, &b| requests[a].start_time.total_cmp(&requests[b].start_time));
+
+    let mut links = HashMap::new();
+    let mut claimed = vec![false; requests.len()];
+    for &from in &order {
+        let redirect = &requests[from];
+        if !(300..400).contains(&redirect.status_code) || redirect.status_code == 304 {
+            continue;
+        }
+
+        let is_candidate = |&&to: &&usize| {
+            to != from && !claimed[to] && requests[to].start_time >= redirect.start_time
+        };
+        let by_header = redirect
+            .response_headers
+            .get("location")
+            .and_then(|location| resolve_location(&redirect.url, location))
+            .and_then(|target| {
+                order
+                    .iter()
+                    .filter(is_candidate)
+                    .find(|&&to| requests[to].url == target)
+            });
+        let by_timing = || {
+            order.iter().filter(is_candidate).find(|&&to| {
+                requests[to].resource_type == redirect.resource_type
+                    && requests[to].start_time >= redirect.end_time - TIMING_TOLERANCE_MS
+            })
+        };
+
+        let link = match by_header {
+            Some(&to) => Some((to, false)),
+            None => by_timing().map(|&to| (to, true)),
+        };
+        if let Some((to, inferred)) = link {
+            claimed[to] = true;
+            links.insert(from, (to, inferred));
+        }
+    }
+    links
+}
+
+/// Resolve a (possibly relative) `location` header against the redirecting URL.
+fn resolve_location(base: &str, location: &str) -> Option<String> {
+    url::Url::parse(base)
+        .and_then(|base| base.join(location))
+        .map(String::from)
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(url: &str, status_code: u16, start_time: f64, end_time: f64) -> RequestDetail {
+        RequestDetail {
+            url: url.to_string(),
+            status_code,
+            mime_type: "text/html".to_string(),
+            resource_type: "Document".to_string(),
+            priority: "VeryHigh".to_string(),
+            start_time,
+            end_time,
+            duration: end_time - start_time,
+            ..request()
+        }
+    }
+
+    fn with_location(mut request: RequestDetail, location: &str) -> RequestDetail {
+        request
+            .response_headers
+            .insert("location".to_string(), location.to_string());
+        request
+    }
+
+    #[test]
+    fn test_two_hop_chain_from_location_headers() {
+        let requests = vec![
+            with_location(
+                make_request("http://example.com/", 301, 0.0, 80.0),
+                "https://example.com/",
+            ),
+            with_location(
+                make_request("https://example.com/", 302, 90.0, 150.0),
+                "/fr/",
+            ),
+            make_request("https://example.com/fr/", 200, 160.0, 400.0),
+        ];
+        let result = RedirectAnalytics::compute(&requests);
+
+        assert_eq!(result.chains.len(), 1);
+        let chain = &result.chains[0];
+        assert_eq!(chain.hops, 2);
+        assert_eq!(
+            chain.urls,
+            vec![
+                "http://example.com/",
+                "https://example.com/",
+                "https://example.com/fr/"
+            ]
+        );
+        assert!((chain.wasted_time_ms - 160.0).abs() < f64::EPSILON);
+        assert!(!chain.inferred);
+        assert!((result.total_wasted_time_ms - 160.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_two_hop_chain_inferred_from_timing() {
+        let mut script = make_request("https://example.com/app.js", 200, 85.0, 120.0);
+        script.resource_type = "Script".to_string();
+        let requests = vec![
+            make_request("http://example.com/", 301, 0.0, 80.0),
+            script,
+            make_request("https://example.com/", 302, 90.0, 150.0),
+            make_request("https://example.com/fr/", 200, 160.0, 400.0),
+        ];
+        let result = RedirectAnalytics::compute(&requests);
+
+        assert_eq!(result.chains.len(), 1);
+        assert_eq!(result.chains[0].hops, 2);
+        assert!(result.chains[0].inferred);
+        assert_eq!(
+            result.chains[0].urls.last().map(String::as_str),
+            Some("https://example.com/fr/")
+        );
+    }
+
+    #[test]
+    fn test_single_redirect_not_reported() {
+        let requests = vec![
+            make_request("http://example.com/", 301, 0.0, 80.0),
+            make_request("https://example.com/", 200, 90.0, 300.0),
+        ];
+        let result = RedirectAnalytics::compute(&requests);
+        assert!(result.chains.is_empty());
+        assert!(result.total_wasted_time_ms.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_not_modified_is_not_a_redirect() {
+        let requests = vec![
+            make_request("https://example.com/a", 304, 0.0, 10.0),
+            make_request("https://example.com/b", 304, 20.0, 30.0),
+            make_request("https://example.com/c", 200, 40.0, 50.0),
+        ];
+        assert!(RedirectAnalytics::compute(&requests).chains.is_empty());
+    }
+}
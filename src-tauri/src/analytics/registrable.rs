@@ -0,0 +1,145 @@
+//! Registrable domain (eTLD+1) extraction.
+//!
+//! Uses the Public Suffix List (ICANN and private sections) bundled in
+//! `public_suffix_list.dat`, so `user.github.io` and `example.co.uk` are
+//! registrable domains of their own. Refresh the file from
+//! <https://publicsuffix.org/list/public_suffix_list.dat> to pick up new suffixes.
+
+use publicsuffix::{List, Psl};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::sync::OnceLock;
+
+/// Bundled copy of the Public Suffix List.
+const PUBLIC_SUFFIX_LIST: &str = include_str!("public_suffix_list.dat");
+
+/// Parsed Public Suffix List, built on first use.
+///
+/// Should the bundled list fail to parse, the empty list still applies the
+/// default "`*`" rule: the last label of any host is its public suffix.
+fn public_suffix_list() -> &'static List {
+    static LIST: OnceLock<List> = OnceLock::new();
+    LIST.get_or_init(|| {
+        PUBLIC_SUFFIX_LIST.parse().unwrap_or_else(|e| {
+            log::error!("Invalid bundled Public Suffix List: {e}");
+            List::new()
+        })
+    })
+}
+
+/// Registrable domain (eTLD+1) of `host`, e.g. `cdn.example.co.uk` → `example.co.uk`.
+///
+/// IP addresses, single-label hosts and bare public suffixes are returned
+/// unchanged (lowercased, without trailing dot).
+#[must_use]
+pub fn registrable_domain(host: &str) -> String {
+    let host = host.trim_end_matches('.').to_lowercase();
+    if host.is_empty() || host.parse::<IpAddr>().is_ok() || host.starts_with('[') {
+        return host;
+    }
+
+    public_suffix_list()
+        .domain(host.as_bytes())
+        .and_then(|domain| std::str::from_utf8(domain.as_bytes()).ok())
+        .map_or_else(|| host.clone(), str::to_string)
+}
+
+/// Host split for display: `cdn.` (dimmed) + `example.com` (highlighted).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DomainParts {
+    /// Labels before the registrable domain, with the trailing dot (`cdn.`), or empty.
+    pub subdomain: String,
+    /// Registrable domain (eTLD+1), or the whole host when it has none.
+    pub registrable: String,
+}
+
+/// Split `host` into its subdomain and registrable domain.
+///
+/// `subdomain + registrable` is the host, lowercased and without trailing dot.
+/// IP addresses and single-label hosts have no subdomain.
+#[must_use]
+pub fn split_domain(host: &str) -> DomainParts {
+    let host = host.trim_end_matches('.').to_lowercase();
+    let registrable = registrable_domain(&host);
+    let subdomain = host
+        .strip_suffix(registrable.as_str())
+        .filter(|prefix| prefix.is_empty() || prefix.ends_with('.'))
+        .unwrap_or_default()
+        .to_string();
+    DomainParts {
+        subdomain,
+        registrable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_domains() {
+        assert_eq!(registrable_domain("a.cdn.com"), "cdn.com");
+        assert_eq!(registrable_domain("b.cdn.com"), "cdn.com");
+        assert_eq!(registrable_domain("example.com"), "example.com");
+        assert_eq!(registrable_domain("WWW.Example.COM."), "example.com");
+    }
+
+    #[test]
+    fn test_multi_label_suffixes() {
+        assert_eq!(registrable_domain("static.bbc.co.uk"), "bbc.co.uk");
+        assert_eq!(registrable_domain("bbc.co.uk"), "bbc.co.uk");
+        assert_eq!(registrable_domain("co.uk"), "co.uk");
+        assert_eq!(registrable_domain("img.shop.com.au"), "shop.com.au");
+        assert_eq!(registrable_domain("a.example.com.es"), "example.com.es");
+        assert_eq!(registrable_domain("x.example.co.at"), "example.co.at");
+        assert_eq!(registrable_domain("y.example.ac.kr"), "example.ac.kr");
+    }
+
+    #[test]
+    fn test_private_and_wildcard_suffixes() {
+        assert_eq!(registrable_domain("user.github.io"), "user.github.io");
+        assert_eq!(registrable_domain("a.user.github.io"), "user.github.io");
+        // `*.ck` with the `!www.ck` exception
+        assert_eq!(registrable_domain("a.b.c.ck"), "b.c.ck");
+        assert_eq!(registrable_domain("www.ck"), "www.ck");
+        // Unknown TLDs fall back to the last label
+        assert_eq!(
+            registrable_domain("app.example.internal"),
+            "example.internal"
+        );
+    }
+
+    #[test]
+    fn test_hosts_without_registrable_domain() {
+        assert_eq!(registrable_domain("localhost"), "localhost");
+        assert_eq!(registrable_domain("192.168.1.10"), "192.168.1.10");
+        assert_eq!(registrable_domain("::1"), "::1");
+        assert_eq!(registrable_domain(""), "");
+    }
+
+    fn parts(host: &str) -> (String, String) {
+        let parts = split_domain(host);
+        (parts.subdomain, parts.registrable)
+    }
+
+    #[test]
+    fn test_split_domain() {
+        let split = |sub: &str, reg: &str| (sub.to_string(), reg.to_string());
+        assert_eq!(parts("cdn.example.com"), split("cdn.", "example.com"));
+        assert_eq!(parts("a.b.example.com"), split("a.b.", "example.com"));
+        assert_eq!(parts("example.com"), split("", "example.com"));
+        assert_eq!(parts("static.bbc.co.uk"), split("static.", "bbc.co.uk"));
+        assert_eq!(parts("WWW.Example.COM."), split("www.", "example.com"));
+    }
+
+    #[test]
+    fn test_split_domain_without_registrable_domain() {
+        let whole = |host: &str| (String::new(), host.to_string());
+        assert_eq!(parts("localhost"), whole("localhost"));
+        assert_eq!(parts("192.168.1.10"), whole("192.168.1.10"));
+        assert_eq!(parts("::1"), whole("::1"));
+        assert_eq!(parts("co.uk"), whole("co.uk"));
+        assert_eq!(parts(""), whole(""));
+    }
+}
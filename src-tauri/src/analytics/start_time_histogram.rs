@@ -0,0 +1,212 @@
+//! Histogram of request start times (requests over time).
+
+use crate::sidecar::RequestDetail;
+use serde::{Deserialize, Serialize};
+
+/// Bucket width used when none is configured (ms).
+pub const DEFAULT_BUCKET_MS: u32 = 500;
+
+/// Upper bound on the number of buckets; wider buckets are used beyond it.
+const MAX_BUCKETS: usize = 200;
+
+/// Requests started within one time interval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartTimeBucket {
+    /// Start of the interval (ms since navigation start, inclusive).
+    pub start_ms: f64,
+    /// End of the interval (ms, exclusive).
+    pub end_ms: f64,
+    /// Number of requests started in the interval.
+    pub count: u32,
+    /// Transfer size of those requests in bytes.
+    pub transfer_size: u64,
+}
+
+/// Request start times bucketed into fixed intervals, up to the end of the load.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartTimeHistogram {
+    /// Width of each bucket (ms).
+    pub bucket_width_ms: f64,
+    /// Consecutive buckets from navigation start, empty ones included.
+    pub buckets: Vec<StartTimeBucket>,
+}
+
+impl StartTimeHistogram {
+    /// Compute the histogram with buckets of `bucket_ms` (0 uses [`DEFAULT_BUCKET_MS`]).
+    ///
+    /// Buckets cover navigation start to the last request end, widened if needed
+    /// to stay within 200 buckets.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn compute(requests: &[RequestDetail], bucket_ms: u32) -> Self {
+        let bucket_ms = if bucket_ms == 0 {
+            DEFAULT_BUCKET_MS
+        } else {
+            bucket_ms
+        };
+        let load_end = requests
+            .iter()
+            .flat_map(|r| [r.start_time, r.end_time])
+            .filter(|t| t.is_finite())
+            .fold(0.0, f64::max);
+        let bucket_width_ms = f64::from(bucket_ms).max((load_end / MAX_BUCKETS as f64).ceil());
+
+        if requests.is_empty() {
+            return Self {
+                bucket_width_ms,
+                buckets: Vec::new(),
+            };
+        }
+
+        let bucket_count = ((load_end / bucket_width_ms).floor() as usize + 1).min(MAX_BUCKETS);
+        let mut buckets: Vec<StartTimeBucket> = (0..bucket_count)
+            .map(|i| StartTimeBucket {
+                start_ms: i as f64 * bucket_width_ms,
+                end_ms: (i + 1) as f64 * bucket_width_ms,
+                count: 0,
+                transfer_size: 0,
+            })
+            .collect();
+
+        for request in requests {
+            let start = if request.start_time.is_finite() {
+                request.start_time.max(0.0)
+            } else {
+                0.0
+            };
+            let index = ((start / bucket_width_ms).floor() as usize).min(bucket_count - 1);
+            buckets[index].count += 1;
+            buckets[index].transfer_size += request.transfer_size;
+        }
+
+        Self {
+            bucket_width_ms,
+            buckets,
+        }
+    }
+
+    /// Combine the histograms of two pages.
+    ///
+    /// Buckets are summed using the wider of the two bucket widths; with equal
+    /// widths this is an exact bucket-by-bucket sum.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn merge(&self, other: &Self) -> Self {
+        let bucket_width_ms = self.bucket_width_ms.max(other.bucket_width_ms);
+        let mut buckets: Vec<StartTimeBucket> = Vec::new();
+        for bucket in self.buckets.iter().chain(&other.buckets) {
+            let index = ((bucket.start_ms / bucket_width_ms).floor() as usize).min(MAX_BUCKETS - 1);
+            while buckets.len() <= index {
+                let i = buckets.len();
+                buckets.push(StartTimeBucket {
+                    start_ms: i as f64 * bucket_width_ms,
+                    end_ms: (i + 1) as f64 * bucket_width_ms,
+                    count: 0,
+                    transfer_size: 0,
+                });
+            }
+            buckets[index].count += bucket.count;
+            buckets[index].transfer_size += bucket.transfer_size;
+        }
+
+        Self {
+            bucket_width_ms,
+            buckets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(start_time: f64, end_time: f64, transfer_size: u64) -> RequestDetail {
+        RequestDetail {
+            url: "https://example.com/a.js".to_string(),
+            transfer_size,
+            resource_size: transfer_size,
+            start_time,
+            end_time,
+            duration: end_time - start_time,
+            ..request()
+        }
+    }
+
+    #[test]
+    fn test_empty_requests() {
+        let histogram = StartTimeHistogram::compute(&[], 0);
+        assert!(histogram.buckets.is_empty());
+        assert!((histogram.bucket_width_ms - 500.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_bucket_counts_for_known_timeline() {
+        let requests = vec![
+            make_request(0.0, 200.0, 1000),
+            make_request(100.0, 300.0, 2000),
+            make_request(499.0, 700.0, 500),
+            make_request(500.0, 900.0, 100),
+            // Long tail: one late request, bucket 2000-2500 ends the load
+            make_request(1800.0, 2100.0, 50),
+        ];
+        let histogram = StartTimeHistogram::compute(&requests, 500);
+
+        let counts: Vec<u32> = histogram.buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![3, 1, 0, 1, 0]);
+        assert_eq!(histogram.buckets[0].transfer_size, 3500);
+        assert!((histogram.buckets[4].start_ms - 2000.0).abs() < f64::EPSILON);
+        assert!((histogram.buckets[4].end_ms - 2500.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_configurable_bucket_width() {
+        let requests = vec![
+            make_request(0.0, 50.0, 10),
+            make_request(150.0, 250.0, 10),
+            make_request(250.0, 260.0, 10),
+        ];
+        let histogram = StartTimeHistogram::compute(&requests, 100);
+
+        let counts: Vec<u32> = histogram.buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_bucket_count_is_capped() {
+        let requests = vec![
+            make_request(0.0, 10.0, 10),
+            make_request(1_000_000.0, 1_000_010.0, 10),
+        ];
+        let histogram = StartTimeHistogram::compute(&requests, 1);
+
+        assert!(histogram.buckets.len() <= MAX_BUCKETS);
+        assert_eq!(histogram.buckets.iter().map(|b| b.count).sum::<u32>(), 2);
+        assert_eq!(histogram.buckets.last().map(|b| b.count), Some(1));
+    }
+
+    #[test]
+    fn test_merge_rebuckets_to_wider_width() {
+        let narrow = StartTimeHistogram::compute(
+            &[make_request(0.0, 50.0, 10), make_request(150.0, 250.0, 20)],
+            100,
+        );
+        let wide = StartTimeHistogram::compute(&[make_request(300.0, 450.0, 30)], 200);
+        let merged = narrow.merge(&wide);
+
+        assert!((merged.bucket_width_ms - 200.0).abs() < f64::EPSILON);
+        let counts: Vec<u32> = merged.buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![2, 1, 0]);
+        assert_eq!(merged.buckets[0].transfer_size, 30);
+    }
+}
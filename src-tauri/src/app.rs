@@ -52,7 +52,8 @@ fn init_logger() {
 ///
 /// # Errors
 ///
-/// Returns an error if the application fails to build.
+/// Returns an error if the application fails to build, including when the
+/// data, cache or log directories cannot be created or written.
 pub fn build() -> tauri::Result<App> {
     // Initialize logging first
     init_logger();
@@ -61,18 +62,59 @@ pub fn build() -> tauri::Result<App> {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .manage(crate::sidecar::AnalysisState::default())
+        .manage(crate::commands::InFlightAnalyses::default())
         .setup(|app| {
             let version = app.package_info().version.to_string();
             let name = &app.package_info().name;
             log::info!("Starting {name} v{version}");
 
+            if let Some(paths) = AppPaths::new() {
+                // Fail loudly now rather than on the first history or cache write
+                paths.ensure_dirs().map_err(|e| {
+                    log::error!("{e}");
+                    e
+                })?;
+                let limits = crate::storage::CacheLimits::default();
+                match crate::storage::enforce_cache_limits(&paths.cache_dir, limits) {
+                    Ok(report) if report.removed_files > 0 => log::info!(
+                        "Evicted {} cache files ({} bytes)",
+                        report.removed_files,
+                        report.removed_bytes
+                    ),
+                    Ok(_) => {},
+                    Err(e) => log::warn!("Cache cleanup failed: {e}"),
+                }
+            }
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             greet,
             get_app_version,
+            app_info,
             analyze_ecoindex,
+            analyze_ecoindex_score_only,
+            analyze_ecoindex_viewports,
+            analyze_ecoindex_budget,
+            get_raw_metrics,
             analyze_lighthouse,
+            simulate_exclusion,
+            simulate_image_optimization,
+            project_impact,
+            compute_eco_debt,
+            compute_analytics_from_requests,
+            summarize_site,
+            estimate_download_times,
+            get_recommendations,
+            cancel_analysis,
+            append_monitoring_record,
+            export_json,
+            compare_to_baseline,
+            self_check,
+            clear_cache,
+            list_history,
+            get_history_entry,
+            delete_history_entry,
         ])
         .build(tauri::generate_context!())
 }
@@ -84,9 +126,17 @@ fn greet(name: &str) -> String {
 }
 
 /// Get the application version.
+///
+/// Deprecated: use `app_info`, which also returns the name, target and build profile.
 #[tauri::command]
 fn get_app_version() -> String {
-    env!("CARGO_PKG_VERSION").to_string()
+    crate::commands::app_info().version
+}
+
+/// Get the application name, version, target triple and build profile.
+#[tauri::command]
+fn app_info() -> crate::utils::diagnostics::AppInfo {
+    crate::commands::app_info()
 }
 
 /// Analyzes a URL and returns its `EcoIndex` result (fast mode, ~5s).
@@ -94,25 +144,54 @@ fn get_app_version() -> String {
 async fn analyze_ecoindex(
     app: tauri::AppHandle,
     url: String,
+    options: Option<crate::domain::AnalysisOptions>,
 ) -> Result<crate::domain::EcoIndexResult, crate::errors::BrowserError> {
-    use crate::browser::{BrowserLauncher, MetricsCollector};
-    use crate::calculator::EcoIndexCalculator;
-    use crate::utils::resolve_chrome_path;
-
-    let chrome_path = resolve_chrome_path(&app)?;
-
-    let launcher = BrowserLauncher::new(chrome_path);
-    let (browser, handler) = launcher.launch().await?;
+    crate::commands::analyze_ecoindex(app, url, options).await
+}
 
-    let collector = MetricsCollector::new(&browser);
-    let metrics = collector.collect(&url).await?;
+/// Analyzes a URL and returns only its score and grade (bulk scanning).
+#[tauri::command]
+async fn analyze_ecoindex_score_only(
+    app: tauri::AppHandle,
+    url: String,
+    options: Option<crate::domain::AnalysisOptions>,
+) -> Result<crate::domain::ScoreSummary, crate::errors::BrowserError> {
+    crate::commands::analyze_ecoindex_score_only(app, url, options).await
+}
 
-    drop(browser);
-    handler.abort();
+/// Analyzes a URL on several emulated screens (mobile, tablet, desktop by default).
+#[tauri::command]
+async fn analyze_ecoindex_viewports(
+    app: tauri::AppHandle,
+    url: String,
+    viewports: Option<Vec<crate::domain::Viewport>>,
+    options: Option<crate::domain::AnalysisOptions>,
+) -> Result<
+    std::collections::BTreeMap<String, crate::domain::EcoIndexResult>,
+    crate::errors::BrowserError,
+> {
+    crate::commands::analyze_ecoindex_viewports(app, url, viewports, options).await
+}
 
-    let result = EcoIndexCalculator::compute(&metrics, &url);
+/// Analyzes a URL and checks it against a budget, stopping at the first busting run.
+#[tauri::command]
+async fn analyze_ecoindex_budget(
+    app: tauri::AppHandle,
+    url: String,
+    budget: crate::calculator::budget::Budget,
+    options: Option<crate::domain::AnalysisOptions>,
+) -> Result<crate::calculator::budget::BudgetCheck, crate::errors::BrowserError> {
+    crate::commands::analyze_ecoindex_budget(app, url, budget, options).await
+}
 
-    Ok(result)
+/// Collects the raw metrics of a URL without computing any score.
+#[tauri::command]
+async fn get_raw_metrics(
+    app: tauri::AppHandle,
+    url: String,
+    options: Option<crate::domain::AnalysisOptions>,
+) -> Result<crate::domain::RawPageMetrics, crate::errors::BrowserError> {
+    crate::commands::get_raw_metrics(app, url, options).await
 }
 
 /// Full Lighthouse analysis with `EcoIndex` plugin (~30s).
@@ -121,6 +200,141 @@ async fn analyze_lighthouse(
     app: tauri::AppHandle,
     url: String,
     include_html: bool,
+    options: Option<crate::domain::LighthouseOptions>,
 ) -> Result<crate::sidecar::LighthouseResult, crate::errors::SidecarError> {
-    crate::commands::analyze_lighthouse(app, url, include_html).await
+    crate::commands::analyze_lighthouse(app, url, include_html, options).await
+}
+
+/// Simulates the `EcoIndex` score without requests to the given domains.
+#[tauri::command]
+fn simulate_exclusion(
+    result: crate::sidecar::LighthouseResult,
+    excluded_domains: Vec<String>,
+) -> crate::calculator::simulation::ExclusionSimulation {
+    crate::commands::simulate_exclusion(result, excluded_domains)
+}
+
+/// Simulates the `EcoIndex` score with images converted to WebP/AVIF.
+#[tauri::command]
+fn simulate_image_optimization(
+    result: crate::sidecar::LighthouseResult,
+    target_format: Option<crate::calculator::simulation::ImageTargetFormat>,
+) -> crate::calculator::simulation::ImageOptimizationSimulation {
+    crate::commands::simulate_image_optimization(result, target_format)
+}
+
+/// Projects the CO2 and water of a result over a year of traffic.
+#[tauri::command]
+fn project_impact(
+    result: crate::domain::EcoIndexResult,
+    monthly_views: u64,
+) -> crate::domain::AnnualImpact {
+    crate::commands::project_impact(result, monthly_views)
+}
+
+/// Weighs the requests of a result with custom per-type and per-party weights.
+#[tauri::command]
+fn compute_eco_debt(
+    result: crate::sidecar::LighthouseResult,
+    weights: Option<crate::analytics::DebtWeights>,
+) -> crate::analytics::EcoDebt {
+    crate::commands::compute_eco_debt(result, weights)
+}
+
+/// Computes request analytics from a request list collected elsewhere.
+#[tauri::command]
+fn compute_analytics_from_requests(
+    requests: Vec<crate::sidecar::RequestDetail>,
+    page_url: String,
+    locale: Option<crate::analytics::Locale>,
+) -> Result<crate::analytics::RequestAnalytics, crate::errors::ErrorResponse> {
+    crate::commands::compute_analytics_from_requests(requests, page_url, locale)
+}
+
+/// Summarizes the scores of several analyzed pages.
+#[tauri::command]
+fn summarize_site(
+    results: Vec<crate::domain::EcoIndexResult>,
+) -> crate::calculator::site_summary::SiteSummary {
+    crate::commands::summarize_site(results)
+}
+
+/// Estimates how long a page weight takes to download on typical connections.
+#[tauri::command]
+fn estimate_download_times(transfer_bytes: u64) -> crate::calculator::download::DownloadEstimates {
+    crate::commands::estimate_download_times(transfer_bytes)
+}
+
+/// Suggests which metric to reduce to reach the next grade.
+#[tauri::command]
+fn get_recommendations(
+    result: crate::domain::EcoIndexResult,
+    locale: Option<crate::analytics::Locale>,
+) -> Vec<crate::calculator::recommendations::Recommendation> {
+    crate::commands::get_recommendations(result, locale)
+}
+
+/// Cancels the analysis in progress.
+#[tauri::command]
+async fn cancel_analysis(app: tauri::AppHandle) -> bool {
+    crate::commands::cancel_analysis(app).await
+}
+
+/// Appends an analysis result to the per-URL monitoring file.
+#[tauri::command]
+fn append_monitoring_record(
+    url_key: String,
+    result: crate::export::MonitoredResult,
+) -> Result<String, crate::errors::ErrorResponse> {
+    crate::commands::append_monitoring_record(url_key, result)
+}
+
+/// Returns an analysis result as JSON (indented when `pretty` is set).
+#[tauri::command]
+fn export_json(
+    result: crate::export::MonitoredResult,
+    pretty: bool,
+) -> Result<String, crate::errors::ErrorResponse> {
+    crate::commands::export_json(result, pretty)
+}
+
+/// Diffs an analysis result against a baseline result saved at `baseline_path`.
+#[tauri::command]
+fn compare_to_baseline(
+    result: crate::export::MonitoredResult,
+    baseline_path: String,
+) -> Result<crate::export::BaselineComparison, crate::errors::ErrorResponse> {
+    crate::commands::compare_to_baseline(result, baseline_path)
+}
+
+/// Removes every file of the application cache directory.
+#[tauri::command]
+fn clear_cache() -> Result<crate::storage::EvictionReport, crate::errors::ErrorResponse> {
+    crate::commands::clear_cache()
+}
+
+/// Returns every readable record of the analysis history.
+#[tauri::command]
+fn list_history() -> Result<Vec<crate::storage::HistoryRecord>, crate::errors::ErrorResponse> {
+    crate::commands::list_history()
+}
+
+/// Returns the history record with the given ID.
+#[tauri::command]
+fn get_history_entry(
+    id: String,
+) -> Result<Option<crate::storage::HistoryRecord>, crate::errors::ErrorResponse> {
+    crate::commands::get_history_entry(id)
+}
+
+/// Removes the history record with the given ID.
+#[tauri::command]
+fn delete_history_entry(id: String) -> Result<bool, crate::errors::ErrorResponse> {
+    crate::commands::delete_history_entry(id)
+}
+
+/// Verifies the environment (Chrome, Lighthouse script, Node sidecar, data dirs).
+#[tauri::command]
+fn self_check(app: tauri::AppHandle) -> crate::utils::diagnostics::SelfCheckReport {
+    crate::commands::self_check(app)
 }
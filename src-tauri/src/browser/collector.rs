@@ -1,58 +1,616 @@
 //! Metrics collector for web pages using CDP.
 
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use base64::Engine;
 use chromiumoxide::browser::Browser;
+use chromiumoxide::cdp::browser_protocol::emulation::SetDeviceMetricsOverrideParams;
+use chromiumoxide::cdp::browser_protocol::fetch::{
+    AuthChallengeResponse, AuthChallengeResponseResponse, AuthChallengeSource,
+    ContinueRequestParams, ContinueWithAuthParams, EnableParams as FetchEnable, EventAuthRequired,
+    EventRequestPaused, FailRequestParams, FulfillRequestParams, HeaderEntry,
+};
 use chromiumoxide::cdp::browser_protocol::network::EnableParams as NetworkEnable;
-use chromiumoxide::cdp::browser_protocol::network::{EventLoadingFinished, EventRequestWillBeSent};
+use chromiumoxide::cdp::browser_protocol::network::{
+    ErrorReason, EventLoadingFailed, EventLoadingFinished, EventRequestWillBeSent,
+    EventResponseReceived, Headers, ResourceType, SetBlockedUrLsParams, SetCookieParams,
+    SetExtraHttpHeadersParams,
+};
+use chromiumoxide::cdp::browser_protocol::page::FrameId;
 use chromiumoxide::Page;
 use futures::StreamExt;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
-use crate::domain::PageMetrics;
+use super::interactions::{
+    click_script, push_state_script, run_interactions, run_route_change, ClickOutcome,
+    InteractionDriver,
+};
+use crate::analytics::{
+    ImageMeasurement, InlineDuplicateAnalytics, InlineElement, INLINE_PREVIEW_CHARS,
+    MIN_INLINE_BYTES,
+};
+use crate::domain::{
+    AnalysisOptions, BasicAuth, CookieSpec, InlineResources, MockResponse, PageMetrics,
+    ReadinessStrategy, RouteChange, RouteTransition, Viewport, NETWORK_IDLE_MS,
+};
 use crate::errors::BrowserError;
 
+/// Measures inline `<script>` and `<style>` content (payload of [`InlineResources`]).
+const INLINE_RESOURCES_SCRIPT: &str = r"
+    (() => {
+        const bytes = (el) => new Blob([el.textContent || '']).size;
+        const scripts = Array.from(document.querySelectorAll('script:not([src])'));
+        const styles = Array.from(document.querySelectorAll('style'));
+        return {
+            scriptCount: scripts.length,
+            scriptBytes: scripts.reduce((sum, el) => sum + bytes(el), 0),
+            styleCount: styles.length,
+            styleBytes: styles.reduce((sum, el) => sum + bytes(el), 0),
+        };
+    })()
+";
+
+/// Digests top-level inline `<svg>` markup and inline script/style text of at
+/// least `__MIN_BYTES__` bytes (payload of [`InlineElement`]).
+///
+/// Contents are hashed in the page (53-bit cyrb53, exact as a JS number): only
+/// the digest, the size and a `__PREVIEW_CHARS__`-character preview are sent.
+const INLINE_ELEMENTS_SCRIPT: &str = r"
+    (() => {
+        const hash = (text) => {
+            let h1 = 0xdeadbeef;
+            let h2 = 0x41c6ce57;
+            for (let i = 0; i < text.length; i++) {
+                const c = text.charCodeAt(i);
+                h1 = Math.imul(h1 ^ c, 2654435761);
+                h2 = Math.imul(h2 ^ c, 1597334677);
+            }
+            h1 = Math.imul(h1 ^ (h1 >>> 16), 2246822507) ^ Math.imul(h2 ^ (h2 >>> 13), 3266489909);
+            h2 = Math.imul(h2 ^ (h2 >>> 16), 2246822507) ^ Math.imul(h1 ^ (h1 >>> 13), 3266489909);
+            return 4294967296 * (2097151 & h2) + (h1 >>> 0);
+        };
+        const encoder = new TextEncoder();
+        const items = [];
+        const push = (kind, content) => {
+            const text = (content || '').trim();
+            const bytes = encoder.encode(text).length;
+            if (bytes >= __MIN_BYTES__) {
+                items.push({ kind, hash: hash(text), bytes, preview: text.slice(0, __PREVIEW_CHARS__) });
+            }
+        };
+        document.querySelectorAll('svg').forEach((el) => {
+            if (!el.parentElement || !el.parentElement.closest('svg')) push('svg', el.outerHTML);
+        });
+        document.querySelectorAll('script:not([src])').forEach((el) => push('script', el.textContent));
+        document.querySelectorAll('style').forEach((el) => push('style', el.textContent));
+        return items;
+    })()
+";
+
+/// Counts the DOM elements, SVG children excluded (one per `<svg>`).
+///
+/// With `__INCLUDE_SHADOW__` set, the elements of open shadow roots are counted
+/// too, recursively. Closed shadow roots (`shadowRoot` is `null`) are skipped.
+const DOM_COUNT_SCRIPT: &str = r"
+    (() => {
+        const includeShadow = __INCLUDE_SHADOW__;
+        const countIn = (root) => {
+            let count = 0;
+            for (const el of root.querySelectorAll('*')) {
+                if (!el.closest('svg') || el.tagName.toLowerCase() === 'svg') {
+                    count++;
+                }
+                if (includeShadow && el.shadowRoot) {
+                    count += countIn(el.shadowRoot);
+                }
+            }
+            return count;
+        };
+        return countIn(document);
+    })()
+";
+
+/// Resolves once the largest contentful paint has not changed for `__SETTLE_MS__`,
+/// or after `__TIMEOUT_MS__` with `settled: false`. Without any LCP entry (nothing
+/// painted yet) it waits for the timeout.
+const LCP_SETTLE_SCRIPT: &str = r"
+    new Promise((resolve) => {
+        let last = null;
+        let settle = null;
+        const done = (settled) => {
+            observer.disconnect();
+            clearTimeout(settle);
+            clearTimeout(deadline);
+            resolve({
+                lcpMs: last ? last.startTime : null,
+                element: last && last.element ? last.element.tagName.toLowerCase() : null,
+                settled,
+            });
+        };
+        const observer = new PerformanceObserver((list) => {
+            const entries = list.getEntries();
+            last = entries[entries.length - 1];
+            clearTimeout(settle);
+            settle = setTimeout(() => done(true), __SETTLE_MS__);
+        });
+        const deadline = setTimeout(() => done(false), __TIMEOUT_MS__);
+        observer.observe({ type: 'largest-contentful-paint', buffered: true });
+    })
+";
+
+/// Extra time given to the LCP script beyond its own timeout before giving up on it.
+const LCP_EVALUATE_MARGIN: Duration = Duration::from_secs(5);
+
+/// Maximum time to open the blank page; a stuck browser can hang here.
+const PAGE_CREATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum time for the navigation to the analyzed URL.
+const NAVIGATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Polling interval of the network-idle readiness check.
+const NETWORK_IDLE_POLL: Duration = Duration::from_millis(50);
+
+/// Maximum network-idle wait after a route change under
+/// [`ReadinessStrategy::DomContentLoaded`].
+const ROUTE_SETTLE_TIMEOUT_MS: u64 = 10_000;
+
+/// Resource Timing buffer size in Chrome; a full buffer means entries were dropped.
+const RESOURCE_TIMING_BUFFER_SIZE: u32 = 250;
+
+/// Metrics from a single collection, with diagnostics used for cross-checks.
+#[derive(Debug, Clone)]
+pub struct CollectedMetrics {
+    /// `EcoIndex` metrics (full page, after scrolling).
+    pub metrics: PageMetrics,
+    /// Metrics of the initial viewport (before scrolling), when requested.
+    pub viewport: Option<PageMetrics>,
+    /// Requests seen by the Resource Timing API (including the document),
+    /// or `None` when unavailable or truncated.
+    pub resource_timing_requests: Option<u32>,
+    /// Natural and rendered dimensions of `<img>` elements.
+    pub images: Vec<ImageMeasurement>,
+    /// Inline script/style bytes (`None` if the measurement failed).
+    pub inline: Option<InlineResources>,
+    /// Inline SVG, script and style contents repeated in the page
+    /// (`None` if the detection failed).
+    pub inline_duplicates: Option<InlineDuplicateAnalytics>,
+    /// URL of the page after redirects (`None` if unavailable).
+    pub final_url: Option<String>,
+    /// Time without network activity when metrics were collected, in milliseconds.
+    pub network_idle_ms: u64,
+    /// Whether the readiness strategy's condition was met before its timeout.
+    pub ready: bool,
+    /// Requests that failed or were cancelled (left out of `metrics.requests`).
+    pub failed_requests: u32,
+    /// MIME type of the main document, from its response headers (`None` if unknown).
+    pub content_type: Option<String>,
+    /// Cost of the configured SPA route change (`None` when not requested or skipped).
+    pub route_transition: Option<RouteTransition>,
+}
+
+/// File extensions of images and media blocked with `block_images`.
+const BLOCKED_MEDIA_EXTENSIONS: [&str; 14] = [
+    "png", "jpg", "jpeg", "gif", "webp", "avif", "svg", "ico", "bmp", "mp4", "webm", "ogg", "mp3",
+    "wav",
+];
+
+/// Request count and transferred bytes observed on the network so far.
+#[derive(Debug, Clone, Default)]
+struct NetworkCounters {
+    requests: Arc<AtomicU32>,
+    /// Counted requests that later failed (no `loadingFinished`, so no size).
+    failed: Arc<AtomicU32>,
+    transfer_bytes: Arc<AtomicU64>,
+    /// Ids of requests left out of the metrics (blocked images/media).
+    excluded: Arc<Mutex<HashSet<String>>>,
+    /// Time of the last request start or finish.
+    last_activity: Arc<Mutex<Option<Instant>>>,
+}
+
+impl NetworkCounters {
+    /// Count a new request, unless it is an image/media request and `block_media` is set.
+    fn record_request(
+        &self,
+        request_id: &str,
+        resource_type: Option<&ResourceType>,
+        block_media: bool,
+    ) {
+        self.touch();
+        if block_media && is_media(resource_type) {
+            if let Ok(mut excluded) = self.excluded.lock() {
+                excluded.insert(request_id.to_string());
+            }
+            return;
+        }
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Add the bytes of a finished request, unless it was excluded.
+    fn record_finished(&self, request_id: &str, encoded_bytes: u64) {
+        self.touch();
+        let excluded = self
+            .excluded
+            .lock()
+            .is_ok_and(|excluded| excluded.contains(request_id));
+        if !excluded {
+            self.transfer_bytes
+                .fetch_add(encoded_bytes, Ordering::Relaxed);
+        }
+    }
+
+    /// Record a failed or cancelled request, unless it was a blocked image/media request.
+    ///
+    /// Failed requests are subtracted from the request count at snapshot time,
+    /// so the count matches the requests whose size was measured.
+    fn record_failed(&self, resource_type: Option<&ResourceType>, block_media: bool) {
+        self.touch();
+        if block_media && is_media(resource_type) {
+            return;
+        }
+        self.failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of failed requests so far.
+    fn failed_requests(&self) -> u32 {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    /// Record network activity now.
+    fn touch(&self) {
+        if let Ok(mut last_activity) = self.last_activity.lock() {
+            *last_activity = Some(Instant::now());
+        }
+    }
+
+    /// Time since the last network activity at `now` (`Duration::MAX` if none was seen).
+    fn idle_for(&self, now: Instant) -> Duration {
+        self.last_activity
+            .lock()
+            .ok()
+            .and_then(|last_activity| *last_activity)
+            .map_or(Duration::MAX, |last| now.saturating_duration_since(last))
+    }
+
+    /// Metrics at this point of the load, given the current DOM count and HTML size.
+    #[allow(clippy::cast_precision_loss)]
+    fn snapshot(&self, dom_count: u32, html_size: u64) -> PageMetrics {
+        let requests = self
+            .requests
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.failed_requests());
+        let size_bytes = self.transfer_bytes.load(Ordering::Relaxed) + html_size;
+        let size_kb = size_bytes as f64 / 1024.0;
+        PageMetrics::new(dom_count, requests, size_kb)
+    }
+}
+
+/// Fail when nothing was loaded: no request and an (almost) empty DOM.
+///
+/// Navigation can "succeed" without loading anything (DNS failure, offline,
+/// blocked), which would otherwise be scored as a perfect page.
+fn check_page_loaded(metrics: PageMetrics, max_empty_dom: u32) -> Result<(), BrowserError> {
+    if metrics.requests == 0 && metrics.dom_elements <= max_empty_dom {
+        return Err(BrowserError::PageLoadFailed(format!(
+            "no request captured and only {} DOM elements, the page did not load",
+            metrics.dom_elements
+        )));
+    }
+    Ok(())
+}
+
+/// Document responses seen so far, with the frame that received them.
+type DocumentLog = Arc<Mutex<Vec<(Option<FrameId>, String)>>>;
+
+/// MIME type of the document loaded in the main frame.
+///
+/// The last main-frame document wins (client-side navigations replace it);
+/// without a known main frame, the first document response is used.
+fn main_document_type(
+    documents: &[(Option<FrameId>, String)],
+    main_frame: Option<&FrameId>,
+) -> Option<String> {
+    main_frame
+        .map_or_else(
+            || documents.first(),
+            |main_frame| {
+                documents
+                    .iter()
+                    .rev()
+                    .find(|(frame, _)| frame.as_ref() == Some(main_frame))
+            },
+        )
+        .map(|(_, mime)| mime.clone())
+}
+
+/// Last largest-contentful-paint candidate seen by [`LCP_SETTLE_SCRIPT`].
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LcpObservation {
+    /// Render time of the candidate (ms since navigation start), `None` if nothing painted.
+    lcp_ms: Option<f64>,
+    /// Tag name of the candidate element (`img`, `p`...).
+    element: Option<String>,
+    /// Whether the candidate stopped changing before the timeout.
+    settled: bool,
+}
+
+/// Wait after navigation according to `strategy`.
+///
+/// Returns whether the strategy's condition was met (network idle, LCP
+/// settled) rather than given up on; plain waits always succeed.
+async fn wait_until_ready(
+    page: &Page,
+    counters: &NetworkCounters,
+    strategy: ReadinessStrategy,
+) -> bool {
+    match strategy.clamped() {
+        ReadinessStrategy::FixedWait { ms } => {
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+            true
+        },
+        ReadinessStrategy::NetworkIdle {
+            idle_ms,
+            timeout_ms,
+        } => {
+            let idle = wait_for_network_idle(
+                counters,
+                Duration::from_millis(idle_ms),
+                Duration::from_millis(timeout_ms),
+            )
+            .await;
+            if !idle {
+                log::warn!("Network not idle after {timeout_ms} ms, collecting anyway");
+            }
+            idle
+        },
+        ReadinessStrategy::DomContentLoaded => true,
+        ReadinessStrategy::LargestContentfulPaint {
+            settle_ms,
+            timeout_ms,
+        } => {
+            let script = LCP_SETTLE_SCRIPT
+                .replace("__SETTLE_MS__", &settle_ms.to_string())
+                .replace("__TIMEOUT_MS__", &timeout_ms.to_string());
+            let observe = async {
+                page.evaluate(script)
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .into_value::<LcpObservation>()
+                    .map_err(|e| e.to_string())
+            };
+            let timeout = Duration::from_millis(timeout_ms);
+            match wait_for_lcp(observe, timeout, LCP_EVALUATE_MARGIN).await {
+                Some(LcpObservation {
+                    lcp_ms: Some(lcp_ms),
+                    element,
+                    settled: true,
+                }) => {
+                    log::info!(
+                        "LCP settled at {lcp_ms:.0} ms ({})",
+                        element.as_deref().unwrap_or("unknown element")
+                    );
+                    true
+                },
+                Some(_) => {
+                    log::warn!("LCP not settled after {timeout_ms} ms, collecting anyway");
+                    false
+                },
+                None => false,
+            }
+        },
+    }
+}
+
+/// Readiness strategy applied after a SPA route change.
+///
+/// A `pushState` navigation fires no `DOMContentLoaded`, so that strategy
+/// would not wait at all: the network is left to go idle instead.
+const fn route_settle_strategy(readiness: ReadinessStrategy) -> ReadinessStrategy {
+    match readiness {
+        ReadinessStrategy::DomContentLoaded => ReadinessStrategy::NetworkIdle {
+            idle_ms: NETWORK_IDLE_MS,
+            timeout_ms: ROUTE_SETTLE_TIMEOUT_MS,
+        },
+        other => other,
+    }
+}
+
+/// Run the LCP observer `observe`, bounded by `timeout` plus `margin`.
+///
+/// When the observer cannot run, falls back to a blind wait until `timeout`;
+/// when it hangs, gives up after the margin. Both return `None`.
+async fn wait_for_lcp(
+    observe: impl Future<Output = Result<LcpObservation, String>>,
+    timeout: Duration,
+    margin: Duration,
+) -> Option<LcpObservation> {
+    let start = Instant::now();
+    match tokio::time::timeout(timeout + margin, observe).await {
+        Ok(Ok(observation)) => Some(observation),
+        Ok(Err(e)) => {
+            log::warn!("LCP observer failed ({e}), waiting {timeout:?} instead");
+            tokio::time::sleep(timeout.saturating_sub(start.elapsed())).await;
+            None
+        },
+        Err(_) => {
+            log::warn!("LCP observer did not answer, collecting anyway");
+            None
+        },
+    }
+}
+
+/// Replays interactions on a page with the collector's evaluate/scroll helpers.
+struct PageDriver<'a> {
+    collector: &'a MetricsCollector<'a>,
+    page: &'a Page,
+}
+
+impl InteractionDriver for PageDriver<'_> {
+    async fn click(&mut self, selector: &str) -> Result<ClickOutcome, BrowserError> {
+        let status = self
+            .page
+            .evaluate(click_script(selector))
+            .await
+            .map_err(|e| BrowserError::JavaScriptError(e.to_string()))?
+            .into_value::<String>()
+            .map_err(|e| BrowserError::JavaScriptError(e.to_string()))?;
+        Ok(ClickOutcome::parse(&status))
+    }
+
+    async fn scroll(&mut self) -> Result<(), BrowserError> {
+        self.collector.scroll_to_bottom(self.page).await
+    }
+
+    async fn push_state(&mut self, path: &str) -> Result<(), BrowserError> {
+        self.page
+            .evaluate(push_state_script(path))
+            .await
+            .map_err(|e| BrowserError::JavaScriptError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Wait until `counters` saw no activity for `idle`, or `timeout` elapsed.
+///
+/// Returns whether the network became idle.
+async fn wait_for_network_idle(
+    counters: &NetworkCounters,
+    idle: Duration,
+    timeout: Duration,
+) -> bool {
+    let start = Instant::now();
+    loop {
+        let now = Instant::now();
+        if counters.idle_for(now) >= idle {
+            return true;
+        }
+        if now.duration_since(start) >= timeout {
+            return false;
+        }
+        tokio::time::sleep(NETWORK_IDLE_POLL).await;
+    }
+}
+
+/// [`INLINE_ELEMENTS_SCRIPT`] with the analytics thresholds filled in.
+fn inline_elements_script() -> String {
+    INLINE_ELEMENTS_SCRIPT
+        .replace("__MIN_BYTES__", &MIN_INLINE_BYTES.to_string())
+        .replace("__PREVIEW_CHARS__", &INLINE_PREVIEW_CHARS.to_string())
+}
+
+/// [`DOM_COUNT_SCRIPT`] counting the light DOM only, or open shadow roots too.
+fn dom_count_script(include_shadow_dom: bool) -> String {
+    DOM_COUNT_SCRIPT.replace("__INCLUDE_SHADOW__", &include_shadow_dom.to_string())
+}
+
+/// Whether a request is an image or media request (blocked with `block_images`).
+const fn is_media(resource_type: Option<&ResourceType>) -> bool {
+    matches!(
+        resource_type,
+        Some(ResourceType::Image | ResourceType::Media)
+    )
+}
+
 /// Collects page metrics following the `EcoIndex` protocol.
 pub struct MetricsCollector<'a> {
     browser: &'a Browser,
+    options: AnalysisOptions,
+    cancel_token: CancellationToken,
 }
 
 impl<'a> MetricsCollector<'a> {
     /// Creates a new collector for the given browser.
     #[must_use]
-    pub const fn new(browser: &'a Browser) -> Self {
-        Self { browser }
+    pub fn new(browser: &'a Browser) -> Self {
+        Self {
+            browser,
+            options: AnalysisOptions::default(),
+            cancel_token: CancellationToken::new(),
+        }
+    }
+
+    /// Sets the analysis options (extra headers, basic auth, viewport snapshot, image blocking).
+    #[must_use]
+    pub fn with_options(mut self, options: AnalysisOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the token used to cancel an in-progress collection.
+    #[must_use]
+    pub fn with_cancel_token(mut self, cancel_token: CancellationToken) -> Self {
+        self.cancel_token = cancel_token;
+        self
     }
 
     /// Collects metrics from a URL following the `EcoIndex` protocol.
     ///
     /// Protocol:
     /// 1. Open page at 1920x1080
-    /// 2. Wait 3 seconds
+    /// 2. Wait 3 seconds (or per `options.readiness`)
     /// 3. Scroll to bottom
     /// 4. Wait 3 seconds
     /// 5. Collect metrics
     ///
+    /// With `viewport_snapshot` enabled, metrics are also captured after step 2
+    /// (initial viewport, before scrolling).
+    ///
+    /// With `block_images` enabled, images and media are blocked and left out of
+    /// the request count and size (structure-only "code weight").
+    ///
+    /// With `route_change` set, the client-side navigation is performed after
+    /// the final measurement and its cost is reported separately.
+    ///
     /// # Errors
     ///
-    /// Returns an error if navigation or metric collection fails.
+    /// Returns an error if navigation or metric collection fails, or
+    /// `BrowserError::Cancelled` if the cancel token fires first.
     pub async fn collect(&self, url: &str) -> Result<PageMetrics, BrowserError> {
-        let page = self
-            .browser
-            .new_page("about:blank")
-            .await
-            .map_err(|e| BrowserError::PageCreationFailed(e.to_string()))?;
+        self.collect_detailed(url).await.map(|c| c.metrics)
+    }
 
-        page.execute(NetworkEnable::default())
-            .await
-            .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+    /// Same as [`Self::collect`], also returning diagnostics such as the
+    /// Resource Timing request count.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::collect`].
+    pub async fn collect_detailed(&self, url: &str) -> Result<CollectedMetrics, BrowserError> {
+        run_cancellable(&self.cancel_token, self.collect_metrics(url)).await
+    }
+
+    async fn collect_metrics(&self, url: &str) -> Result<CollectedMetrics, BrowserError> {
+        let page = timed_page_creation(PAGE_CREATION_TIMEOUT, self.browser.new_page("about:blank"))
+            .await?;
 
-        let request_count = Arc::new(AtomicU32::new(0));
-        let total_size = Arc::new(AtomicU64::new(0));
+        self.prepare_page(&page, url).await?;
 
-        let req_counter = Arc::clone(&request_count);
-        let size_counter = Arc::clone(&total_size);
+        let proxy_auth = self
+            .options
+            .proxy_config()
+            .ok()
+            .flatten()
+            .and_then(|proxy| proxy.credentials);
+
+        // Mocked pages never reach the network, so no auth challenge can occur
+        let fetch_handle = if !self.options.mock_responses.is_empty() {
+            Some(Self::spawn_mock_handler(&page, self.options.mock_responses.clone()).await?)
+        } else if self.options.basic_auth.is_some() || proxy_auth.is_some() {
+            let server_auth = self.options.basic_auth.clone();
+            Some(Self::spawn_auth_handler(&page, server_auth, proxy_auth).await?)
+        } else {
+            None
+        };
+
+        let counters = NetworkCounters::default();
+        let req_counter = counters.clone();
+        let size_counter = counters.clone();
+        let block_media = self.options.block_images;
 
         let mut request_events = page
             .event_listener::<EventRequestWillBeSent>()
@@ -64,43 +622,333 @@ impl<'a> MetricsCollector<'a> {
             .await
             .map_err(|e| BrowserError::CdpError(e.to_string()))?;
 
+        let mut failed_events = page
+            .event_listener::<EventLoadingFailed>()
+            .await
+            .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+
+        let (documents, doc_handle) = Self::spawn_document_tracker(&page).await?;
+
         let req_handle = tokio::spawn(async move {
-            while (request_events.next().await).is_some() {
-                req_counter.fetch_add(1, Ordering::Relaxed);
+            while let Some(event) = request_events.next().await {
+                req_counter.record_request(
+                    event.request_id.as_ref(),
+                    event.r#type.as_ref(),
+                    block_media,
+                );
             }
         });
 
         let size_handle = tokio::spawn(async move {
-            while let Some(event) = finished_events.next().await {
-                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-                size_counter.fetch_add(event.encoded_data_length as u64, Ordering::Relaxed);
+            loop {
+                tokio::select! {
+                    Some(event) = finished_events.next() => {
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        size_counter.record_finished(
+                            event.request_id.as_ref(),
+                            event.encoded_data_length as u64,
+                        );
+                    },
+                    Some(event) = failed_events.next() => {
+                        size_counter.record_failed(Some(&event.r#type), block_media);
+                    },
+                    else => break,
+                }
             }
         });
 
-        page.goto(url)
-            .await
-            .map_err(|e| BrowserError::NavigationFailed(e.to_string()))?;
+        timed_navigation(NAVIGATION_TIMEOUT, page.goto(url)).await?;
 
-        tokio::time::sleep(Duration::from_secs(3)).await;
+        let ready = wait_until_ready(&page, &counters, self.options.readiness).await;
+
+        let viewport = if self.options.viewport_snapshot {
+            let dom_count = self.count_dom_elements(&page).await?;
+            let html_size = self.get_html_size(&page).await?;
+            Some(counters.snapshot(dom_count, html_size))
+        } else {
+            None
+        };
 
         self.scroll_to_bottom(&page).await?;
 
+        if !self.options.interactions.is_empty() {
+            let mut driver = PageDriver {
+                collector: self,
+                page: &page,
+            };
+            let performed = run_interactions(&mut driver, &self.options.interactions).await?;
+            log::debug!(
+                "Performed {performed}/{} interactions",
+                self.options.interactions.len()
+            );
+        }
+
         tokio::time::sleep(Duration::from_secs(3)).await;
 
         let dom_count = self.count_dom_elements(&page).await?;
         let html_size = self.get_html_size(&page).await?;
+        let resource_timing_requests = self.count_resource_timing_entries(&page).await;
+        let images = if self.options.block_images {
+            Vec::new()
+        } else {
+            self.measure_images(&page).await
+        };
+        let inline = self.measure_inline_resources(&page).await;
+        let inline_duplicates = self.detect_inline_duplicates(&page).await;
+        let final_url = page.url().await.ok().flatten();
+        let network_idle_ms =
+            u64::try_from(counters.idle_for(Instant::now()).as_millis()).unwrap_or(u64::MAX);
+
+        let main_frame = page.mainframe().await.ok().flatten();
+        let content_type = documents
+            .lock()
+            .ok()
+            .and_then(|documents| main_document_type(&documents, main_frame.as_ref()));
+
+        let metrics = counters.snapshot(dom_count, html_size);
+        let route_transition = match &self.options.route_change {
+            Some(route) => {
+                self.measure_route_change(&page, &counters, metrics, route)
+                    .await?
+            },
+            None => None,
+        };
 
         req_handle.abort();
         size_handle.abort();
-
-        let requests = request_count.load(Ordering::Relaxed);
-        let size_bytes = total_size.load(Ordering::Relaxed) + html_size;
-        #[allow(clippy::cast_precision_loss)]
-        let size_kb = size_bytes as f64 / 1024.0;
+        doc_handle.abort();
+        if let Some(handle) = fetch_handle {
+            handle.abort();
+        }
 
         let _ = page.close().await;
 
-        Ok(PageMetrics::new(dom_count, requests, size_kb))
+        check_page_loaded(metrics, self.options.empty_page_dom_threshold())?;
+
+        Ok(CollectedMetrics {
+            metrics,
+            viewport,
+            resource_timing_requests,
+            images,
+            inline,
+            inline_duplicates,
+            final_url,
+            network_idle_ms,
+            ready,
+            failed_requests: counters.failed_requests(),
+            content_type,
+            route_transition,
+        })
+    }
+
+    /// Performs the SPA route change and measures what it loaded on top of `before`.
+    ///
+    /// The page is left to settle with the same readiness strategy as the
+    /// initial load, or until the network is idle when that strategy does not
+    /// wait (see [`route_settle_strategy`]). Returns `None` when the route
+    /// change was skipped.
+    async fn measure_route_change(
+        &self,
+        page: &Page,
+        counters: &NetworkCounters,
+        before: PageMetrics,
+        route: &RouteChange,
+    ) -> Result<Option<RouteTransition>, BrowserError> {
+        let mut driver = PageDriver {
+            collector: self,
+            page,
+        };
+        if !run_route_change(&mut driver, route).await? {
+            return Ok(None);
+        }
+        wait_until_ready(
+            page,
+            counters,
+            route_settle_strategy(self.options.readiness),
+        )
+        .await;
+
+        let dom_count = self.count_dom_elements(page).await?;
+        let html_size = self.get_html_size(page).await?;
+        Ok(Some(RouteTransition::between(
+            before,
+            counters.snapshot(dom_count, html_size),
+        )))
+    }
+
+    /// Applies the options that must be set before navigation (network,
+    /// headers, cookies, viewport, media blocking).
+    async fn prepare_page(&self, page: &Page, url: &str) -> Result<(), BrowserError> {
+        page.execute(NetworkEnable::default())
+            .await
+            .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+
+        if let Some(params) = extra_headers_params(&self.options.extra_headers) {
+            let mut names: Vec<&String> = self.options.extra_headers.keys().collect();
+            names.sort_unstable();
+            log::debug!("Sending extra headers: {names:?}");
+            page.execute(params)
+                .await
+                .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+        }
+
+        if !self.options.cookies.is_empty() {
+            let cookies = cookie_params(&self.options.cookies, url)?;
+            // Names only: cookie values are session secrets
+            let names: Vec<&str> = cookies.iter().map(|c| c.name.as_str()).collect();
+            log::debug!("Setting cookies: {names:?}");
+            for params in cookies {
+                page.execute(params)
+                    .await
+                    .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+            }
+        }
+
+        if let Some(viewport) = &self.options.viewport {
+            log::debug!(
+                "Emulating viewport {} ({}x{})",
+                viewport.name,
+                viewport.width,
+                viewport.height
+            );
+            page.execute(device_metrics_params(viewport)?)
+                .await
+                .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+        }
+
+        if self.options.block_images {
+            log::debug!("Blocking images and media");
+            page.execute(SetBlockedUrLsParams::new(blocked_media_patterns()))
+                .await
+                .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the frame and MIME type of every document response of `page`.
+    async fn spawn_document_tracker(
+        page: &Page,
+    ) -> Result<(DocumentLog, JoinHandle<()>), BrowserError> {
+        let mut response_events = page
+            .event_listener::<EventResponseReceived>()
+            .await
+            .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+
+        let documents = DocumentLog::default();
+        let recorder = Arc::clone(&documents);
+        let handle = tokio::spawn(async move {
+            while let Some(event) = response_events.next().await {
+                if event.r#type == ResourceType::Document {
+                    if let Ok(mut documents) = recorder.lock() {
+                        documents.push((event.frame_id.clone(), event.response.mime_type.clone()));
+                    }
+                }
+            }
+        });
+        Ok((documents, handle))
+    }
+
+    /// Enables the `Fetch` domain and answers auth challenges: site challenges
+    /// with `server_auth`, proxy challenges with `proxy_auth`.
+    ///
+    /// Paused requests are resumed untouched. A request challenged a second time
+    /// (wrong credentials) or without matching credentials is cancelled instead of looping.
+    async fn spawn_auth_handler(
+        page: &Page,
+        server_auth: Option<BasicAuth>,
+        proxy_auth: Option<BasicAuth>,
+    ) -> Result<JoinHandle<()>, BrowserError> {
+        let mut paused_events = page
+            .event_listener::<EventRequestPaused>()
+            .await
+            .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+
+        let mut auth_events = page
+            .event_listener::<EventAuthRequired>()
+            .await
+            .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+
+        page.execute(FetchEnable::builder().handle_auth_requests(true).build())
+            .await
+            .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+
+        for (source, auth) in [("site", &server_auth), ("proxy", &proxy_auth)] {
+            if let Some(auth) = auth {
+                log::debug!("Basic auth enabled for {source} user '{}'", auth.username);
+            }
+        }
+
+        let page = page.clone();
+        Ok(tokio::spawn(async move {
+            let mut attempted = HashSet::new();
+            loop {
+                tokio::select! {
+                    Some(event) = paused_events.next() => {
+                        let _ = page
+                            .execute(ContinueRequestParams::new(event.request_id.clone()))
+                            .await;
+                    },
+                    Some(event) = auth_events.next() => {
+                        let first_attempt = attempted.insert(event.request_id.inner().clone());
+                        let auth = match event.auth_challenge.source {
+                            Some(AuthChallengeSource::Proxy) => proxy_auth.as_ref(),
+                            _ => server_auth.as_ref(),
+                        };
+                        let response = auth_challenge_response(auth, first_attempt);
+                        let _ = page
+                            .execute(ContinueWithAuthParams::new(
+                                event.request_id.clone(),
+                                response,
+                            ))
+                            .await;
+                    },
+                    else => break,
+                }
+            }
+        }))
+    }
+
+    /// Enables the `Fetch` domain and serves every request from `mocks`.
+    ///
+    /// Requests without a fixture fail as if the network was unreachable.
+    async fn spawn_mock_handler(
+        page: &Page,
+        mocks: HashMap<String, MockResponse>,
+    ) -> Result<JoinHandle<()>, BrowserError> {
+        let mut paused_events = page
+            .event_listener::<EventRequestPaused>()
+            .await
+            .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+
+        page.execute(FetchEnable::default())
+            .await
+            .map_err(|e| BrowserError::CdpError(e.to_string()))?;
+
+        log::debug!("Mock mode enabled with {} fixtures", mocks.len());
+
+        let page = page.clone();
+        Ok(tokio::spawn(async move {
+            while let Some(event) = paused_events.next().await {
+                let request_id = event.request_id.clone();
+                let result = if let Some(mock) = find_mock(&mocks, &event.request.url) {
+                    page.execute(mock_fulfill_params(request_id, mock))
+                        .await
+                        .map(|_| ())
+                } else {
+                    log::debug!("No fixture for {}, failing request", event.request.url);
+                    page.execute(FailRequestParams::new(
+                        request_id,
+                        ErrorReason::InternetDisconnected,
+                    ))
+                    .await
+                    .map(|_| ())
+                };
+                if let Err(e) = result {
+                    log::warn!("Failed to answer mocked request: {e}");
+                }
+            }
+        }))
     }
 
     async fn scroll_to_bottom(&self, page: &Page) -> Result<(), BrowserError> {
@@ -111,27 +959,94 @@ impl<'a> MetricsCollector<'a> {
     }
 
     async fn count_dom_elements(&self, page: &Page) -> Result<u32, BrowserError> {
+        let result = page
+            .evaluate(dom_count_script(self.options.include_shadow_dom))
+            .await
+            .map_err(|e| BrowserError::JavaScriptError(e.to_string()))?;
+
+        result
+            .into_value::<u32>()
+            .map_err(|e| BrowserError::JavaScriptError(e.to_string()))
+    }
+
+    /// Counts Resource Timing entries plus the document itself.
+    ///
+    /// Returns `None` if the script fails or the buffer is full (entries dropped).
+    async fn count_resource_timing_entries(&self, page: &Page) -> Option<u32> {
+        let entries = page
+            .evaluate("performance.getEntriesByType('resource').length")
+            .await
+            .ok()?
+            .into_value::<u32>()
+            .ok()?;
+        (entries < RESOURCE_TIMING_BUFFER_SIZE).then_some(entries + 1)
+    }
+
+    /// Measures natural vs rendered size of every `<img>`.
+    ///
+    /// Byte sizes come from Resource Timing (0 for cross-origin images without
+    /// `Timing-Allow-Origin`). Returns an empty list if the script fails.
+    async fn measure_images(&self, page: &Page) -> Vec<ImageMeasurement> {
         let result = page
             .evaluate(
                 r"
                 (() => {
-                    let count = 0;
-                    const allElements = document.querySelectorAll('*');
-                    for (const el of allElements) {
-                        if (!el.closest('svg') || el.tagName.toLowerCase() === 'svg') {
-                            count++;
-                        }
-                    }
-                    return count;
+                    const dpr = window.devicePixelRatio || 1;
+                    return Array.from(document.images).map((img) => {
+                        const rect = img.getBoundingClientRect();
+                        const url = img.currentSrc || img.src;
+                        const entry = performance.getEntriesByName(url)[0];
+                        return {
+                            url,
+                            naturalWidth: img.naturalWidth,
+                            naturalHeight: img.naturalHeight,
+                            displayWidth: rect.width,
+                            displayHeight: rect.height,
+                            devicePixelRatio: dpr,
+                            transferSize: entry ? (entry.encodedBodySize || 0) : 0,
+                        };
+                    });
                 })()
             ",
             )
+            .await;
+
+        let images = result.map_err(|e| e.to_string()).and_then(|r| {
+            r.into_value::<Vec<ImageMeasurement>>()
+                .map_err(|e| e.to_string())
+        });
+        images.unwrap_or_else(|e| {
+            log::warn!("Could not measure images: {e}");
+            Vec::new()
+        })
+    }
+
+    /// Measures inline script and style bytes. Returns `None` if the script fails.
+    async fn measure_inline_resources(&self, page: &Page) -> Option<InlineResources> {
+        let inline = page
+            .evaluate(INLINE_RESOURCES_SCRIPT)
             .await
-            .map_err(|e| BrowserError::JavaScriptError(e.to_string()))?;
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.into_value::<InlineResources>().map_err(|e| e.to_string()));
+        inline
+            .map_err(|e| log::warn!("Could not measure inline resources: {e}"))
+            .ok()
+    }
 
-        result
-            .into_value::<u32>()
-            .map_err(|e| BrowserError::JavaScriptError(e.to_string()))
+    /// Detects repeated inline contents. Returns `None` if the script fails.
+    async fn detect_inline_duplicates(&self, page: &Page) -> Option<InlineDuplicateAnalytics> {
+        let elements = page
+            .evaluate(inline_elements_script())
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| {
+                r.into_value::<Vec<InlineElement>>()
+                    .map_err(|e| e.to_string())
+            });
+        elements
+            .map(|elements| InlineDuplicateAnalytics::compute(&elements))
+            .map_err(|e| log::warn!("Could not detect inline duplicates: {e}"))
+            .ok()
     }
 
     async fn get_html_size(&self, page: &Page) -> Result<u64, BrowserError> {
@@ -145,3 +1060,884 @@ impl<'a> MetricsCollector<'a> {
             .map_err(|e| BrowserError::JavaScriptError(e.to_string()))
     }
 }
+
+/// Run `future` until it completes or `token` is cancelled, whichever comes first.
+async fn run_cancellable<T>(
+    token: &CancellationToken,
+    future: impl Future<Output = Result<T, BrowserError>>,
+) -> Result<T, BrowserError> {
+    tokio::select! {
+        biased;
+        () = token.cancelled() => Err(BrowserError::Cancelled),
+        result = future => result,
+    }
+}
+
+/// Create a page, failing with `PageCreationFailed` if it takes longer than `timeout`.
+async fn timed_page_creation<T, E: std::fmt::Display>(
+    timeout: Duration,
+    creation: impl Future<Output = Result<T, E>>,
+) -> Result<T, BrowserError> {
+    tokio::time::timeout(timeout, creation)
+        .await
+        .map_err(|_| {
+            BrowserError::PageCreationFailed(format!("timed out after {}ms", timeout.as_millis()))
+        })?
+        .map_err(|e| BrowserError::PageCreationFailed(e.to_string()))
+}
+
+/// Navigate, failing with `NavigationTimeout` if it takes longer than `timeout`.
+async fn timed_navigation<T, E: std::fmt::Display>(
+    timeout: Duration,
+    navigation: impl Future<Output = Result<T, E>>,
+) -> Result<T, BrowserError> {
+    #[allow(clippy::cast_possible_truncation)]
+    tokio::time::timeout(timeout, navigation)
+        .await
+        .map_err(|_| BrowserError::NavigationTimeout(timeout.as_millis() as u64))?
+        .map_err(|e| BrowserError::NavigationFailed(e.to_string()))
+}
+
+/// Find the fixture for `url`, comparing URLs in normalized form
+/// (`https://a.test` matches `https://a.test/`).
+fn find_mock<'m>(mocks: &'m HashMap<String, MockResponse>, url: &str) -> Option<&'m MockResponse> {
+    if let Some(mock) = mocks.get(url) {
+        return Some(mock);
+    }
+    let normalized = url::Url::parse(url).ok()?;
+    mocks
+        .iter()
+        .find(|(key, _)| url::Url::parse(key).is_ok_and(|key| key == normalized))
+        .map(|(_, mock)| mock)
+}
+
+/// Build the `Fetch.fulfillRequest` command answering `request_id` with `mock`.
+fn mock_fulfill_params(
+    request_id: chromiumoxide::cdp::browser_protocol::fetch::RequestId,
+    mock: &MockResponse,
+) -> FulfillRequestParams {
+    let mut headers: Vec<HeaderEntry> = mock
+        .headers
+        .iter()
+        .map(|(name, value)| HeaderEntry::new(name.clone(), value.clone()))
+        .collect();
+    headers.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut params = FulfillRequestParams::new(request_id, i64::from(mock.status));
+    params.response_headers = Some(headers);
+    params.body = Some(
+        base64::engine::general_purpose::STANDARD
+            .encode(mock.body.as_bytes())
+            .into(),
+    );
+    params
+}
+
+/// URL patterns passed to `Network.setBlockedURLs` to block images and media.
+fn blocked_media_patterns() -> Vec<String> {
+    BLOCKED_MEDIA_EXTENSIONS
+        .iter()
+        .flat_map(|ext| [format!("*.{ext}"), format!("*.{ext}?*")])
+        .collect()
+}
+
+/// Build the `Network.setExtraHTTPHeaders` command, or `None` when there are no headers.
+fn extra_headers_params(headers: &HashMap<String, String>) -> Option<SetExtraHttpHeadersParams> {
+    if headers.is_empty() {
+        return None;
+    }
+    let map: serde_json::Map<String, serde_json::Value> = headers
+        .iter()
+        .map(|(name, value)| (name.clone(), serde_json::Value::String(value.clone())))
+        .collect();
+    Some(SetExtraHttpHeadersParams::new(Headers::new(map)))
+}
+
+/// Device metrics override emulating `viewport`.
+fn device_metrics_params(
+    viewport: &Viewport,
+) -> Result<SetDeviceMetricsOverrideParams, BrowserError> {
+    viewport.validate().map_err(BrowserError::InvalidViewport)?;
+    Ok(SetDeviceMetricsOverrideParams::new(
+        i64::from(viewport.width),
+        i64::from(viewport.height),
+        viewport.device_scale_factor,
+        viewport.mobile,
+    ))
+}
+
+/// Build the `Network.setCookie` commands for `cookies`, set on `page_url`.
+///
+/// Cookies without a domain are bound to the analyzed URL.
+fn cookie_params(
+    cookies: &[CookieSpec],
+    page_url: &str,
+) -> Result<Vec<SetCookieParams>, BrowserError> {
+    cookies
+        .iter()
+        .map(|cookie| {
+            cookie.validate().map_err(BrowserError::InvalidCookie)?;
+            let mut params = SetCookieParams::new(cookie.name.clone(), cookie.value.clone());
+            if cookie.domain.is_none() {
+                params.url = Some(page_url.to_string());
+            }
+            params.domain.clone_from(&cookie.domain);
+            params.path = Some(cookie.path.clone().unwrap_or_else(|| "/".to_string()));
+            params.secure = Some(cookie.secure);
+            params.http_only = Some(cookie.http_only);
+            Ok(params)
+        })
+        .collect()
+}
+
+/// Build the answer to an auth challenge: credentials on first attempt, cancel
+/// afterwards or when there are no credentials for the challenger.
+fn auth_challenge_response(auth: Option<&BasicAuth>, first_attempt: bool) -> AuthChallengeResponse {
+    match auth {
+        Some(auth) if first_attempt => AuthChallengeResponse {
+            response: AuthChallengeResponseResponse::ProvideCredentials,
+            username: Some(auth.username.clone()),
+            password: Some(auth.password.clone()),
+        },
+        _ => AuthChallengeResponse::new(AuthChallengeResponseResponse::CancelAuth),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_cancellable_aborts_promptly() {
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            canceller.cancel();
+        });
+
+        let start = Instant::now();
+        let result = run_cancellable(&token, async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        })
+        .await;
+
+        assert!(matches!(result, Err(BrowserError::Cancelled)));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_main_document_type_ignores_iframes() {
+        let main = FrameId::new("main");
+        let documents = vec![
+            (Some(main.clone()), "text/html".to_string()),
+            (Some(FrameId::new("ad")), "application/pdf".to_string()),
+        ];
+        assert_eq!(
+            main_document_type(&documents, Some(&main)).as_deref(),
+            Some("text/html")
+        );
+        // Unknown main frame: the first document is the navigation's
+        assert_eq!(
+            main_document_type(&documents, None).as_deref(),
+            Some("text/html")
+        );
+        assert!(main_document_type(&[], Some(&main)).is_none());
+    }
+
+    #[test]
+    fn test_main_document_type_after_client_navigation() {
+        let main = FrameId::new("main");
+        let documents = vec![
+            (Some(main.clone()), "text/html".to_string()),
+            (Some(main.clone()), "application/pdf".to_string()),
+        ];
+        assert_eq!(
+            main_document_type(&documents, Some(&main)).as_deref(),
+            Some("application/pdf")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_cancellable_completes() {
+        let token = CancellationToken::new();
+        let result = run_cancellable(&token, async { Ok(42) }).await;
+        assert!(matches!(result, Ok(42)));
+    }
+
+    #[tokio::test]
+    async fn test_hung_page_creation_times_out() {
+        let hung = std::future::pending::<Result<(), String>>();
+        let result = timed_page_creation(Duration::from_millis(20), hung).await;
+        assert!(
+            matches!(result, Err(BrowserError::PageCreationFailed(ref msg)) if msg.contains("20ms"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hung_navigation_times_out() {
+        let hung = std::future::pending::<Result<(), String>>();
+        let result = timed_navigation(Duration::from_millis(20), hung).await;
+        assert!(matches!(result, Err(BrowserError::NavigationTimeout(20))));
+    }
+
+    #[tokio::test]
+    async fn test_timed_steps_keep_their_error_mapping() {
+        let created = timed_page_creation(Duration::from_secs(1), async { Ok::<_, String>(1) });
+        assert!(matches!(created.await, Ok(1)));
+
+        let failed = timed_navigation(Duration::from_secs(1), async {
+            Err::<(), _>("net::ERR_NAME_NOT_RESOLVED")
+        });
+        assert!(matches!(
+            failed.await,
+            Err(BrowserError::NavigationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_network_counters_two_phase_snapshots() {
+        let counters = NetworkCounters::default();
+        counters.requests.fetch_add(10, Ordering::Relaxed);
+        counters
+            .transfer_bytes
+            .fetch_add(100 * 1024, Ordering::Relaxed);
+        let viewport = counters.snapshot(300, 1024);
+
+        // Lazy-loaded content after scrolling
+        counters.requests.fetch_add(5, Ordering::Relaxed);
+        counters
+            .transfer_bytes
+            .fetch_add(50 * 1024, Ordering::Relaxed);
+        let full_page = counters.snapshot(400, 2048);
+
+        assert_eq!(viewport, PageMetrics::new(300, 10, 101.0));
+        assert_eq!(full_page, PageMetrics::new(400, 15, 152.0));
+    }
+
+    #[test]
+    fn test_blocked_images_excluded_from_metrics() {
+        let counters = NetworkCounters::default();
+        counters.record_request("doc", Some(&ResourceType::Document), true);
+        counters.record_request("js", Some(&ResourceType::Script), true);
+        counters.record_request("img", Some(&ResourceType::Image), true);
+        counters.record_request("video", Some(&ResourceType::Media), true);
+        counters.record_finished("doc", 10 * 1024);
+        counters.record_finished("js", 20 * 1024);
+        // An image that slipped through the URL patterns is still left out
+        counters.record_finished("img", 500 * 1024);
+
+        assert_eq!(counters.snapshot(100, 0), PageMetrics::new(100, 2, 30.0));
+    }
+
+    #[test]
+    fn test_images_counted_without_blocking() {
+        let counters = NetworkCounters::default();
+        counters.record_request("doc", Some(&ResourceType::Document), false);
+        counters.record_request("img", Some(&ResourceType::Image), false);
+        counters.record_finished("doc", 10 * 1024);
+        counters.record_finished("img", 500 * 1024);
+
+        assert_eq!(counters.snapshot(100, 0), PageMetrics::new(100, 2, 510.0));
+    }
+
+    #[test]
+    fn test_empty_page_is_load_failure() {
+        let empty = PageMetrics::new(3, 0, 0.0);
+        assert!(matches!(
+            check_page_loaded(empty, 3),
+            Err(BrowserError::PageLoadFailed(_))
+        ));
+        assert!(check_page_loaded(PageMetrics::new(0, 0, 0.0), 3).is_err());
+    }
+
+    #[test]
+    fn test_loaded_pages_pass_check() {
+        // Requests captured
+        assert!(check_page_loaded(PageMetrics::new(3, 1, 0.5), 3).is_ok());
+        // Content without requests (e.g. served from a mock or inline)
+        assert!(check_page_loaded(PageMetrics::new(50, 0, 2.0), 3).is_ok());
+        // Threshold is configurable
+        assert!(check_page_loaded(PageMetrics::new(3, 0, 0.0), 0).is_ok());
+    }
+
+    #[test]
+    fn test_failed_requests_left_out_of_count() {
+        let counters = NetworkCounters::default();
+        counters.record_request("doc", Some(&ResourceType::Document), false);
+        counters.record_request("js", Some(&ResourceType::Script), false);
+        counters.record_request("api", Some(&ResourceType::Fetch), false);
+        counters.record_request("font", Some(&ResourceType::Font), false);
+        counters.record_finished("doc", 10 * 1024);
+        counters.record_finished("js", 20 * 1024);
+        counters.record_failed(Some(&ResourceType::Fetch), false);
+        counters.record_failed(Some(&ResourceType::Font), false);
+
+        assert_eq!(counters.failed_requests(), 2);
+        assert_eq!(counters.snapshot(100, 0), PageMetrics::new(100, 2, 30.0));
+    }
+
+    #[test]
+    fn test_blocked_media_failures_not_counted_as_failed() {
+        let counters = NetworkCounters::default();
+        counters.record_request("doc", Some(&ResourceType::Document), true);
+        counters.record_request("img", Some(&ResourceType::Image), true);
+        counters.record_finished("doc", 10 * 1024);
+        // Blocked by URL pattern: reported as a loading failure
+        counters.record_failed(Some(&ResourceType::Image), true);
+
+        assert_eq!(counters.failed_requests(), 0);
+        assert_eq!(counters.snapshot(100, 0), PageMetrics::new(100, 1, 10.0));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_network_idle_after_activity_stops() {
+        let counters = NetworkCounters::default();
+        counters.record_request("doc", Some(&ResourceType::Document), false);
+        let activity = counters.clone();
+        let simulated = tokio::spawn(async move {
+            for i in 0..10 {
+                activity.record_request(&i.to_string(), Some(&ResourceType::Script), false);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        });
+
+        let start = Instant::now();
+        let idle = wait_for_network_idle(
+            &counters,
+            Duration::from_millis(100),
+            Duration::from_secs(5),
+        )
+        .await;
+        let _ = simulated.await;
+
+        assert!(idle);
+        // At least the quiet period after the last simulated request
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_network_idle_times_out_on_busy_network() {
+        let counters = NetworkCounters::default();
+        counters.record_request("doc", Some(&ResourceType::Document), false);
+        let activity = counters.clone();
+        let simulated = tokio::spawn(async move {
+            for i in 0..50 {
+                activity.record_request(&i.to_string(), Some(&ResourceType::Fetch), false);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        });
+
+        let idle = wait_for_network_idle(
+            &counters,
+            Duration::from_millis(200),
+            Duration::from_millis(150),
+        )
+        .await;
+        simulated.abort();
+
+        assert!(!idle);
+    }
+
+    #[test]
+    fn test_route_change_settles_without_readiness_wait() {
+        assert_eq!(
+            route_settle_strategy(ReadinessStrategy::DomContentLoaded),
+            ReadinessStrategy::NetworkIdle {
+                idle_ms: NETWORK_IDLE_MS,
+                timeout_ms: ROUTE_SETTLE_TIMEOUT_MS,
+            }
+        );
+        for strategy in [
+            ReadinessStrategy::default(),
+            ReadinessStrategy::NetworkIdle {
+                idle_ms: 1000,
+                timeout_ms: 5000,
+            },
+            ReadinessStrategy::LargestContentfulPaint {
+                settle_ms: 500,
+                timeout_ms: 5000,
+            },
+        ] {
+            assert_eq!(route_settle_strategy(strategy), strategy);
+        }
+    }
+
+    #[test]
+    fn test_lcp_observation_parsing() {
+        let settled: Result<LcpObservation, _> =
+            serde_json::from_str(r#"{"lcpMs":1234.5,"element":"img","settled":true}"#);
+        assert!(settled.is_ok_and(|o| o
+            == LcpObservation {
+                lcp_ms: Some(1234.5),
+                element: Some("img".to_string()),
+                settled: true,
+            }));
+
+        // Timed out before anything painted
+        let timed_out: Result<LcpObservation, _> =
+            serde_json::from_str(r#"{"lcpMs":null,"element":null,"settled":false}"#);
+        assert!(timed_out.is_ok_and(|o| o.lcp_ms.is_none() && !o.settled));
+
+        assert!(LCP_SETTLE_SCRIPT.contains("__SETTLE_MS__"));
+        assert!(LCP_SETTLE_SCRIPT.contains("__TIMEOUT_MS__"));
+    }
+
+    #[test]
+    fn test_dom_count_script_modes() {
+        let light = dom_count_script(false);
+        assert!(light.contains("const includeShadow = false;"));
+        assert!(!light.contains("__INCLUDE_SHADOW__"));
+
+        let shadow = dom_count_script(true);
+        assert!(shadow.contains("const includeShadow = true;"));
+        assert!(shadow.contains("countIn(el.shadowRoot)"));
+
+        // Light DOM by default, like the official EcoIndex
+        assert!(!AnalysisOptions::default().include_shadow_dom);
+        let options: Result<AnalysisOptions, _> =
+            serde_json::from_str(r#"{"includeShadowDom":true}"#);
+        assert!(options.is_ok_and(|o| o.include_shadow_dom));
+    }
+
+    #[tokio::test]
+    async fn test_lcp_wait_falls_back_to_timeout() {
+        let timeout = Duration::from_millis(100);
+        let margin = Duration::from_millis(100);
+
+        // Observer failed: blind wait for the whole timeout
+        let start = Instant::now();
+        let failed = wait_for_lcp(async { Err("not supported".to_string()) }, timeout, margin);
+        assert!(failed.await.is_none());
+        assert!(start.elapsed() >= timeout);
+
+        // Observer hung: give up after the margin
+        let start = Instant::now();
+        assert!(wait_for_lcp(std::future::pending(), timeout, margin)
+            .await
+            .is_none());
+        assert!(start.elapsed() >= timeout + margin);
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // Observer answered: no extra wait
+        let observed = LcpObservation {
+            lcp_ms: Some(800.0),
+            element: None,
+            settled: true,
+        };
+        let start = Instant::now();
+        let answer = wait_for_lcp(async { Ok(observed.clone()) }, timeout, margin).await;
+        assert_eq!(answer, Some(observed));
+        assert!(start.elapsed() < timeout);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_network_idle_when_already_idle() {
+        let counters = NetworkCounters::default();
+        let idle = wait_for_network_idle(
+            &counters,
+            Duration::from_millis(100),
+            Duration::from_secs(5),
+        )
+        .await;
+        assert!(idle);
+    }
+
+    #[test]
+    fn test_network_idle_without_activity() {
+        let counters = NetworkCounters::default();
+        assert_eq!(counters.idle_for(Instant::now()), Duration::MAX);
+    }
+
+    #[test]
+    fn test_network_idle_vs_active() {
+        let counters = NetworkCounters::default();
+        counters.record_request("doc", Some(&ResourceType::Document), false);
+
+        // Still active: the request just started
+        assert!(counters.idle_for(Instant::now()) < Duration::from_secs(1));
+
+        // Idle: nothing happened for the last 2 seconds
+        let later = Instant::now() + Duration::from_secs(2);
+        assert!(counters.idle_for(later) >= Duration::from_secs(2));
+
+        // A finished request is activity too
+        counters.record_finished("doc", 1024);
+        assert!(counters.idle_for(Instant::now()) < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_inline_elements_script_matches_payload() {
+        let script = inline_elements_script();
+        assert!(!script.contains("__"));
+        assert!(script.contains(">= 256"));
+        for key in ["kind", "hash", "bytes", "preview"] {
+            assert!(script.contains(key), "{key}");
+        }
+        // The largest 53-bit hash still fits
+        let payload = serde_json::json!([
+            { "kind": "svg", "hash": 9_007_199_254_740_991_u64, "bytes": 300, "preview": "<svg>" },
+            { "kind": "script", "hash": 42, "bytes": 256, "preview": "console.log(1)" },
+        ]);
+        let elements: Vec<InlineElement> = serde_json::from_value(payload).unwrap_or_default();
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[0].hash, 9_007_199_254_740_991);
+        assert_eq!(elements[1].preview, "console.log(1)");
+    }
+
+    #[test]
+    fn test_inline_resources_script_matches_payload() {
+        for key in ["scriptCount", "scriptBytes", "styleCount", "styleBytes"] {
+            assert!(INLINE_RESOURCES_SCRIPT.contains(key), "{key}");
+        }
+        let payload = serde_json::json!({
+            "scriptCount": 3,
+            "scriptBytes": 12_000,
+            "styleCount": 2,
+            "styleBytes": 800,
+        });
+        let inline: Option<InlineResources> = serde_json::from_value(payload).ok();
+        assert_eq!(
+            inline,
+            Some(InlineResources {
+                script_count: 3,
+                script_bytes: 12_000,
+                style_count: 2,
+                style_bytes: 800,
+            })
+        );
+    }
+
+    #[test]
+    fn test_blocked_media_patterns() {
+        let patterns = blocked_media_patterns();
+        assert!(patterns.contains(&"*.png".to_string()));
+        assert!(patterns.contains(&"*.jpg?*".to_string()));
+        assert_eq!(patterns.len(), BLOCKED_MEDIA_EXTENSIONS.len() * 2);
+    }
+
+    fn mocks() -> HashMap<String, MockResponse> {
+        HashMap::from([
+            (
+                "https://mock.test/".to_string(),
+                MockResponse {
+                    headers: HashMap::from([("content-type".to_string(), "text/html".to_string())]),
+                    body: r#"<html><body><div>a</div><div>b</div><img src="/a.png"></body></html>"#
+                        .to_string(),
+                    ..MockResponse::default()
+                },
+            ),
+            (
+                "https://mock.test/a.png".to_string(),
+                MockResponse {
+                    headers: HashMap::from([("content-type".to_string(), "image/png".to_string())]),
+                    body: "x".repeat(2048),
+                    ..MockResponse::default()
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_find_mock_normalizes_urls() {
+        let mocks = mocks();
+        assert!(find_mock(&mocks, "https://mock.test/").is_some());
+        assert!(find_mock(&mocks, "https://mock.test").is_some());
+        assert!(find_mock(&mocks, "https://MOCK.test/a.png").is_some());
+        assert!(find_mock(&mocks, "https://mock.test/missing.js").is_none());
+        assert!(find_mock(&mocks, "not a url").is_none());
+    }
+
+    #[test]
+    fn test_mock_fulfill_params() {
+        let mock = MockResponse {
+            status: 404,
+            headers: HashMap::from([
+                ("x-b".to_string(), "2".to_string()),
+                ("x-a".to_string(), "1".to_string()),
+            ]),
+            body: "hi".to_string(),
+        };
+        let params = mock_fulfill_params(
+            chromiumoxide::cdp::browser_protocol::fetch::RequestId::new("req-1"),
+            &mock,
+        );
+        assert_eq!(params.response_code, 404);
+        let names: Vec<&str> = params
+            .response_headers
+            .iter()
+            .flatten()
+            .map(|h| h.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["x-a", "x-b"]);
+        let body: Option<String> = params.body.map(Into::into);
+        assert_eq!(body.as_deref(), Some("aGk="));
+    }
+
+    /// Analyzes a fully mocked page twice with a real Chrome (`CHROME_PATH`).
+    #[tokio::test]
+    #[ignore = "requires Chrome: set CHROME_PATH"]
+    async fn test_mocked_page_metrics_are_deterministic() {
+        let Some(chrome_path) = std::env::var_os("CHROME_PATH") else {
+            return;
+        };
+        let options = AnalysisOptions {
+            mock_responses: mocks(),
+            ..AnalysisOptions::default()
+        };
+
+        let mut results = Vec::new();
+        for _ in 0..2 {
+            let launcher = crate::browser::BrowserLauncher::new(chrome_path.clone().into());
+            let started = launcher.launch().await;
+            assert!(started.is_ok(), "failed to launch Chrome");
+            let Ok((browser, handler)) = started else {
+                return;
+            };
+            let metrics = MetricsCollector::new(&browser)
+                .with_options(options.clone())
+                .collect("https://mock.test/")
+                .await;
+            handler.abort();
+            results.push(metrics.ok());
+        }
+
+        let first = results[0];
+        assert!(first.is_some());
+        assert_eq!(first, results[1]);
+        assert!(first.is_some_and(|m| m.requests == 2 && m.dom_elements >= 6));
+    }
+
+    /// Follows a mocked redirect with a real Chrome (`CHROME_PATH`).
+    #[tokio::test]
+    #[ignore = "requires Chrome: set CHROME_PATH"]
+    async fn test_final_url_after_redirect() {
+        let Some(chrome_path) = std::env::var_os("CHROME_PATH") else {
+            return;
+        };
+        let mut mock_responses = mocks();
+        mock_responses.insert(
+            "http://mock.test/old".to_string(),
+            MockResponse {
+                status: 301,
+                headers: HashMap::from([(
+                    "location".to_string(),
+                    "https://mock.test/".to_string(),
+                )]),
+                body: String::new(),
+            },
+        );
+        let options = AnalysisOptions {
+            mock_responses,
+            ..AnalysisOptions::default()
+        };
+
+        let launcher = crate::browser::BrowserLauncher::new(chrome_path.into());
+        let started = launcher.launch().await;
+        assert!(started.is_ok(), "failed to launch Chrome");
+        let Ok((browser, handler)) = started else {
+            return;
+        };
+        let collected = MetricsCollector::new(&browser)
+            .with_options(options)
+            .collect_detailed("http://mock.test/old")
+            .await;
+        handler.abort();
+
+        let final_url = collected.ok().and_then(|c| c.final_url);
+        assert_eq!(final_url.as_deref(), Some("https://mock.test/"));
+    }
+
+    /// Page with a large icon `<svg>` pasted three times, one inline script and one stylesheet.
+    fn inline_page_mocks() -> HashMap<String, MockResponse> {
+        let icon = format!(
+            r#"<svg viewBox="0 0 24 24"><path d="{}"></path></svg>"#,
+            "M0 0h24v24H0z".repeat(30)
+        );
+        let body = format!(
+            "<html><head><style>{}</style></head><body>{icon}{icon}{icon}<script>{}</script></body></html>",
+            "p { margin: 0; }".repeat(20),
+            "var a = 1;".repeat(40)
+        );
+        HashMap::from([(
+            "https://mock.test/".to_string(),
+            MockResponse {
+                headers: HashMap::from([("content-type".to_string(), "text/html".to_string())]),
+                body,
+                ..MockResponse::default()
+            },
+        )])
+    }
+
+    /// Runs the inline-content scripts on a mocked page with a real Chrome (`CHROME_PATH`).
+    #[tokio::test]
+    #[ignore = "requires Chrome: set CHROME_PATH"]
+    async fn test_inline_duplicates_on_real_page() {
+        let Some(chrome_path) = std::env::var_os("CHROME_PATH") else {
+            return;
+        };
+        let options = AnalysisOptions {
+            mock_responses: inline_page_mocks(),
+            ..AnalysisOptions::default()
+        };
+
+        let launcher = crate::browser::BrowserLauncher::new(chrome_path.into());
+        let started = launcher.launch().await;
+        assert!(started.is_ok(), "failed to launch Chrome");
+        let Ok((browser, handler)) = started else {
+            return;
+        };
+        let collected = MetricsCollector::new(&browser)
+            .with_options(options)
+            .collect_detailed("https://mock.test/")
+            .await;
+        handler.abort();
+
+        let duplicates = collected.ok().and_then(|c| c.inline_duplicates);
+        assert!(duplicates.is_some());
+        let Some(duplicates) = duplicates else { return };
+        // Three icons, the script and the stylesheet, all above the threshold
+        assert_eq!(duplicates.checked_count, 5);
+        assert_eq!(duplicates.groups.len(), 1);
+        let icon = &duplicates.groups[0];
+        assert_eq!(icon.kind, "svg");
+        assert_eq!(icon.occurrences, 3);
+        assert_eq!(icon.wasted_bytes, 2 * icon.bytes);
+        assert!(icon.preview.starts_with("<svg"));
+        assert_eq!(icon.preview.chars().count(), INLINE_PREVIEW_CHARS);
+    }
+
+    /// Measures inline script and style bytes on a mocked page with a real Chrome (`CHROME_PATH`).
+    #[tokio::test]
+    #[ignore = "requires Chrome: set CHROME_PATH"]
+    async fn test_inline_resources_on_real_page() {
+        let Some(chrome_path) = std::env::var_os("CHROME_PATH") else {
+            return;
+        };
+        let options = AnalysisOptions {
+            mock_responses: inline_page_mocks(),
+            ..AnalysisOptions::default()
+        };
+
+        let launcher = crate::browser::BrowserLauncher::new(chrome_path.into());
+        let started = launcher.launch().await;
+        assert!(started.is_ok(), "failed to launch Chrome");
+        let Ok((browser, handler)) = started else {
+            return;
+        };
+        let collected = MetricsCollector::new(&browser)
+            .with_options(options)
+            .collect_detailed("https://mock.test/")
+            .await;
+        handler.abort();
+
+        let inline = collected.ok().and_then(|c| c.inline);
+        assert_eq!(
+            inline,
+            Some(InlineResources {
+                script_count: 1,
+                script_bytes: 400,
+                style_count: 1,
+                style_bytes: 320,
+            })
+        );
+    }
+
+    #[test]
+    fn test_cookie_params_from_specs() {
+        let cookies = vec![
+            CookieSpec {
+                name: "session".to_string(),
+                value: "abc".to_string(),
+                domain: Some(".example.com".to_string()),
+                path: Some("/app".to_string()),
+                secure: true,
+                http_only: true,
+            },
+            CookieSpec {
+                name: "lang".to_string(),
+                value: "fr".to_string(),
+                ..CookieSpec::default()
+            },
+        ];
+        let params = cookie_params(&cookies, "https://example.com/app/home");
+        assert!(params.is_ok());
+        let Ok(params) = params else { return };
+
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].name, "session");
+        assert_eq!(params[0].value, "abc");
+        assert_eq!(params[0].domain.as_deref(), Some(".example.com"));
+        assert_eq!(params[0].path.as_deref(), Some("/app"));
+        assert!(params[0].url.is_none());
+        assert_eq!(params[0].secure, Some(true));
+        assert_eq!(params[0].http_only, Some(true));
+
+        // No domain: bound to the analyzed URL, root path
+        assert_eq!(
+            params[1].url.as_deref(),
+            Some("https://example.com/app/home")
+        );
+        assert!(params[1].domain.is_none());
+        assert_eq!(params[1].path.as_deref(), Some("/"));
+    }
+
+    #[test]
+    fn test_cookie_params_rejects_invalid_spec() {
+        let cookies = vec![CookieSpec {
+            name: "session".to_string(),
+            value: "abc".to_string(),
+            domain: Some("https://example.com".to_string()),
+            ..CookieSpec::default()
+        }];
+        let params = cookie_params(&cookies, "https://example.com/");
+        assert!(matches!(params, Err(BrowserError::InvalidCookie(_))));
+        // The value never appears in the error
+        assert!(params.is_err_and(|e| !e.to_string().contains("abc")));
+    }
+
+    #[test]
+    fn test_extra_headers_params_empty() {
+        assert!(extra_headers_params(&HashMap::new()).is_none());
+    }
+
+    #[test]
+    fn test_extra_headers_params_from_map() {
+        let headers = HashMap::from([
+            ("X-Feature-Flag".to_string(), "beta".to_string()),
+            ("X-Bypass-Token".to_string(), "abc123".to_string()),
+        ]);
+        let params = extra_headers_params(&headers);
+
+        let expected = serde_json::json!({
+            "X-Feature-Flag": "beta",
+            "X-Bypass-Token": "abc123",
+        });
+        assert_eq!(params.map(|p| p.headers.inner().clone()), Some(expected));
+    }
+
+    #[test]
+    fn test_auth_challenge_response() {
+        let auth = BasicAuth {
+            username: "user".to_string(),
+            password: "pass".to_string(),
+        };
+
+        let first = auth_challenge_response(Some(&auth), true);
+        assert_eq!(
+            first.response,
+            AuthChallengeResponseResponse::ProvideCredentials
+        );
+        assert_eq!(first.username.as_deref(), Some("user"));
+        assert_eq!(first.password.as_deref(), Some("pass"));
+
+        let retry = auth_challenge_response(Some(&auth), false);
+        assert_eq!(retry.response, AuthChallengeResponseResponse::CancelAuth);
+        assert!(retry.password.is_none());
+
+        let unknown = auth_challenge_response(None, true);
+        assert_eq!(unknown.response, AuthChallengeResponseResponse::CancelAuth);
+    }
+}
@@ -0,0 +1,263 @@
+//! Replay of user interactions (click, wait, scroll) before measurement, and
+//! client-side route changes measured after it.
+
+use std::time::Duration;
+
+use crate::domain::{Interaction, RouteChange, MAX_READINESS_WAIT_MS};
+use crate::errors::BrowserError;
+
+/// Result of a click in the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ClickOutcome {
+    /// The element was found and clicked.
+    Clicked,
+    /// No element matches the selector.
+    Missing,
+    /// The browser rejected the selector syntax.
+    Invalid,
+}
+
+impl ClickOutcome {
+    /// Parse the status returned by [`click_script`].
+    pub(super) fn parse(status: &str) -> Self {
+        match status {
+            "clicked" => Self::Clicked,
+            "invalid" => Self::Invalid,
+            _ => Self::Missing,
+        }
+    }
+}
+
+/// Page operations needed to replay interactions.
+pub(super) trait InteractionDriver {
+    /// Click the first element matching `selector`.
+    async fn click(&mut self, selector: &str) -> Result<ClickOutcome, BrowserError>;
+
+    /// Scroll to the bottom of the page.
+    async fn scroll(&mut self) -> Result<(), BrowserError>;
+
+    /// Push `path` to the history and notify the router.
+    async fn push_state(&mut self, path: &str) -> Result<(), BrowserError>;
+}
+
+/// Script clicking the first element matching `selector`.
+///
+/// Returns `"clicked"`, `"missing"` or `"invalid"` (selector syntax error).
+pub(super) fn click_script(selector: &str) -> String {
+    // JSON string literals are valid JavaScript string literals
+    let selector = serde_json::Value::String(selector.to_string());
+    format!(
+        r"
+        (() => {{
+            let el;
+            try {{
+                el = document.querySelector({selector});
+            }} catch (e) {{
+                return 'invalid';
+            }}
+            if (!el) return 'missing';
+            el.scrollIntoView({{ block: 'center' }});
+            el.click();
+            return 'clicked';
+        }})()
+        "
+    )
+}
+
+/// Script pushing `path` to the history, then dispatching `popstate` so
+/// routers listening to history changes render the new route.
+pub(super) fn push_state_script(path: &str) -> String {
+    let path = serde_json::Value::String(path.to_string());
+    format!(
+        r"
+        (() => {{
+            history.pushState({{}}, '', {path});
+            window.dispatchEvent(new PopStateEvent('popstate', {{ state: {{}} }}));
+        }})()
+        "
+    )
+}
+
+/// Perform a client-side route change and return whether it happened.
+///
+/// Like interactions, an invalid route or a click on a missing element is
+/// skipped with a warning; only browser failures are errors.
+pub(super) async fn run_route_change(
+    driver: &mut impl InteractionDriver,
+    route: &RouteChange,
+) -> Result<bool, BrowserError> {
+    if let Err(e) = route.validate() {
+        log::warn!("Skipping route change: {e}");
+        return Ok(false);
+    }
+    match route {
+        RouteChange::Click { selector } => match driver.click(selector).await? {
+            ClickOutcome::Clicked => Ok(true),
+            ClickOutcome::Missing => {
+                log::warn!("Skipping route change: no element matches {selector:?}");
+                Ok(false)
+            },
+            ClickOutcome::Invalid => {
+                log::warn!("Skipping route change: invalid selector {selector:?}");
+                Ok(false)
+            },
+        },
+        RouteChange::PushState { path } => {
+            driver.push_state(path).await?;
+            Ok(true)
+        },
+    }
+}
+
+/// Replay `interactions` in order and return how many were performed.
+///
+/// Invalid actions and clicks on missing elements are skipped with a warning;
+/// only browser failures abort the sequence.
+pub(super) async fn run_interactions(
+    driver: &mut impl InteractionDriver,
+    interactions: &[Interaction],
+) -> Result<usize, BrowserError> {
+    let mut performed = 0;
+    for (index, interaction) in interactions.iter().enumerate() {
+        if let Err(e) = interaction.validate() {
+            log::warn!("Skipping interaction {index}: {e}");
+            continue;
+        }
+        match interaction {
+            Interaction::Click { selector } => match driver.click(selector).await? {
+                ClickOutcome::Clicked => performed += 1,
+                ClickOutcome::Missing => {
+                    log::warn!("Skipping interaction {index}: no element matches {selector:?}");
+                },
+                ClickOutcome::Invalid => {
+                    log::warn!("Skipping interaction {index}: invalid selector {selector:?}");
+                },
+            },
+            Interaction::Wait { ms } => {
+                tokio::time::sleep(Duration::from_millis((*ms).min(MAX_READINESS_WAIT_MS))).await;
+                performed += 1;
+            },
+            Interaction::Scroll => {
+                driver.scroll().await?;
+                performed += 1;
+            },
+        }
+    }
+    Ok(performed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the operations it receives; selectors starting with `#missing`
+    /// match nothing, `[` is a syntax error.
+    #[derive(Default)]
+    struct RecordingDriver {
+        calls: Vec<String>,
+    }
+
+    impl InteractionDriver for RecordingDriver {
+        async fn click(&mut self, selector: &str) -> Result<ClickOutcome, BrowserError> {
+            self.calls.push(format!("click {selector}"));
+            Ok(if selector.starts_with("#missing") {
+                ClickOutcome::Missing
+            } else if selector == "[" {
+                ClickOutcome::Invalid
+            } else {
+                ClickOutcome::Clicked
+            })
+        }
+
+        async fn scroll(&mut self) -> Result<(), BrowserError> {
+            self.calls.push("scroll".to_string());
+            Ok(())
+        }
+
+        async fn push_state(&mut self, path: &str) -> Result<(), BrowserError> {
+            self.calls.push(format!("push {path}"));
+            Ok(())
+        }
+    }
+
+    fn click(selector: &str) -> Interaction {
+        Interaction::Click {
+            selector: selector.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interactions_run_in_order() {
+        let mut driver = RecordingDriver::default();
+        let interactions = vec![
+            click("#menu"),
+            Interaction::Wait { ms: 10 },
+            Interaction::Scroll,
+            click(".load-more"),
+        ];
+        let performed = run_interactions(&mut driver, &interactions).await;
+
+        assert!(matches!(performed, Ok(4)));
+        assert_eq!(
+            driver.calls,
+            vec!["click #menu", "scroll", "click .load-more"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_and_invalid_interactions_are_skipped() {
+        let mut driver = RecordingDriver::default();
+        let interactions = vec![
+            click("#missing"),
+            click("  "),
+            click("["),
+            Interaction::Scroll,
+        ];
+        let performed = run_interactions(&mut driver, &interactions).await;
+
+        assert!(matches!(performed, Ok(1)));
+        // The empty selector never reaches the page
+        assert_eq!(driver.calls, vec!["click #missing", "click [", "scroll"]);
+    }
+
+    #[tokio::test]
+    async fn test_route_change() {
+        let mut driver = RecordingDriver::default();
+        let push = RouteChange::PushState {
+            path: "/cart".to_string(),
+        };
+        let click = RouteChange::Click {
+            selector: "a.next".to_string(),
+        };
+        let missing = RouteChange::Click {
+            selector: "#missing-link".to_string(),
+        };
+
+        assert!(matches!(
+            run_route_change(&mut driver, &push).await,
+            Ok(true)
+        ));
+        assert!(matches!(
+            run_route_change(&mut driver, &click).await,
+            Ok(true)
+        ));
+        assert!(matches!(
+            run_route_change(&mut driver, &missing).await,
+            Ok(false)
+        ));
+        assert_eq!(
+            driver.calls,
+            vec!["push /cart", "click a.next", "click #missing-link"]
+        );
+        assert!(push_state_script(r#"/a"b"#).contains(r#"history.pushState({}, '', "/a\"b")"#));
+    }
+
+    #[test]
+    fn test_click_script_quotes_selector() {
+        let script = click_script(r#"a[href="x"]'"#);
+        assert!(script.contains(r#"document.querySelector("a[href=\"x\"]'")"#));
+        assert_eq!(ClickOutcome::parse("clicked"), ClickOutcome::Clicked);
+        assert_eq!(ClickOutcome::parse("invalid"), ClickOutcome::Invalid);
+        assert_eq!(ClickOutcome::parse("missing"), ClickOutcome::Missing);
+    }
+}
@@ -3,54 +3,152 @@
 use std::path::{Path, PathBuf};
 
 use chromiumoxide::browser::{Browser, BrowserConfig};
-use futures::StreamExt;
+use futures::{Stream, StreamExt};
 use tokio::task::JoinHandle;
 
+use crate::domain::{ChromeLimits, HeadlessMode};
 use crate::errors::BrowserError;
 
+/// Chrome flags used for every launch (the headless flag is added per mode).
+const LAUNCH_ARGS: [&str; 11] = [
+    "--disable-gpu",
+    "--disable-dev-shm-usage",
+    "--disable-extensions",
+    "--disable-background-networking",
+    "--disable-sync",
+    "--disable-translate",
+    "--disable-default-apps",
+    "--no-first-run",
+    "--window-size=1920,1080",
+    "--hide-scrollbars",
+    "--mute-audio",
+];
+
+/// Handler errors meaning the browser is gone (matched case-insensitively).
+///
+/// Without them the analysis would wait for page events until it times out.
+const FATAL_SIGNATURES: [&str; 8] = [
+    "connection closed",
+    "closed connection",
+    "broken pipe",
+    "connection reset",
+    "target crashed",
+    "check failed",
+    "sigsegv",
+    "sigtrap",
+];
+
 /// Launches and manages Chrome browser instances.
 pub struct BrowserLauncher {
     chrome_path: PathBuf,
+    headless: HeadlessMode,
+    proxy_server: Option<String>,
+    limits: ChromeLimits,
 }
 
 impl BrowserLauncher {
     /// Creates a new launcher with the specified Chrome executable path.
     #[must_use]
     pub const fn new(chrome_path: PathBuf) -> Self {
-        Self { chrome_path }
+        Self {
+            chrome_path,
+            headless: HeadlessMode::New,
+            proxy_server: None,
+            limits: ChromeLimits {
+                single_renderer: false,
+                max_js_heap_mb: None,
+                nice: None,
+            },
+        }
+    }
+
+    /// Sets the browser display mode (new headless by default).
+    #[must_use]
+    pub const fn with_headless_mode(mut self, headless: HeadlessMode) -> Self {
+        self.headless = headless;
+        self
+    }
+
+    /// Routes every browser request through `proxy_server` (`scheme://host:port`).
+    #[must_use]
+    pub fn with_proxy(mut self, proxy_server: Option<String>) -> Self {
+        self.proxy_server = proxy_server;
+        self
+    }
+
+    /// Limits Chrome's resource usage (no limits by default).
+    #[must_use]
+    pub const fn with_limits(mut self, limits: ChromeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Chrome command-line flags for this launcher.
+    fn launch_args(&self) -> Vec<String> {
+        self.headless
+            .launch_arg()
+            .into_iter()
+            .chain(LAUNCH_ARGS)
+            .map(str::to_string)
+            .chain(
+                self.proxy_server
+                    .iter()
+                    .map(|server| format!("--proxy-server={server}")),
+            )
+            .chain(self.limits.launch_args())
+            .collect()
     }
 
-    /// Launches Chrome in headless mode and returns the browser instance.
+    /// Program to run and its leading arguments: Chrome itself, or Chrome
+    /// under `nice` when a niceness is set.
+    ///
+    /// Starting Chrome under `nice` gives the lower priority to every process
+    /// it forks (zygote, GPU, renderers), which renicing it after launch would
+    /// miss. Without `nice` (e.g. on Windows) Chrome runs at normal priority.
+    fn program(&self) -> (PathBuf, Vec<String>) {
+        let Some(nice) = self.limits.niceness() else {
+            return (self.chrome_path.clone(), Vec::new());
+        };
+        let Some(nice_path) = nice_path() else {
+            log::warn!("nice not found, Chrome runs at normal priority");
+            return (self.chrome_path.clone(), Vec::new());
+        };
+        let args = vec![
+            "-n".to_string(),
+            nice.to_string(),
+            self.chrome_path.to_string_lossy().into_owned(),
+        ];
+        (nice_path, args)
+    }
+
+    /// Launches Chrome and returns the browser instance.
+    ///
+    /// The returned handle drives the browser connection. It completes with
+    /// `BrowserError::BrowserCrashed` as soon as the browser is gone, so callers
+    /// can race it against page operations instead of waiting for a timeout.
     ///
     /// # Errors
     ///
     /// Returns an error if the browser fails to launch.
-    pub async fn launch(&self) -> Result<(Browser, JoinHandle<()>), BrowserError> {
+    pub async fn launch(&self) -> Result<(Browser, JoinHandle<BrowserError>), BrowserError> {
+        // `with_head` stops chromiumoxide from adding its own headless flag
+        let (program, program_args) = self.program();
         let config = BrowserConfig::builder()
-            .chrome_executable(&self.chrome_path)
+            .chrome_executable(program)
             .no_sandbox()
             .disable_default_args()
-            .arg("--headless=new")
-            .arg("--disable-gpu")
-            .arg("--disable-dev-shm-usage")
-            .arg("--disable-extensions")
-            .arg("--disable-background-networking")
-            .arg("--disable-sync")
-            .arg("--disable-translate")
-            .arg("--disable-default-apps")
-            .arg("--no-first-run")
-            .arg("--window-size=1920,1080")
-            .arg("--hide-scrollbars")
-            .arg("--mute-audio")
+            .with_head()
+            .args(program_args)
+            .args(self.launch_args())
             .viewport(None)
             .build()
             .map_err(BrowserError::LaunchFailed)?;
 
-        let (browser, mut handler) = Browser::launch(config)
+        let (browser, handler) = Browser::launch(config)
             .await
             .map_err(|e| BrowserError::LaunchFailed(e.to_string()))?;
 
-        let handle = tokio::spawn(async move { while handler.next().await.is_some() {} });
+        let handle = tokio::spawn(watch_handler(handler));
 
         Ok((browser, handle))
     }
@@ -81,6 +179,50 @@ impl BrowserLauncher {
     }
 }
 
+/// Absolute path of the `nice` utility, searched in `PATH` (Unix only).
+///
+/// chromiumoxide canonicalizes the executable path, so a bare `nice` would not resolve.
+fn nice_path() -> Option<PathBuf> {
+    #[cfg(unix)]
+    {
+        std::env::var_os("PATH").and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join("nice"))
+                .find(|path| path.is_file())
+        })
+    }
+
+    #[cfg(not(unix))]
+    None
+}
+
+/// Drive the browser handler until it reports a fatal error or ends.
+///
+/// Other handler errors (unexpected messages, decoding issues) are logged and ignored.
+async fn watch_handler<E: std::fmt::Display>(
+    mut events: impl Stream<Item = Result<(), E>> + Unpin,
+) -> BrowserError {
+    while let Some(event) = events.next().await {
+        if let Err(e) = event {
+            let message = e.to_string();
+            if let Some(signature) = fatal_signature(&message) {
+                log::error!("Browser crashed ({signature}): {message}");
+                return BrowserError::BrowserCrashed(message);
+            }
+            log::debug!("Browser handler error: {message}");
+        }
+    }
+    BrowserError::BrowserCrashed("browser connection closed".to_string())
+}
+
+/// The known fatal signature contained in `message`, if any.
+fn fatal_signature(message: &str) -> Option<&'static str> {
+    let message = message.to_lowercase();
+    FATAL_SIGNATURES
+        .into_iter()
+        .find(|signature| message.contains(signature))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,6 +231,88 @@ mod tests {
     fn test_new() {
         let launcher = BrowserLauncher::new(PathBuf::from("/path/to/chrome"));
         assert_eq!(launcher.chrome_path, PathBuf::from("/path/to/chrome"));
+        assert_eq!(launcher.headless, HeadlessMode::New);
+    }
+
+    #[test]
+    fn test_launch_args_per_headless_mode() {
+        let args = |mode| {
+            BrowserLauncher::new(PathBuf::from("/path/to/chrome"))
+                .with_headless_mode(mode)
+                .launch_args()
+        };
+
+        let new = args(HeadlessMode::New);
+        assert_eq!(new[0], "--headless=new");
+        assert_eq!(
+            new.iter().filter(|a| a.starts_with("--headless")).count(),
+            1
+        );
+
+        let old = args(HeadlessMode::Old);
+        assert_eq!(old[0], "--headless=old");
+        assert_eq!(
+            old.iter().filter(|a| a.starts_with("--headless")).count(),
+            1
+        );
+
+        let headful = args(HeadlessMode::Headful);
+        assert!(!headful.iter().any(|a| a.starts_with("--headless")));
+        assert_eq!(headful.len(), LAUNCH_ARGS.len());
+        assert!(!new.iter().any(|a| a.starts_with("--proxy-server")));
+    }
+
+    #[test]
+    fn test_launch_args_with_proxy() {
+        let args = BrowserLauncher::new(PathBuf::from("/path/to/chrome"))
+            .with_proxy(Some("socks5://proxy.corp:1080".to_string()))
+            .launch_args();
+        assert_eq!(
+            args.iter()
+                .filter(|a| a.starts_with("--proxy-server"))
+                .collect::<Vec<_>>(),
+            vec!["--proxy-server=socks5://proxy.corp:1080"]
+        );
+    }
+
+    #[test]
+    fn test_launch_args_with_limits() {
+        let unlimited = BrowserLauncher::new(PathBuf::from("/path/to/chrome")).launch_args();
+        assert!(!unlimited
+            .iter()
+            .any(|a| a.starts_with("--renderer-process-limit") || a.starts_with("--js-flags")));
+
+        let args = BrowserLauncher::new(PathBuf::from("/path/to/chrome"))
+            .with_limits(ChromeLimits {
+                single_renderer: true,
+                max_js_heap_mb: Some(256),
+                nice: Some(10),
+            })
+            .launch_args();
+        assert!(args.contains(&"--renderer-process-limit=1".to_string()));
+        assert!(args.contains(&"--js-flags=--max-old-space-size=256".to_string()));
+    }
+
+    #[test]
+    fn test_program_runs_chrome_under_nice() {
+        let chrome = PathBuf::from("/path/to/chrome");
+        let (program, args) = BrowserLauncher::new(chrome.clone()).program();
+        assert_eq!(program, chrome);
+        assert!(args.is_empty());
+
+        let (program, args) = BrowserLauncher::new(chrome.clone())
+            .with_limits(ChromeLimits {
+                nice: Some(40),
+                ..ChromeLimits::default()
+            })
+            .program();
+        if let Some(nice) = nice_path() {
+            assert_eq!(program, nice);
+            assert_eq!(args, vec!["-n", "19", "/path/to/chrome"]);
+        } else {
+            assert_eq!(program, chrome);
+            assert!(args.is_empty());
+        }
     }
 
     #[test]
@@ -111,4 +335,35 @@ mod tests {
             .to_string_lossy()
             .contains("chrome-headless-shell"));
     }
+
+    #[test]
+    fn test_fatal_signatures() {
+        assert_eq!(
+            fatal_signature("Trying to work with closed connection"),
+            Some("closed connection")
+        );
+        assert_eq!(
+            fatal_signature("IO error: Broken pipe (os error 32)"),
+            Some("broken pipe")
+        );
+        assert_eq!(fatal_signature("Received no response"), None);
+    }
+
+    #[tokio::test]
+    async fn test_fatal_handler_event_fails_fast() {
+        let events = futures::stream::iter(vec![Ok(()), Err("Connection closed normally")])
+            .chain(futures::stream::pending());
+        let crashed =
+            tokio::time::timeout(std::time::Duration::from_secs(1), watch_handler(events)).await;
+        assert!(matches!(crashed, Ok(BrowserError::BrowserCrashed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_non_fatal_handler_errors_are_ignored() {
+        let events = futures::stream::iter(vec![Err("Received unexpected ws message")])
+            .chain(futures::stream::pending());
+        let running =
+            tokio::time::timeout(std::time::Duration::from_millis(50), watch_handler(events)).await;
+        assert!(running.is_err());
+    }
 }
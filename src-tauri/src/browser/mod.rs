@@ -1,7 +1,8 @@
 //! Browser automation module for metrics collection.
 
 pub mod collector;
+mod interactions;
 pub mod launcher;
 
-pub use collector::MetricsCollector;
+pub use collector::{CollectedMetrics, MetricsCollector};
 pub use launcher::BrowserLauncher;
@@ -0,0 +1,190 @@
+//! Page budgets for CI gating.
+//!
+//! A budget combines hard ceilings on raw metrics (page size, request count)
+//! with a minimum grade. Ceilings can be checked on every run, so a
+//! multi-run analysis can stop as soon as one run busts them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{EcoIndexResult, PageMetrics};
+
+/// Limits a page must stay within.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Budget {
+    /// Maximum page size in KB (hard ceiling).
+    pub max_size_kb: Option<f64>,
+    /// Maximum number of requests (hard ceiling).
+    pub max_requests: Option<u32>,
+    /// Worst acceptable grade (`'C'` accepts A, B and C).
+    pub min_grade: Option<char>,
+}
+
+/// A budget limit that was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum BudgetViolation {
+    /// Page size above `max_size_kb`.
+    SizeKb {
+        /// Budget limit.
+        limit: f64,
+        /// Measured value.
+        actual: f64,
+    },
+    /// Request count above `max_requests`.
+    Requests {
+        /// Budget limit.
+        limit: u32,
+        /// Measured value.
+        actual: u32,
+    },
+    /// Grade worse than `min_grade`.
+    Grade {
+        /// Budget limit.
+        limit: char,
+        /// Measured value.
+        actual: char,
+    },
+}
+
+/// Outcome of a budget evaluation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetReport {
+    /// Whether every limit was respected.
+    pub passed: bool,
+    /// Limits exceeded, in budget order.
+    pub violations: Vec<BudgetViolation>,
+}
+
+impl BudgetReport {
+    /// Report for the given violations (passed when there are none).
+    #[must_use]
+    pub fn from_violations(violations: Vec<BudgetViolation>) -> Self {
+        Self {
+            passed: violations.is_empty(),
+            violations,
+        }
+    }
+}
+
+/// Result of an analysis gated by a budget.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetCheck {
+    /// Result computed from the runs that were completed.
+    pub result: EcoIndexResult,
+    /// Budget evaluation.
+    pub report: BudgetReport,
+    /// Number of runs actually performed.
+    pub completed_runs: u32,
+    /// Set when the remaining runs were skipped because a run busted a hard ceiling.
+    pub stopped_early: bool,
+}
+
+/// Evaluate `result` against every limit of `budget`.
+#[must_use]
+pub fn evaluate(result: &EcoIndexResult, budget: &Budget) -> BudgetReport {
+    let mut violations = hard_limit_violations(&result.metrics, budget);
+    if let Some(limit) = budget.min_grade {
+        // Grades run from 'A' (best) to 'G' (worst)
+        if result.grade.to_ascii_uppercase() > limit.to_ascii_uppercase() {
+            violations.push(BudgetViolation::Grade {
+                limit,
+                actual: result.grade,
+            });
+        }
+    }
+    BudgetReport::from_violations(violations)
+}
+
+/// Hard ceilings exceeded by the metrics of a single run.
+///
+/// A non-empty list means the run fails the budget whatever the other runs
+/// measure, so the remaining runs can be skipped.
+#[must_use]
+pub fn hard_limit_violations(metrics: &PageMetrics, budget: &Budget) -> Vec<BudgetViolation> {
+    let mut violations = Vec::new();
+    if let Some(limit) = budget.max_size_kb {
+        if metrics.size_kb > limit {
+            violations.push(BudgetViolation::SizeKb {
+                limit,
+                actual: metrics.size_kb,
+            });
+        }
+    }
+    if let Some(limit) = budget.max_requests {
+        if metrics.requests > limit {
+            violations.push(BudgetViolation::Requests {
+                limit,
+                actual: metrics.requests,
+            });
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculator::EcoIndexCalculator;
+
+    fn result(dom_elements: u32, requests: u32, size_kb: f64) -> EcoIndexResult {
+        EcoIndexCalculator::compute(
+            &PageMetrics::new(dom_elements, requests, size_kb),
+            "https://example.com",
+        )
+    }
+
+    #[test]
+    fn test_within_budget() {
+        let budget = Budget {
+            max_size_kb: Some(2000.0),
+            max_requests: Some(100),
+            min_grade: Some('G'),
+        };
+        let report = evaluate(&result(500, 40, 1000.0), &budget);
+        assert!(report.passed);
+        assert!(report.violations.is_empty());
+        assert!(evaluate(&result(500, 40, 1000.0), &Budget::default()).passed);
+    }
+
+    #[test]
+    fn test_violations_reported_in_budget_order() {
+        let budget = Budget {
+            max_size_kb: Some(500.0),
+            max_requests: Some(20),
+            min_grade: Some('a'),
+        };
+        let page = result(2000, 150, 4000.0);
+        let report = evaluate(&page, &budget);
+
+        assert!(!report.passed);
+        assert_eq!(
+            report.violations,
+            vec![
+                BudgetViolation::SizeKb {
+                    limit: 500.0,
+                    actual: 4000.0
+                },
+                BudgetViolation::Requests {
+                    limit: 20,
+                    actual: 150
+                },
+                BudgetViolation::Grade {
+                    limit: 'a',
+                    actual: page.grade
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hard_limits_ignore_grade() {
+        let budget = Budget {
+            min_grade: Some('A'),
+            ..Budget::default()
+        };
+        assert!(hard_limit_violations(&PageMetrics::new(5000, 300, 9000.0), &budget).is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+//! Download time of a page on typical connections.
+//!
+//! Turns a byte total into something tangible ("about 14 s on Slow 3G"). The
+//! model is deliberately simple: one round trip, then the bytes at full
+//! throughput. Real loads add request round trips and parsing, so the
+//! estimates are lower bounds.
+
+use serde::{Deserialize, Serialize};
+
+use super::ecoindex::{SWD_BYTES_PER_GB, SWD_KWH_PER_GB};
+use crate::domain::NetworkProfile;
+
+/// Download estimate on one connection type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionEstimate {
+    /// Connection type.
+    pub profile: NetworkProfile,
+    /// Display name of the connection type.
+    pub label: String,
+    /// Download throughput (kbit/s).
+    pub throughput_kbps: f64,
+    /// Round-trip time (ms).
+    pub latency_ms: f64,
+    /// Estimated download time (ms).
+    pub load_time_ms: f64,
+}
+
+/// Download estimates of a page on every connection type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadEstimates {
+    /// Bytes transferred by the page.
+    pub transfer_bytes: u64,
+    /// Network energy of the transfer (Wh, Sustainable Web Design intensity).
+    ///
+    /// The model has a single intensity for every network, so it does not vary
+    /// with the connection type.
+    pub transfer_energy_wh: f64,
+    /// One estimate per connection type, slowest first.
+    pub connections: Vec<ConnectionEstimate>,
+}
+
+impl DownloadEstimates {
+    /// Estimate the download of `transfer_bytes` on every [`NetworkProfile`].
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_bytes(transfer_bytes: u64) -> Self {
+        Self {
+            transfer_bytes,
+            transfer_energy_wh: transfer_bytes as f64 / SWD_BYTES_PER_GB * SWD_KWH_PER_GB * 1000.0,
+            connections: NetworkProfile::ALL
+                .iter()
+                .map(|&profile| ConnectionEstimate {
+                    profile,
+                    label: profile.label().to_string(),
+                    throughput_kbps: profile.throughput_kbps(),
+                    latency_ms: profile.latency_ms(),
+                    load_time_ms: load_time_ms(transfer_bytes, profile),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Time to download `transfer_bytes` on `profile`: one round trip plus the transfer (ms).
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn load_time_ms(transfer_bytes: u64, profile: NetworkProfile) -> f64 {
+    // kbit/s is bits per millisecond
+    let transfer_ms = transfer_bytes as f64 * 8.0 / profile.throughput_kbps();
+    profile.latency_ms() + transfer_ms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_time_from_throughput() {
+        // 500 KB at 400 kbit/s: 10 s, plus a 2 s round trip
+        assert!((load_time_ms(500_000, NetworkProfile::Slow3g) - 12_000.0).abs() < 1e-9);
+        // 1 MB at 10240 kbit/s: 781.25 ms, plus 40 ms
+        assert!((load_time_ms(1_000_000, NetworkProfile::Broadband) - 821.25).abs() < 1e-9);
+        // Empty page: latency only
+        assert!((load_time_ms(0, NetworkProfile::Slow4g) - 150.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_estimates_per_profile() {
+        let estimates = DownloadEstimates::from_bytes(2_000_000);
+
+        assert_eq!(estimates.connections.len(), NetworkProfile::ALL.len());
+        assert_eq!(estimates.connections[0].profile, NetworkProfile::Slow3g);
+        assert_eq!(estimates.connections[0].label, "Slow 3G");
+        assert!((estimates.connections[0].load_time_ms - 42_000.0).abs() < 1e-9);
+        // Slower connections first, and each one slower than the next
+        assert!(estimates
+            .connections
+            .windows(2)
+            .all(|pair| pair[0].load_time_ms > pair[1].load_time_ms));
+        // 0.002 GB * 0.81 kWh/GB
+        assert!((estimates.transfer_energy_wh - 1.62).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_profile_serialization() {
+        let json = serde_json::to_string(&NetworkProfile::Slow3g).unwrap_or_default();
+        assert_eq!(json, r#""slow3g""#);
+    }
+}
@@ -2,10 +2,121 @@
 //!
 //! Implements the official `EcoIndex` algorithm based on quantile tables.
 
+use serde::{Deserialize, Serialize};
+
 use crate::domain::quantiles::{
     DOM_QUANTILES, GRADE_THRESHOLDS, REQUEST_QUANTILES, SIZE_QUANTILES,
 };
-use crate::domain::{EcoIndexResult, PageMetrics};
+use crate::domain::{grade_proximity, Co2Model, EcoIndexResult, GradeProximity, PageMetrics};
+
+/// `EcoIndex` greenhouse gas emissions per page view (gCO2e) for a perfect score.
+pub const GHG_BASE_G: f64 = 2.0;
+
+/// Additional `EcoIndex` emissions (gCO2e) per page view at a score of 0.
+pub const GHG_SCORE_FACTOR_G: f64 = 2.0;
+
+/// Energy per page view (Wh) for a perfect score (about 4.5 Wh).
+///
+/// Not a separate model: [`GHG_BASE_G`] converted to electricity at the global
+/// grid intensity of [`SWD_GRID_INTENSITY_G_PER_KWH`]. All the `EcoIndex`
+/// emissions are attributed to electricity use, device manufacturing included,
+/// so this is an upper estimate.
+pub const ENERGY_BASE_WH: f64 = GHG_BASE_G / SWD_GRID_INTENSITY_G_PER_KWH * 1000.0;
+
+/// Additional energy (Wh) per page view at a score of 0.
+///
+/// [`GHG_SCORE_FACTOR_G`] converted like [`ENERGY_BASE_WH`].
+pub const ENERGY_SCORE_FACTOR_WH: f64 = GHG_SCORE_FACTOR_G / SWD_GRID_INTENSITY_G_PER_KWH * 1000.0;
+
+/// Sustainable Web Design (v3) energy intensity of data transfer, in kWh per GB.
+///
+/// Covers data centers, networks and user devices (embodied and operational).
+pub const SWD_KWH_PER_GB: f64 = 0.81;
+
+/// Global average grid carbon intensity used by SWD v3, in gCO2e per kWh.
+pub const SWD_GRID_INTENSITY_G_PER_KWH: f64 = 442.0;
+
+/// Share of first-time visitors in the SWD model (the rest are returning visitors).
+pub const SWD_FIRST_VISIT_SHARE: f64 = 0.75;
+
+/// Share of the page re-downloaded by a returning visitor (the rest is cached).
+pub const SWD_RETURNING_DATA_RATIO: f64 = 0.02;
+
+/// Bytes per GB in the SWD model (decimal gigabytes).
+pub const SWD_BYTES_PER_GB: f64 = 1_000_000_000.0;
+
+/// Metric of the `EcoIndex` formula.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Metric {
+    /// DOM element count (weight 3).
+    DomElements,
+    /// HTTP request count (weight 2).
+    Requests,
+    /// Transfer size in KB (weight 1).
+    SizeKb,
+}
+
+impl Metric {
+    /// Weight of the metric in the formula.
+    #[must_use]
+    pub const fn weight(self) -> f64 {
+        match self {
+            Self::DomElements => 3.0,
+            Self::Requests => 2.0,
+            Self::SizeKb => 1.0,
+        }
+    }
+
+    /// Quantile table of the metric.
+    #[must_use]
+    pub const fn quantiles(self) -> &'static [f64] {
+        match self {
+            Self::DomElements => &DOM_QUANTILES,
+            Self::Requests => &REQUEST_QUANTILES,
+            Self::SizeKb => &SIZE_QUANTILES,
+        }
+    }
+
+    /// Value of the metric in `metrics`.
+    #[must_use]
+    pub fn value(self, metrics: &PageMetrics) -> f64 {
+        match self {
+            Self::DomElements => f64::from(metrics.dom_elements),
+            Self::Requests => f64::from(metrics.requests),
+            Self::SizeKb => metrics.size_kb,
+        }
+    }
+}
+
+/// Contribution of one metric to the score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricContribution {
+    /// Metric concerned.
+    pub metric: Metric,
+    /// Value of the metric.
+    pub value: f64,
+    /// Quantile position (0-20).
+    pub quantile_position: f64,
+    /// Weight in the formula.
+    pub weight: f64,
+    /// Score points lost because of this metric.
+    pub penalty: f64,
+}
+
+/// Per-metric breakdown of an `EcoIndex` score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreBreakdown {
+    /// `EcoIndex` score (0-100).
+    pub score: f64,
+    /// Contributions in formula order (DOM, requests, size).
+    pub contributions: Vec<MetricContribution>,
+    /// Points missing to reach the next-better grade (absent at grade A).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grade_proximity: Option<GradeProximity>,
+}
 
 /// Calculator for `EcoIndex` scores.
 pub struct EcoIndexCalculator;
@@ -17,7 +128,7 @@ impl EcoIndexCalculator {
     #[must_use]
     #[allow(clippy::cast_precision_loss)]
     pub fn get_quantile_position(value: f64, quantiles: &[f64]) -> f64 {
-        if value <= quantiles[0] {
+        if value.is_nan() || value <= quantiles[0] {
             return 0.0;
         }
         if value >= quantiles[quantiles.len() - 1] {
@@ -37,6 +148,8 @@ impl EcoIndexCalculator {
     /// Computes the `EcoIndex` score from page metrics.
     ///
     /// Formula: `100 - 5 × (3×Q_dom + 2×Q_req + Q_size) / 6`
+    ///
+    /// Always returns a finite score in `0..=100`, even for NaN or infinite sizes.
     #[must_use]
     pub fn compute_score(metrics: &PageMetrics) -> f64 {
         let q_dom = Self::get_quantile_position(f64::from(metrics.dom_elements), &DOM_QUANTILES);
@@ -45,7 +158,58 @@ impl EcoIndexCalculator {
 
         let weighted = 3.0f64.mul_add(q_dom, 2.0f64.mul_add(q_req, q_size));
         let score = 100.0 - (5.0 * weighted) / 6.0;
-        score.clamp(0.0, 100.0)
+        if score.is_finite() {
+            score.clamp(0.0, 100.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Breaks a score down into the points lost to each metric.
+    ///
+    /// The penalties add up to `100 - score` (before clamping).
+    #[must_use]
+    pub fn explain(metrics: &PageMetrics) -> ScoreBreakdown {
+        let contributions = [Metric::DomElements, Metric::Requests, Metric::SizeKb]
+            .into_iter()
+            .map(|metric| {
+                let value = metric.value(metrics);
+                let quantile_position = Self::get_quantile_position(value, metric.quantiles());
+                MetricContribution {
+                    metric,
+                    value,
+                    quantile_position,
+                    weight: metric.weight(),
+                    penalty: 5.0 * metric.weight() * quantile_position / 6.0,
+                }
+            })
+            .collect();
+        let score = Self::compute_score(metrics);
+        ScoreBreakdown {
+            score,
+            contributions,
+            grade_proximity: grade_proximity(score),
+        }
+    }
+
+    /// Value at a quantile position (inverse of [`Self::get_quantile_position`]).
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn value_at_position(position: f64, quantiles: &[f64]) -> f64 {
+        let last = quantiles.len() - 1;
+        if position.is_nan() || position <= 0.0 {
+            return quantiles[0];
+        }
+        if position >= last as f64 {
+            return quantiles[last];
+        }
+        let index = position.floor() as usize;
+        let fraction = position - index as f64;
+        (quantiles[index + 1] - quantiles[index]).mul_add(fraction, quantiles[index])
     }
 
     /// Determines the grade (A-G) from a score.
@@ -62,7 +226,7 @@ impl EcoIndexCalculator {
     /// Computes greenhouse gas emissions in gCO2e per page view.
     #[must_use]
     pub fn compute_ghg(score: f64) -> f64 {
-        2.0 + 2.0 * (100.0 - score) / 100.0
+        GHG_BASE_G + GHG_SCORE_FACTOR_G * (100.0 - score) / 100.0
     }
 
     /// Computes water consumption in centiliters per page view.
@@ -71,15 +235,59 @@ impl EcoIndexCalculator {
         3.0 + 3.0 * (100.0 - score) / 100.0
     }
 
+    /// Computes greenhouse gas emissions in gCO2e per page view with the
+    /// Sustainable Web Design model (v3, global grid, no green hosting).
+    ///
+    /// Emissions are proportional to the transferred bytes, weighted by the
+    /// share of first and returning visits like Website Carbon.
+    #[must_use]
+    pub fn compute_swd_ghg(size_kb: f64) -> f64 {
+        let gigabytes = PageMetrics::sanitize_size_kb(size_kb) * 1024.0 / SWD_BYTES_PER_GB;
+        let visit_factor =
+            SWD_RETURNING_DATA_RATIO.mul_add(1.0 - SWD_FIRST_VISIT_SHARE, SWD_FIRST_VISIT_SHARE);
+        gigabytes * SWD_KWH_PER_GB * SWD_GRID_INTENSITY_G_PER_KWH * visit_factor
+    }
+
+    /// Computes estimated energy consumption in Wh per page view.
+    ///
+    /// The [`Self::compute_ghg`] emissions at the global grid intensity, so linear
+    /// in `100 - score` like GHG and water: [`ENERGY_BASE_WH`] at 100, plus
+    /// [`ENERGY_SCORE_FACTOR_WH`] at 0.
+    #[must_use]
+    pub fn compute_energy(score: f64) -> f64 {
+        ENERGY_BASE_WH + ENERGY_SCORE_FACTOR_WH * (100.0 - score) / 100.0
+    }
+
     /// Performs a complete `EcoIndex` calculation.
     #[must_use]
     pub fn compute(metrics: &PageMetrics, url: &str) -> EcoIndexResult {
+        Self::compute_with_model(metrics, url, Co2Model::default())
+    }
+
+    /// Performs a complete `EcoIndex` calculation, computing `ghg` with `co2_model`.
+    #[must_use]
+    pub fn compute_with_model(
+        metrics: &PageMetrics,
+        url: &str,
+        co2_model: Co2Model,
+    ) -> EcoIndexResult {
         let score = Self::compute_score(metrics);
         let grade = Self::get_grade(score);
-        let ghg = Self::compute_ghg(score);
+        let swd_ghg = Self::compute_swd_ghg(metrics.size_kb);
+        let ghg = match co2_model {
+            Co2Model::EcoIndex | Co2Model::Both => Self::compute_ghg(score),
+            Co2Model::Swd => swd_ghg,
+        };
         let water = Self::compute_water(score);
+        let energy = Self::compute_energy(score);
 
-        EcoIndexResult::new(score, grade, ghg, water, *metrics, url.to_string())
+        let mut result =
+            EcoIndexResult::new(score, grade, ghg, water, energy, *metrics, url.to_string());
+        result.co2_model = co2_model;
+        if co2_model == Co2Model::Both {
+            result.swd_ghg = Some(swd_ghg);
+        }
+        result
     }
 }
 
@@ -138,6 +346,24 @@ mod tests {
         assert_eq!(EcoIndexCalculator::get_grade(0.0), 'G');
     }
 
+    #[test]
+    fn test_explain_penalties_sum_to_score() {
+        let metrics = PageMetrics::new(1200, 80, 2000.0);
+        let breakdown = EcoIndexCalculator::explain(&metrics);
+        let penalties: f64 = breakdown.contributions.iter().map(|c| c.penalty).sum();
+        assert!((100.0 - penalties - breakdown.score).abs() < 1e-9);
+        assert_eq!(breakdown.contributions[0].metric, Metric::DomElements);
+    }
+
+    #[test]
+    fn test_value_at_position_inverts_quantile_position() {
+        for value in [0.0, 61.0, 600.0, 2479.0] {
+            let position = EcoIndexCalculator::get_quantile_position(value, &DOM_QUANTILES);
+            let back = EcoIndexCalculator::value_at_position(position, &DOM_QUANTILES);
+            assert!((back - value).abs() < 1e-6, "{value} -> {back}");
+        }
+    }
+
     #[test]
     fn test_ghg_calculation() {
         assert!((EcoIndexCalculator::compute_ghg(100.0) - 2.0).abs() < f64::EPSILON);
@@ -145,6 +371,81 @@ mod tests {
         assert!((EcoIndexCalculator::compute_ghg(50.0) - 3.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_swd_ghg_for_known_size() {
+        // 2 MiB: 0.002097152 GB * 0.81 kWh/GB * 442 g/kWh * 0.755
+        let ghg = EcoIndexCalculator::compute_swd_ghg(2048.0);
+        assert!((ghg - 0.566_870_881_075_2).abs() < 1e-9);
+        assert!(EcoIndexCalculator::compute_swd_ghg(0.0).abs() < f64::EPSILON);
+        assert!(EcoIndexCalculator::compute_swd_ghg(f64::NAN).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_compute_with_co2_model() {
+        let metrics = PageMetrics::new(500, 50, 2048.0);
+        let url = "https://example.com";
+        let ecoindex = EcoIndexCalculator::compute(&metrics, url);
+        let swd = EcoIndexCalculator::compute_with_model(&metrics, url, Co2Model::Swd);
+        let both = EcoIndexCalculator::compute_with_model(&metrics, url, Co2Model::Both);
+
+        assert_eq!(ecoindex.co2_model, Co2Model::EcoIndex);
+        assert!(ecoindex.swd_ghg.is_none());
+        assert!((swd.ghg - 0.566_870_881_075_2).abs() < 1e-9);
+        assert!(swd.swd_ghg.is_none());
+        assert!((both.ghg - ecoindex.ghg).abs() < f64::EPSILON);
+        assert_eq!(both.swd_ghg, Some(swd.ghg));
+        // The model only changes the carbon figure
+        assert!((swd.score - ecoindex.score).abs() < f64::EPSILON);
+        assert!((swd.water - ecoindex.water).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_score_is_finite_for_invalid_inputs() {
+        let inputs = [
+            PageMetrics {
+                dom_elements: 100,
+                requests: 10,
+                size_kb: f64::NAN,
+            },
+            PageMetrics {
+                dom_elements: 100,
+                requests: 10,
+                size_kb: f64::INFINITY,
+            },
+            PageMetrics {
+                dom_elements: 100,
+                requests: 10,
+                size_kb: f64::NEG_INFINITY,
+            },
+            PageMetrics::new(u32::MAX, u32::MAX, f64::MAX),
+            PageMetrics::new(0, 0, -1.0),
+        ];
+        for metrics in inputs {
+            let result = EcoIndexCalculator::compute(&metrics, "https://example.com");
+            assert!(result.score.is_finite(), "{metrics:?}");
+            assert!((0.0..=100.0).contains(&result.score));
+            assert!(('A'..='G').contains(&result.grade));
+            assert!(result.ghg.is_finite() && result.water.is_finite());
+        }
+
+        let absurd =
+            EcoIndexCalculator::compute_score(&PageMetrics::new(u32::MAX, u32::MAX, f64::MAX));
+        assert!(absurd.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_energy_calculation() {
+        assert!((EcoIndexCalculator::compute_energy(100.0) - 4.524_886_877_8).abs() < 1e-9);
+        assert!((EcoIndexCalculator::compute_energy(0.0) - 9.049_773_755_7).abs() < 1e-9);
+        assert!((EcoIndexCalculator::compute_energy(50.0) - 6.787_330_316_7).abs() < 1e-9);
+        // The GHG figure at the global grid intensity
+        for score in [0.0, 37.5, 100.0] {
+            let ghg = EcoIndexCalculator::compute_ghg(score);
+            let energy = EcoIndexCalculator::compute_energy(score);
+            assert!((energy * SWD_GRID_INTENSITY_G_PER_KWH / 1000.0 - ghg).abs() < 1e-9);
+        }
+    }
+
     #[test]
     fn test_water_calculation() {
         assert!((EcoIndexCalculator::compute_water(100.0) - 3.0).abs() < f64::EPSILON);
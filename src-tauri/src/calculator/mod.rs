@@ -1,5 +1,11 @@
 //! `EcoIndex` calculator module.
 
+pub mod budget;
+pub mod download;
 pub mod ecoindex;
+pub mod multi_run;
+pub mod recommendations;
+pub mod simulation;
+pub mod site_summary;
 
-pub use ecoindex::EcoIndexCalculator;
+pub use ecoindex::{EcoIndexCalculator, Metric, MetricContribution, ScoreBreakdown};
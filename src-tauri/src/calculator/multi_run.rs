@@ -0,0 +1,141 @@
+//! Aggregation of repeated analyses.
+//!
+//! Network timing and third-party content make single runs noisy. Running
+//! the same page several times and keeping the per-metric median gives a
+//! more stable score.
+
+use super::EcoIndexCalculator;
+use crate::domain::{MetricRange, PageMetrics, RunStatistics};
+
+/// Median of `values` (mean of the two middle values for even counts).
+///
+/// Returns `None` for an empty slice.
+#[must_use]
+pub fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        Some((sorted[mid - 1] + sorted[mid]) / 2.0)
+    } else {
+        Some(sorted[mid])
+    }
+}
+
+/// Min, median and max of `values`, or `None` for an empty slice.
+#[must_use]
+pub fn metric_range(values: &[f64]) -> Option<MetricRange> {
+    let median = median(values)?;
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    Some(MetricRange { min, median, max })
+}
+
+/// Median of integer counts, rounded to the nearest integer.
+#[must_use]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn median_u32(values: &[u32]) -> Option<u32> {
+    let values: Vec<f64> = values.iter().map(|&v| f64::from(v)).collect();
+    median(&values).map(|m| m.round() as u32)
+}
+
+/// Combine several samples of the same page into per-metric medians.
+///
+/// Each metric is aggregated independently, so the result may not match any
+/// single run. Returns `None` when `samples` is empty.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn aggregate_page_metrics(samples: &[PageMetrics]) -> Option<(PageMetrics, RunStatistics)> {
+    let dom: Vec<u32> = samples.iter().map(|m| m.dom_elements).collect();
+    let requests: Vec<u32> = samples.iter().map(|m| m.requests).collect();
+    let size: Vec<f64> = samples.iter().map(|m| m.size_kb).collect();
+    let scores: Vec<f64> = samples
+        .iter()
+        .map(EcoIndexCalculator::compute_score)
+        .collect();
+
+    let metrics = PageMetrics::new(median_u32(&dom)?, median_u32(&requests)?, median(&size)?);
+    let stats = RunStatistics {
+        runs: samples.len() as u32,
+        score: metric_range(&scores)?,
+        dom_elements: metric_range(&to_f64(&dom))?,
+        requests: metric_range(&to_f64(&requests))?,
+        size_kb: metric_range(&size)?,
+    };
+
+    Some((metrics, stats))
+}
+
+fn to_f64(values: &[u32]) -> Vec<f64> {
+    values.iter().map(|&v| f64::from(v)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median_odd_and_even() {
+        assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+        assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), Some(2.5));
+        assert_eq!(median(&[]), None);
+    }
+
+    #[test]
+    fn test_median_u32_rounds() {
+        assert_eq!(median_u32(&[10, 11]), Some(11));
+        assert_eq!(median_u32(&[7]), Some(7));
+        assert_eq!(median_u32(&[]), None);
+    }
+
+    #[test]
+    fn test_metric_range() {
+        let range = metric_range(&[5.0, 1.0, 3.0]).unwrap_or(MetricRange {
+            min: 0.0,
+            median: 0.0,
+            max: 0.0,
+        });
+        assert!((range.min - 1.0).abs() < f64::EPSILON);
+        assert!((range.median - 3.0).abs() < f64::EPSILON);
+        assert!((range.max - 5.0).abs() < f64::EPSILON);
+        assert!((range.spread() - 4.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_aggregate_page_metrics_per_field() {
+        // Each field has its median in a different sample
+        let samples = [
+            PageMetrics::new(100, 30, 900.0),
+            PageMetrics::new(300, 10, 1100.0),
+            PageMetrics::new(200, 20, 500.0),
+        ];
+        let result = aggregate_page_metrics(&samples);
+        assert!(result.is_some());
+        let Some((metrics, stats)) = result else {
+            return;
+        };
+
+        assert_eq!(metrics.dom_elements, 200);
+        assert_eq!(metrics.requests, 20);
+        assert!((metrics.size_kb - 900.0).abs() < f64::EPSILON);
+
+        assert_eq!(stats.runs, 3);
+        assert!((stats.dom_elements.min - 100.0).abs() < f64::EPSILON);
+        assert!((stats.dom_elements.max - 300.0).abs() < f64::EPSILON);
+        assert!((stats.size_kb.min - 500.0).abs() < f64::EPSILON);
+        assert!(stats.score.min <= stats.score.median);
+        assert!(stats.score.median <= stats.score.max);
+    }
+
+    #[test]
+    fn test_aggregate_empty() {
+        assert!(aggregate_page_metrics(&[]).is_none());
+    }
+}
@@ -0,0 +1,221 @@
+//! Next-step recommendations for improving an `EcoIndex` grade.
+//!
+//! Each recommendation targets the next grade up, by changing a single metric
+//! while the others stay as measured.
+
+use serde::{Deserialize, Serialize};
+
+use super::{EcoIndexCalculator, Metric};
+use crate::analytics::Locale;
+use crate::domain::{EcoIndexResult, PageMetrics};
+
+/// Area of the page a recommendation is about, for grouping in the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecommendationCategory {
+    /// Page structure (DOM size).
+    Structure,
+    /// Number of network requests.
+    Network,
+    /// Transferred bytes.
+    Weight,
+}
+
+impl RecommendationCategory {
+    /// Category of recommendations reducing `metric`.
+    #[must_use]
+    pub const fn of(metric: Metric) -> Self {
+        match metric {
+            Metric::DomElements => Self::Structure,
+            Metric::Requests => Self::Network,
+            Metric::SizeKb => Self::Weight,
+        }
+    }
+}
+
+/// Actionable suggestion for one metric.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Recommendation {
+    /// Area of the page concerned.
+    pub category: RecommendationCategory,
+    /// What to do, with the current and target values ("Réduire le nombre
+    /// d'éléments DOM : 5000 actuellement, viser 1200 (note D)").
+    pub message: String,
+    /// Metric to reduce.
+    pub metric: Metric,
+    /// Current value of the metric.
+    pub current: f64,
+    /// Value to reach (the next grade, or the best possible value when out of reach).
+    pub target: f64,
+    /// Score points currently lost to this metric.
+    pub penalty: f64,
+    /// Score with the metric at `target`.
+    pub estimated_score: f64,
+    /// Grade with the metric at `target`.
+    pub estimated_grade: char,
+    /// Score gain with the metric at `target`.
+    pub score_gain: f64,
+}
+
+/// Recommendations for `result`, the metric costing the most points first.
+///
+/// Empty for grade A pages.
+#[must_use]
+pub fn recommendations(result: &EcoIndexResult) -> Vec<Recommendation> {
+    recommendations_with_locale(result, Locale::default())
+}
+
+/// Recommendations for `result` with messages in the given locale.
+#[must_use]
+pub fn recommendations_with_locale(result: &EcoIndexResult, locale: Locale) -> Vec<Recommendation> {
+    let metrics = result.metrics;
+    let breakdown = EcoIndexCalculator::explain(&metrics);
+    let Some(proximity) = breakdown.grade_proximity else {
+        return Vec::new();
+    };
+    let deficit = proximity.points_needed;
+
+    let mut recommendations: Vec<Recommendation> = breakdown
+        .contributions
+        .iter()
+        .filter(|c| c.penalty > 0.0)
+        .filter_map(|c| {
+            let target_position = ((c.penalty - deficit).max(0.0) * 6.0) / (5.0 * c.weight);
+            // Rounded down to whole elements/requests/KB, so the target is reachable
+            let target =
+                EcoIndexCalculator::value_at_position(target_position, c.metric.quantiles())
+                    .floor()
+                    .max(0.0);
+            if target >= c.value {
+                return None;
+            }
+            let estimated_score =
+                EcoIndexCalculator::compute_score(&with_value(metrics, c.metric, target));
+            let estimated_grade = EcoIndexCalculator::get_grade(estimated_score);
+            Some(Recommendation {
+                category: RecommendationCategory::of(c.metric),
+                message: message(c.metric, c.value, target, estimated_grade, locale),
+                metric: c.metric,
+                current: c.value,
+                target,
+                penalty: c.penalty,
+                estimated_score,
+                estimated_grade,
+                score_gain: estimated_score - breakdown.score,
+            })
+        })
+        .collect();
+
+    recommendations.sort_by(|a, b| b.penalty.total_cmp(&a.penalty));
+    recommendations
+}
+
+/// Suggestion to bring `metric` from `current` down to `target`.
+fn message(metric: Metric, current: f64, target: f64, grade: char, locale: Locale) -> String {
+    match (locale, metric) {
+        (Locale::Fr, Metric::DomElements) => format!(
+            "Réduire le nombre d'éléments DOM : {current:.0} actuellement, viser {target:.0} (note {grade})"
+        ),
+        (Locale::Fr, Metric::Requests) => format!(
+            "Réduire le nombre de requêtes : {current:.0} actuellement, viser {target:.0} (note {grade})"
+        ),
+        (Locale::Fr, Metric::SizeKb) => format!(
+            "Alléger la page : {current:.0} Ko actuellement, viser {target:.0} Ko (note {grade})"
+        ),
+        (Locale::En, Metric::DomElements) => format!(
+            "Reduce DOM elements: you have {current:.0}, target {target:.0} (grade {grade})"
+        ),
+        (Locale::En, Metric::Requests) => format!(
+            "Reduce requests: you have {current:.0}, target {target:.0} (grade {grade})"
+        ),
+        (Locale::En, Metric::SizeKb) => format!(
+            "Reduce page weight: you have {current:.0} KB, target {target:.0} KB (grade {grade})"
+        ),
+    }
+}
+
+/// `metrics` with `metric` set to `value`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn with_value(metrics: PageMetrics, metric: Metric, value: f64) -> PageMetrics {
+    match metric {
+        Metric::DomElements => PageMetrics::new(value as u32, metrics.requests, metrics.size_kb),
+        Metric::Requests => PageMetrics::new(metrics.dom_elements, value as u32, metrics.size_kb),
+        Metric::SizeKb => PageMetrics::new(metrics.dom_elements, metrics.requests, value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(dom_elements: u32, requests: u32, size_kb: f64) -> EcoIndexResult {
+        EcoIndexCalculator::compute(
+            &PageMetrics::new(dom_elements, requests, size_kb),
+            "https://example.com",
+        )
+    }
+
+    #[test]
+    fn test_dom_heavy_page_gets_dom_first() {
+        let page = result(5000, 10, 100.0);
+        let recommendations = recommendations(&page);
+
+        assert!(!recommendations.is_empty());
+        let first = &recommendations[0];
+        assert_eq!(first.metric, Metric::DomElements);
+        assert!((first.current - 5000.0).abs() < f64::EPSILON);
+        assert!(first.target < first.current);
+        // Reducing the DOM alone reaches the next grade (E -> D)
+        assert_eq!(page.grade, 'E');
+        assert_eq!(first.estimated_grade, 'D');
+        assert!(first.score_gain > 0.0);
+        assert_eq!(first.category, RecommendationCategory::Structure);
+        assert_eq!(
+            first.message,
+            format!(
+                "Réduire le nombre d'éléments DOM : 5000 actuellement, viser {} (note D)",
+                first.target
+            )
+        );
+    }
+
+    #[test]
+    fn test_every_recommendation_has_category_and_message() {
+        let page = result(5000, 300, 20_000.0);
+        let recommendations = recommendations_with_locale(&page, Locale::En);
+
+        assert_eq!(recommendations.len(), 3);
+        for r in &recommendations {
+            assert_eq!(r.category, RecommendationCategory::of(r.metric));
+            assert!(r.message.starts_with("Reduce "), "{}", r.message);
+            assert!(r.message.contains(&format!("target {:.0}", r.target)));
+        }
+    }
+
+    #[test]
+    fn test_request_heavy_page_gets_requests_first() {
+        let recommendations = recommendations(&result(100, 300, 200.0));
+        assert_eq!(
+            recommendations.first().map(|r| r.metric),
+            Some(Metric::Requests)
+        );
+    }
+
+    #[test]
+    fn test_grade_a_has_no_recommendations() {
+        let page = result(10, 2, 10.0);
+        assert_eq!(page.grade, 'A');
+        assert!(recommendations(&page).is_empty());
+    }
+
+    #[test]
+    fn test_unreachable_target_uses_best_value() {
+        // Only 10 requests: removing them all cannot lift a G page to F
+        let recommendations = recommendations(&result(100_000, 10, 50_000.0));
+        let requests = recommendations
+            .iter()
+            .find(|r| r.metric == Metric::Requests);
+        assert!(requests.is_some_and(|r| r.target.abs() < f64::EPSILON));
+    }
+}
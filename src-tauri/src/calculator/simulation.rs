@@ -0,0 +1,315 @@
+//! "What-if" simulations on top of the `EcoIndex` calculator.
+//!
+//! Simulations recompute the score after hypothetical changes to a page,
+//! such as removing every request to a third-party domain or converting
+//! images to a modern format.
+
+use serde::{Deserialize, Serialize};
+
+use super::EcoIndexCalculator;
+use crate::domain::PageMetrics;
+use crate::sidecar::{ImageFormatAnalytics, RequestDetail};
+
+/// Size of an AVIF image relative to the WebP estimate used by Lighthouse.
+///
+/// AVIF is typically about 20% smaller than WebP at similar quality.
+const AVIF_TO_WEBP_RATIO: f64 = 0.8;
+
+/// Score and grade for a set of metrics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedScore {
+    /// Metrics used for the calculation.
+    pub metrics: PageMetrics,
+    /// `EcoIndex` score (0-100).
+    pub score: f64,
+    /// Grade (A-G).
+    pub grade: char,
+}
+
+impl SimulatedScore {
+    fn from_metrics(metrics: PageMetrics) -> Self {
+        let score = EcoIndexCalculator::compute_score(&metrics);
+        Self {
+            metrics,
+            score,
+            grade: EcoIndexCalculator::get_grade(score),
+        }
+    }
+}
+
+/// Result of removing all requests to a set of domains.
+///
+/// Only requests and transfer size can be recomputed from request details;
+/// the DOM element count is held constant since the elements that trigger
+/// those requests cannot be identified.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExclusionSimulation {
+    /// Domains that were excluded.
+    pub excluded_domains: Vec<String>,
+    /// Number of requests removed.
+    pub removed_requests: u32,
+    /// Transfer size removed in KB.
+    pub removed_size_kb: f64,
+    /// Score with the original metrics.
+    pub original: SimulatedScore,
+    /// Score after exclusion (DOM unchanged).
+    pub simulated: SimulatedScore,
+    /// Score improvement (simulated - original).
+    pub score_delta: f64,
+}
+
+/// Recompute the `EcoIndex` score as if requests to `excluded_domains` were removed.
+///
+/// A domain also matches its subdomains (`example.com` excludes `cdn.example.com`).
+#[must_use]
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub fn simulate_exclusion(
+    metrics: &PageMetrics,
+    requests: &[RequestDetail],
+    excluded_domains: &[String],
+) -> ExclusionSimulation {
+    let excluded: Vec<String> = excluded_domains
+        .iter()
+        .map(|d| d.trim().trim_start_matches('.').to_lowercase())
+        .filter(|d| !d.is_empty())
+        .collect();
+
+    let removed: Vec<&RequestDetail> = requests
+        .iter()
+        .filter(|r| is_excluded(&r.domain, &excluded))
+        .collect();
+
+    let removed_requests = removed.len() as u32;
+    let removed_bytes: u64 = removed.iter().map(|r| r.transfer_size).sum();
+    let removed_size_kb = removed_bytes as f64 / 1000.0;
+
+    let simulated_metrics = PageMetrics::new(
+        metrics.dom_elements,
+        metrics.requests.saturating_sub(removed_requests),
+        (metrics.size_kb - removed_size_kb).max(0.0),
+    );
+
+    let original = SimulatedScore::from_metrics(*metrics);
+    let simulated = SimulatedScore::from_metrics(simulated_metrics);
+    let score_delta = simulated.score - original.score;
+
+    ExclusionSimulation {
+        excluded_domains: excluded,
+        removed_requests,
+        removed_size_kb,
+        original,
+        simulated,
+        score_delta,
+    }
+}
+
+/// Modern image format targeted by an image migration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageTargetFormat {
+    /// WebP (the format Lighthouse estimates savings for).
+    #[default]
+    Webp,
+    /// AVIF (estimated from the WebP savings).
+    Avif,
+}
+
+/// Result of converting every convertible image to a modern format.
+///
+/// Only the transfer size changes; DOM and request counts are held constant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageOptimizationSimulation {
+    /// Format the images are converted to.
+    pub target_format: ImageTargetFormat,
+    /// Number of images converted.
+    pub converted_images: u32,
+    /// Transfer size saved in KB.
+    pub saved_size_kb: f64,
+    /// Score with the original metrics.
+    pub original: SimulatedScore,
+    /// Score after conversion.
+    pub simulated: SimulatedScore,
+    /// Score improvement (simulated - original).
+    pub score_delta: f64,
+}
+
+/// Recompute the `EcoIndex` score as if all images listed in `image_formats`
+/// were served as `target_format`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn simulate_image_optimization(
+    metrics: &PageMetrics,
+    image_formats: Option<&ImageFormatAnalytics>,
+    target_format: ImageTargetFormat,
+) -> ImageOptimizationSimulation {
+    let items = image_formats.map_or(&[][..], |formats| formats.items.as_slice());
+
+    let saved_bytes: f64 = items
+        .iter()
+        .map(|item| {
+            let total = item.total_bytes.max(0.0);
+            let wasted = item.wasted_bytes.clamp(0.0, total);
+            match target_format {
+                ImageTargetFormat::Webp => wasted,
+                ImageTargetFormat::Avif => (total - wasted).mul_add(-AVIF_TO_WEBP_RATIO, total),
+            }
+        })
+        .sum();
+    let saved_size_kb = (saved_bytes / 1000.0).min(metrics.size_kb.max(0.0));
+
+    let simulated_metrics = PageMetrics::new(
+        metrics.dom_elements,
+        metrics.requests,
+        metrics.size_kb - saved_size_kb,
+    );
+
+    let original = SimulatedScore::from_metrics(*metrics);
+    let simulated = SimulatedScore::from_metrics(simulated_metrics);
+    let score_delta = simulated.score - original.score;
+
+    ImageOptimizationSimulation {
+        target_format,
+        converted_images: items.len() as u32,
+        saved_size_kb,
+        original,
+        simulated,
+        score_delta,
+    }
+}
+
+/// Check whether a host matches one of the excluded domains (or a subdomain of one).
+fn is_excluded(host: &str, excluded: &[String]) -> bool {
+    let host = host.to_lowercase();
+    excluded.iter().any(|domain| {
+        host == *domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+    use crate::sidecar::ImageFormatItem;
+
+    fn make_request(domain: &str, transfer_size: u64) -> RequestDetail {
+        RequestDetail {
+            url: format!("https://{domain}/resource"),
+            domain: domain.to_string(),
+            transfer_size,
+            resource_size: transfer_size,
+            ..request()
+        }
+    }
+
+    fn heavy_third_party_page() -> (PageMetrics, Vec<RequestDetail>) {
+        let mut requests = vec![make_request("example.com", 200_000)];
+        for _ in 0..60 {
+            requests.push(make_request("ads.tracker.net", 50_000));
+        }
+        let total_bytes: u64 = requests.iter().map(|r| r.transfer_size).sum();
+        #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+        let metrics = PageMetrics::new(800, requests.len() as u32, total_bytes as f64 / 1000.0);
+        (metrics, requests)
+    }
+
+    #[test]
+    fn test_excluding_heavy_domain_improves_score() {
+        let (metrics, requests) = heavy_third_party_page();
+        let simulation = simulate_exclusion(&metrics, &requests, &["tracker.net".to_string()]);
+
+        assert_eq!(simulation.removed_requests, 60);
+        assert!((simulation.removed_size_kb - 3000.0).abs() < f64::EPSILON);
+        assert_eq!(simulation.simulated.metrics.requests, 1);
+        assert!((simulation.simulated.metrics.size_kb - 200.0).abs() < 1e-9);
+        assert!(simulation.score_delta > 0.0);
+        assert!(simulation.simulated.score > simulation.original.score);
+    }
+
+    #[test]
+    fn test_dom_held_constant() {
+        let (metrics, requests) = heavy_third_party_page();
+        let simulation = simulate_exclusion(&metrics, &requests, &["tracker.net".to_string()]);
+
+        assert_eq!(simulation.simulated.metrics.dom_elements, 800);
+    }
+
+    #[test]
+    fn test_unknown_domain_changes_nothing() {
+        let (metrics, requests) = heavy_third_party_page();
+        let simulation = simulate_exclusion(&metrics, &requests, &["other.org".to_string()]);
+
+        assert_eq!(simulation.removed_requests, 0);
+        assert!(simulation.score_delta.abs() < f64::EPSILON);
+    }
+
+    fn image_formats() -> ImageFormatAnalytics {
+        let item = |url: &str, total_bytes: f64, wasted_bytes: f64| ImageFormatItem {
+            url: url.to_string(),
+            from_format: "jpeg".to_string(),
+            total_bytes,
+            wasted_bytes,
+        };
+        ImageFormatAnalytics {
+            potential_savings: 1_500_000.0,
+            items: vec![
+                item("https://example.com/hero.jpg", 2_000_000.0, 1_000_000.0),
+                item("https://example.com/gallery.png", 1_000_000.0, 500_000.0),
+            ],
+            score: 20,
+            adoption_percentage: Some(0.0),
+        }
+    }
+
+    #[test]
+    fn test_image_savings_recompute_score() {
+        let metrics = PageMetrics::new(800, 40, 4000.0);
+        let simulation =
+            simulate_image_optimization(&metrics, Some(&image_formats()), ImageTargetFormat::Webp);
+
+        assert_eq!(simulation.converted_images, 2);
+        assert!((simulation.saved_size_kb - 1500.0).abs() < 1e-9);
+        assert!((simulation.simulated.metrics.size_kb - 2500.0).abs() < 1e-9);
+        assert_eq!(simulation.simulated.metrics.dom_elements, 800);
+        assert_eq!(simulation.simulated.metrics.requests, 40);
+
+        let expected = EcoIndexCalculator::compute_score(&PageMetrics::new(800, 40, 2500.0));
+        assert!((simulation.simulated.score - expected).abs() < 1e-9);
+        assert!(simulation.score_delta > 0.0);
+    }
+
+    #[test]
+    fn test_avif_saves_more_than_webp() {
+        let metrics = PageMetrics::new(800, 40, 4000.0);
+        let webp =
+            simulate_image_optimization(&metrics, Some(&image_formats()), ImageTargetFormat::Webp);
+        let avif =
+            simulate_image_optimization(&metrics, Some(&image_formats()), ImageTargetFormat::Avif);
+
+        // WebP leaves 1500 KB of images, AVIF 80% of that
+        assert!((avif.saved_size_kb - 1800.0).abs() < 1e-9);
+        assert!(avif.simulated.score >= webp.simulated.score);
+    }
+
+    #[test]
+    fn test_no_image_formats_changes_nothing() {
+        let metrics = PageMetrics::new(800, 40, 4000.0);
+        let simulation = simulate_image_optimization(&metrics, None, ImageTargetFormat::Avif);
+
+        assert_eq!(simulation.converted_images, 0);
+        assert!(simulation.score_delta.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_is_excluded_matches_subdomains_only() {
+        let excluded = vec!["tracker.net".to_string()];
+        assert!(is_excluded("tracker.net", &excluded));
+        assert!(is_excluded("ads.Tracker.net", &excluded));
+        assert!(!is_excluded("nottracker.net", &excluded));
+    }
+}
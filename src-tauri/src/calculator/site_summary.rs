@@ -0,0 +1,149 @@
+//! Score distribution over a set of pages.
+//!
+//! Grades are too coarse to compare two sites whose pages mostly land in the
+//! same grade; 10-point score bands show where in the grade they sit.
+
+use serde::{Deserialize, Serialize};
+
+use super::multi_run::median;
+use crate::domain::EcoIndexResult;
+
+/// Width of a score band (points).
+const BAND_WIDTH: u8 = 10;
+
+/// Number of bands covering 0-100.
+const BAND_COUNT: usize = 10;
+
+/// Pages whose score falls in `[min, max)` (`[90, 100]` for the last band).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreBand {
+    /// Lowest score of the band (inclusive).
+    pub min: u8,
+    /// Highest score of the band (exclusive, except 100).
+    pub max: u8,
+    /// Number of pages in the band.
+    pub count: u32,
+}
+
+/// `EcoIndex` scores of a set of pages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteSummary {
+    /// Number of pages with a valid score.
+    pub page_count: u32,
+    /// Mean score (0 without pages).
+    pub average_score: f64,
+    /// Median score (0 without pages).
+    pub median_score: f64,
+    /// Pages per 10-point band, from 0-10 up to 90-100 (always 10 bands).
+    pub score_bands: Vec<ScoreBand>,
+}
+
+impl SiteSummary {
+    /// Summarize the scores of `results`; results with a non-finite score are ignored.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_results(results: &[EcoIndexResult]) -> Self {
+        let scores: Vec<f64> = results
+            .iter()
+            .map(|r| r.score)
+            .filter(|s| s.is_finite())
+            .collect();
+        let page_count = scores.len() as u32;
+        Self {
+            page_count,
+            average_score: if page_count > 0 {
+                scores.iter().sum::<f64>() / f64::from(page_count)
+            } else {
+                0.0
+            },
+            median_score: median(&scores).unwrap_or_default(),
+            score_bands: score_bands(&scores),
+        }
+    }
+}
+
+/// Count `scores` per 10-point band.
+///
+/// A score on a boundary belongs to the band it starts (80.0 is in 80-90);
+/// 100 belongs to the last band. Scores outside 0-100 go to the nearest band.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn score_bands(scores: &[f64]) -> Vec<ScoreBand> {
+    let mut bands: Vec<ScoreBand> = (0..BAND_COUNT as u8)
+        .map(|i| ScoreBand {
+            min: i * BAND_WIDTH,
+            max: (i + 1) * BAND_WIDTH,
+            count: 0,
+        })
+        .collect();
+    for score in scores.iter().filter(|s| s.is_finite()) {
+        let index = (score / f64::from(BAND_WIDTH)).floor().max(0.0) as usize;
+        if let Some(band) = bands.get_mut(index.min(BAND_COUNT - 1)) {
+            band.count += 1;
+        }
+    }
+    bands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculator::EcoIndexCalculator;
+    use crate::domain::PageMetrics;
+
+    fn result_with_score(score: f64) -> EcoIndexResult {
+        let mut result =
+            EcoIndexCalculator::compute(&PageMetrics::new(500, 50, 1000.0), "https://example.com");
+        result.score = score;
+        result
+    }
+
+    fn counts(bands: &[ScoreBand]) -> Vec<u32> {
+        bands.iter().map(|b| b.count).collect()
+    }
+
+    #[test]
+    fn test_boundary_scores() {
+        let bands = score_bands(&[80.0, 79.999, 0.0, 10.0, 100.0, 90.0, 99.9]);
+
+        assert_eq!(bands.len(), 10);
+        assert_eq!((bands[8].min, bands[8].max), (80, 90));
+        assert_eq!(counts(&bands), vec![1, 1, 0, 0, 0, 0, 0, 1, 1, 3]);
+    }
+
+    #[test]
+    fn test_out_of_range_scores_clamped() {
+        let bands = score_bands(&[-5.0, 120.0, f64::NAN]);
+        assert_eq!(counts(&bands), vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_site_summary() {
+        let results: Vec<EcoIndexResult> = [45.0, 80.0, 85.0, 62.0]
+            .into_iter()
+            .map(result_with_score)
+            .collect();
+        let summary = SiteSummary::from_results(&results);
+
+        assert_eq!(summary.page_count, 4);
+        assert!((summary.average_score - 68.0).abs() < 1e-9);
+        assert!((summary.median_score - 71.0).abs() < 1e-9);
+        assert_eq!(
+            counts(&summary.score_bands),
+            vec![0, 0, 0, 0, 1, 0, 1, 0, 2, 0]
+        );
+    }
+
+    #[test]
+    fn test_empty_summary() {
+        let summary = SiteSummary::from_results(&[]);
+
+        assert_eq!(summary.page_count, 0);
+        assert!(summary.average_score.abs() < f64::EPSILON);
+        assert!(summary.median_score.abs() < f64::EPSILON);
+        assert_eq!(summary.score_bands.len(), 10);
+        assert!(summary.score_bands.iter().all(|b| b.count == 0));
+    }
+}
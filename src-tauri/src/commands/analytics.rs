@@ -0,0 +1,181 @@
+//! Analytics on request lists collected outside of the application.
+
+use crate::analytics::{Locale, RequestAnalytics};
+use crate::errors::{AppError, ErrorResponse};
+use crate::sidecar::RequestDetail;
+
+/// Computes the request analytics of `page_url` from an externally-supplied
+/// request list (e.g. converted from a HAR file), without running a browser.
+///
+/// Requests are validated and normalized first: each URL must be absolute,
+/// missing domains and resource types are filled in and timings made consistent.
+/// Analytics labels use `locale` (French when omitted).
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn compute_analytics_from_requests(
+    requests: Vec<RequestDetail>,
+    page_url: String,
+    locale: Option<Locale>,
+) -> Result<RequestAnalytics, ErrorResponse> {
+    Ok(analytics_from_requests(
+        requests,
+        &page_url,
+        locale.unwrap_or_default(),
+    )?)
+}
+
+fn analytics_from_requests(
+    requests: Vec<RequestDetail>,
+    page_url: &str,
+    locale: Locale,
+) -> Result<RequestAnalytics, AppError> {
+    let page = url::Url::parse(page_url)
+        .map_err(|e| AppError::Analysis(format!("Invalid page URL {page_url:?}: {e}")))?;
+    if requests.is_empty() {
+        return Err(AppError::Analysis("No requests to analyze".to_string()));
+    }
+    let requests = requests
+        .into_iter()
+        .enumerate()
+        .map(|(index, request)| {
+            normalize_request(request)
+                .map_err(|e| AppError::Analysis(format!("Invalid request #{index}: {e}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(RequestAnalytics::compute_for_page(
+        &requests,
+        page.as_str(),
+        None,
+        locale,
+    ))
+}
+
+/// Bring a request to the shape the sidecar produces.
+///
+/// The URL must be absolute. A missing domain is taken from the URL and
+/// lowercased, a missing resource type becomes `Other`, and timings are made
+/// consistent: non-finite values count as 0, the end is never before the
+/// start, and the duration is recomputed when it is not usable. Response header
+/// names are lowercased, as the analytics look them up.
+fn normalize_request(mut request: RequestDetail) -> Result<RequestDetail, String> {
+    let url = url::Url::parse(request.url.trim())
+        .map_err(|e| format!("invalid URL {:?}: {e}", request.url))?;
+    request.url = url.to_string();
+
+    if request.domain.trim().is_empty() {
+        request.domain = url.host_str().unwrap_or_default().to_string();
+    }
+    request.domain = request.domain.trim().to_ascii_lowercase();
+    if request.resource_type.trim().is_empty() {
+        request.resource_type = "Other".to_string();
+    }
+    request.response_headers = request
+        .response_headers
+        .into_iter()
+        .map(|(name, value)| (name.to_ascii_lowercase(), value))
+        .collect();
+
+    let finite = |ms: f64| if ms.is_finite() { ms.max(0.0) } else { 0.0 };
+    request.start_time = finite(request.start_time);
+    request.end_time = finite(request.end_time).max(request.start_time);
+    if !request.duration.is_finite() || request.duration <= 0.0 {
+        request.duration = request.end_time - request.start_time;
+    }
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::test_support::request;
+
+    fn make_request(url: &str, resource_type: &str, start_time: f64, size: u64) -> RequestDetail {
+        RequestDetail {
+            url: url.to_string(),
+            domain: url::Url::parse(url)
+                .ok()
+                .and_then(|u| u.host_str().map(str::to_string))
+                .unwrap_or_default(),
+            mime_type: String::new(),
+            resource_type: resource_type.to_string(),
+            transfer_size: size,
+            resource_size: size,
+            start_time,
+            end_time: start_time + 100.0,
+            ..request()
+        }
+    }
+
+    fn page() -> Vec<RequestDetail> {
+        vec![
+            make_request("https://www.example.com/", "Document", 0.0, 20_000),
+            make_request("https://www.example.com/app.js", "Script", 120.0, 80_000),
+            make_request("https://cdn.vendor.net/lib.js", "Script", 150.0, 40_000),
+            make_request("https://www.example.com/hero.jpg", "Image", 300.0, 200_000),
+        ]
+    }
+
+    fn json(analytics: &RequestAnalytics) -> String {
+        serde_json::to_string(analytics).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_matches_in_process_analytics() {
+        let requests = page();
+        let expected = RequestAnalytics::compute_for_page(
+            &requests,
+            "https://www.example.com/",
+            None,
+            Locale::En,
+        );
+        let analytics = analytics_from_requests(requests, "https://www.example.com/", Locale::En);
+
+        assert!(analytics.is_ok_and(|analytics| {
+            analytics.domain_stats.domains.len() == 2
+                && analytics.critical_path.is_some()
+                && json(&analytics) == json(&expected)
+        }));
+    }
+
+    #[test]
+    fn test_requests_are_normalized() {
+        let mut request = make_request("https://CDN.Vendor.net/lib.js", "", 50.0, 1000);
+        request.domain = String::new();
+        request.end_time = f64::NAN;
+        request.duration = -1.0;
+
+        let normalized = normalize_request(request);
+        assert!(normalized.is_ok_and(|r| {
+            r.domain == "cdn.vendor.net"
+                && r.resource_type == "Other"
+                && (r.end_time - 50.0).abs() < f64::EPSILON
+                && r.duration.abs() < f64::EPSILON
+        }));
+    }
+
+    #[test]
+    fn test_header_names_are_lowercased() {
+        let mut requests = page();
+        requests[1].mime_type = "application/javascript".to_string();
+        requests[1]
+            .response_headers
+            .insert("Content-Encoding".to_string(), "br".to_string());
+
+        let analytics = analytics_from_requests(requests, "https://www.example.com/", Locale::En);
+        assert!(analytics.is_ok());
+        let Ok(analytics) = analytics else { return };
+        assert_eq!(analytics.compression_stats.checked_count, 1);
+        assert_eq!(analytics.compression_stats.uncompressed_count, 0);
+    }
+
+    #[test]
+    fn test_invalid_input_rejected() {
+        let mut requests = page();
+        requests[2].url = "lib.js".to_string();
+        let err = analytics_from_requests(requests, "https://www.example.com/", Locale::Fr);
+        assert!(err.is_err_and(|e| e.to_string().contains("Invalid request #2")));
+
+        assert!(analytics_from_requests(page(), "example", Locale::Fr).is_err());
+        assert!(analytics_from_requests(Vec::new(), "https://example.com", Locale::Fr).is_err());
+    }
+}
@@ -1,10 +1,22 @@
 //! `EcoIndex` analysis command.
 
-use crate::browser::{BrowserLauncher, MetricsCollector};
-use crate::calculator::EcoIndexCalculator;
-use crate::domain::EcoIndexResult;
-use crate::errors::BrowserError;
-use crate::utils::resolve_chrome_path;
+use tauri::Manager;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::calculator::budget::{Budget, BudgetCheck};
+use crate::domain::{AnalysisOptions, EcoIndexResult, RawPageMetrics, ScoreSummary, Viewport};
+use crate::errors::{BrowserError, SidecarError};
+use crate::facade::{
+    analyze_url_score_only, analyze_url_with_budget, analyze_url_with_cancel, analyze_viewports,
+    collect_raw_metrics,
+};
+use crate::sidecar::{AnalysisGuard, AnalysisState};
+use crate::utils::{resolve_chrome_path, InFlight};
+
+/// Full analyses in progress, shared by identical concurrent requests.
+pub type InFlightAnalyses = InFlight<Result<EcoIndexResult, BrowserError>>;
 
 /// Analyzes a URL and returns its `EcoIndex` result.
 ///
@@ -13,23 +25,201 @@ use crate::utils::resolve_chrome_path;
 /// 2. Collects page metrics using the `EcoIndex` protocol
 /// 3. Calculates the `EcoIndex` score
 /// 4. Returns the complete result
+///
+/// `options` can add extra headers, cookies or basic-auth credentials to the navigation.
+/// With `options.runs > 1` the page is analyzed several times (fresh browser each
+/// time) and the score is computed from the per-metric medians.
+/// With `options.viewport_snapshot` the initial-viewport metrics are returned too.
+/// `options.headless` selects the browser display mode (headful in debug builds only).
+/// With `options.block_images` images and media are excluded (structure-only analysis).
+/// With `options.route_change` the cost of an in-app navigation is reported too (SPAs).
+///
+/// A request identical to one already in progress (same URL and options) waits
+/// for that analysis and returns its result instead of launching another browser.
 #[tauri::command]
 pub async fn analyze_ecoindex(
     app: tauri::AppHandle,
     url: String,
+    options: Option<AnalysisOptions>,
 ) -> Result<EcoIndexResult, BrowserError> {
     let chrome_path = resolve_chrome_path(&app)?;
+    let options = options.unwrap_or_default();
+
+    let Some(in_flight) = app.try_state::<InFlightAnalyses>() else {
+        return run_analysis(app, chrome_path, url, options).await;
+    };
+    let key = analysis_key(&url, &options);
+    let handle = app.clone();
+    in_flight
+        .run(key, move || run_analysis(handle, chrome_path, url, options))
+        .await
+}
+
+/// Runs a full analysis, holding the shared analysis slot while it lasts.
+async fn run_analysis(
+    app: tauri::AppHandle,
+    chrome_path: PathBuf,
+    url: String,
+    options: AnalysisOptions,
+) -> Result<EcoIndexResult, BrowserError> {
+    let analysis = begin_analysis(&app).await?;
+
+    analyze_url_with_cancel(&chrome_path, &url, &options, analysis.token()).await
+}
+
+/// Analyzes a URL and returns only its score and grade (bulk scanning).
+///
+/// Uses the same collection as [`analyze_ecoindex`] with the same `options`,
+/// without building the detailed result.
+#[tauri::command]
+pub async fn analyze_ecoindex_score_only(
+    app: tauri::AppHandle,
+    url: String,
+    options: Option<AnalysisOptions>,
+) -> Result<ScoreSummary, BrowserError> {
+    let chrome_path = resolve_chrome_path(&app)?;
+    let options = options.unwrap_or_default();
+
+    let analysis = begin_analysis(&app).await?;
+
+    analyze_url_score_only(&chrome_path, &url, &options, analysis.token()).await
+}
+
+/// Analyzes a URL on several emulated screens and returns the results keyed by viewport name.
+///
+/// `viewports` defaults to the mobile, tablet and desktop presets. Viewports are
+/// analyzed one after the other in a shared browser, with the same `options`.
+#[tauri::command]
+pub async fn analyze_ecoindex_viewports(
+    app: tauri::AppHandle,
+    url: String,
+    viewports: Option<Vec<Viewport>>,
+    options: Option<AnalysisOptions>,
+) -> Result<BTreeMap<String, EcoIndexResult>, BrowserError> {
+    let chrome_path = resolve_chrome_path(&app)?;
+    let viewports = viewports.unwrap_or_else(Viewport::presets);
+    let options = options.unwrap_or_default();
+
+    let analysis = begin_analysis(&app).await?;
+
+    analyze_viewports(&chrome_path, &url, &viewports, &options, analysis.token()).await
+}
+
+/// Analyzes a URL and checks the result against `budget` (CI gating).
+///
+/// Uses the same collection as [`analyze_ecoindex`] with the same `options`, but
+/// stops after the first run that exceeds the budget's size or request ceiling.
+#[tauri::command]
+pub async fn analyze_ecoindex_budget(
+    app: tauri::AppHandle,
+    url: String,
+    budget: Budget,
+    options: Option<AnalysisOptions>,
+) -> Result<BudgetCheck, BrowserError> {
+    let chrome_path = resolve_chrome_path(&app)?;
+    let options = options.unwrap_or_default();
+
+    let analysis = begin_analysis(&app).await?;
+
+    analyze_url_with_budget(&chrome_path, &url, &options, &budget, analysis.token()).await
+}
+
+/// Collects the raw metrics of a URL without computing any score (debugging, external tools).
+///
+/// Uses the same collection as [`analyze_ecoindex`] with the same `options`.
+#[tauri::command]
+pub async fn get_raw_metrics(
+    app: tauri::AppHandle,
+    url: String,
+    options: Option<AnalysisOptions>,
+) -> Result<RawPageMetrics, BrowserError> {
+    let chrome_path = resolve_chrome_path(&app)?;
+    let options = options.unwrap_or_default();
+
+    let analysis = begin_analysis(&app).await?;
+
+    collect_raw_metrics(&chrome_path, &url, &options, analysis.token()).await
+}
+
+/// Identifies an analysis by its normalized URL and options.
+///
+/// The fragment is dropped (it does not change what is loaded) and the options
+/// are serialized with sorted keys, so map ordering does not matter.
+fn analysis_key(url: &str, options: &AnalysisOptions) -> String {
+    let url = url::Url::parse(url.trim()).map_or_else(
+        |_| url.trim().to_string(),
+        |mut parsed| {
+            parsed.set_fragment(None);
+            parsed.to_string()
+        },
+    );
+    let mut options = serde_json::to_value(options).unwrap_or_default();
+    options.sort_all_objects();
+    format!("{url} {options}")
+}
+
+/// Registers the analysis with the shared state (when managed) for cancellation.
+///
+/// The analysis slot is released when the returned guard is dropped.
+async fn begin_analysis(app: &tauri::AppHandle) -> Result<AnalysisGuard, BrowserError> {
+    let begun = match app.try_state::<AnalysisState>() {
+        Some(state) => state.begin().await,
+        None => AnalysisState::default().begin().await,
+    };
+    begun.map_err(begin_error)
+}
+
+/// Error for an analysis that could not take the analysis slot.
+///
+/// A busy slot is reported as such: nothing was launched, the user only has to wait.
+fn begin_error(error: SidecarError) -> BrowserError {
+    match error {
+        SidecarError::AnalysisInProgress => BrowserError::AnalysisInProgress,
+        other => BrowserError::LaunchFailed(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let launcher = BrowserLauncher::new(chrome_path);
-    let (browser, handler) = launcher.launch().await?;
+    #[test]
+    fn test_analysis_key_normalization() {
+        let mut options = AnalysisOptions::default();
+        let key = analysis_key("https://Example.com#top", &options);
+        assert_eq!(key, analysis_key(" https://example.com/ ", &options));
 
-    let collector = MetricsCollector::new(&browser);
-    let metrics = collector.collect(&url).await?;
+        options
+            .extra_headers
+            .insert("X-A".to_string(), "1".to_string());
+        options
+            .extra_headers
+            .insert("X-B".to_string(), "2".to_string());
+        let with_headers = analysis_key("https://example.com/", &options);
+        assert_ne!(key, with_headers);
 
-    drop(browser);
-    handler.abort();
+        // Same headers inserted in another order
+        let mut reordered = AnalysisOptions::default();
+        reordered
+            .extra_headers
+            .insert("X-B".to_string(), "2".to_string());
+        reordered
+            .extra_headers
+            .insert("X-A".to_string(), "1".to_string());
+        assert_eq!(
+            with_headers,
+            analysis_key("https://example.com/", &reordered)
+        );
+        assert_ne!(key, analysis_key("https://example.com/other", &options));
+    }
 
-    let result = EcoIndexCalculator::compute(&metrics, &url);
+    #[tokio::test]
+    async fn test_busy_analysis_slot_is_not_a_launch_failure() {
+        let state = AnalysisState::default();
+        let first = state.begin().await.map_err(begin_error);
+        assert!(first.is_ok());
 
-    Ok(result)
+        let second = state.begin().await.map_err(begin_error);
+        assert!(matches!(second, Err(BrowserError::AnalysisInProgress)));
+    }
 }
@@ -0,0 +1,9 @@
+//! Application information command.
+
+use crate::utils::diagnostics::AppInfo;
+
+/// Returns the application name, version, target triple and build profile.
+#[tauri::command]
+pub fn app_info() -> AppInfo {
+    AppInfo::current()
+}
@@ -0,0 +1,21 @@
+//! Baseline comparison command.
+
+use std::path::Path;
+
+use crate::errors::ErrorResponse;
+use crate::export::{BaselineComparison, MonitoredResult};
+
+/// Diffs `result` against the result saved at `baseline_path`.
+///
+/// Returns a "no baseline" marker when the file does not exist.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn compare_to_baseline(
+    result: MonitoredResult,
+    baseline_path: String,
+) -> Result<BaselineComparison, ErrorResponse> {
+    Ok(crate::export::compare_to_baseline(
+        &result,
+        Path::new(&baseline_path),
+    )?)
+}
@@ -0,0 +1,19 @@
+//! Cache management command.
+
+use crate::errors::{AppError, ErrorResponse};
+use crate::storage::EvictionReport;
+use crate::utils::AppPaths;
+
+/// Removes every file of the application cache directory.
+#[tauri::command]
+pub fn clear_cache() -> Result<EvictionReport, ErrorResponse> {
+    let paths = AppPaths::new()
+        .ok_or_else(|| AppError::Config("Application data directory unavailable".to_string()))?;
+    let report = crate::storage::clear_cache(&paths.cache_dir)?;
+    log::info!(
+        "Cleared cache: {} files, {} bytes",
+        report.removed_files,
+        report.removed_bytes
+    );
+    Ok(report)
+}
@@ -0,0 +1,16 @@
+//! Analysis cancellation command.
+
+use tauri::Manager;
+
+use crate::sidecar::AnalysisState;
+
+/// Cancels the analysis in progress (fast path or Lighthouse).
+///
+/// Returns `false` if no analysis was running.
+#[tauri::command]
+pub async fn cancel_analysis(app: tauri::AppHandle) -> bool {
+    match app.try_state::<AnalysisState>() {
+        Some(state) => state.cancel().await,
+        None => false,
+    }
+}
@@ -0,0 +1,14 @@
+//! Custom eco debt command.
+
+use crate::analytics::{DebtWeights, EcoDebt};
+use crate::sidecar::LighthouseResult;
+
+/// Weighs the requests of `result` with `weights` (every kilobyte weighs 1 when omitted).
+///
+/// The debt is an organization-specific indicator; the official `EcoIndex`
+/// score of the result is left unchanged.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn compute_eco_debt(result: LighthouseResult, weights: Option<DebtWeights>) -> EcoDebt {
+    EcoDebt::compute(&result.requests, &result.url, &weights.unwrap_or_default())
+}
@@ -0,0 +1,11 @@
+//! Result export command.
+
+use crate::errors::ErrorResponse;
+use crate::export::{to_json, MonitoredResult};
+
+/// Returns `result` (Lighthouse or `EcoIndex`) as JSON, indented when `pretty` is set.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn export_json(result: MonitoredResult, pretty: bool) -> Result<String, ErrorResponse> {
+    Ok(to_json(&result, pretty)?)
+}
@@ -0,0 +1,37 @@
+//! Analysis history commands.
+
+use crate::errors::{AppError, ErrorResponse};
+use crate::storage::HistoryRecord;
+use crate::utils::AppPaths;
+
+/// Returns every readable record of the analysis history, oldest first.
+#[tauri::command]
+pub fn list_history() -> Result<Vec<HistoryRecord>, ErrorResponse> {
+    Ok(crate::storage::load_history(&history_file()?)?)
+}
+
+/// Returns the history record with the given ID, if any.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn get_history_entry(id: String) -> Result<Option<HistoryRecord>, ErrorResponse> {
+    Ok(crate::storage::get_history_entry(&history_file()?, &id)?)
+}
+
+/// Removes the history record with the given ID.
+///
+/// Returns whether a record was removed.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn delete_history_entry(id: String) -> Result<bool, ErrorResponse> {
+    let deleted = crate::storage::delete_history_entry(&history_file()?, &id)?;
+    if deleted {
+        log::info!("Deleted history entry {id}");
+    }
+    Ok(deleted)
+}
+
+fn history_file() -> Result<std::path::PathBuf, AppError> {
+    let paths = AppPaths::new()
+        .ok_or_else(|| AppError::Config("Application data directory unavailable".to_string()))?;
+    Ok(paths.history_file())
+}
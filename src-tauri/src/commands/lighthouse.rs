@@ -2,8 +2,14 @@
 //!
 //! Full Lighthouse analysis with `EcoIndex` plugin via Node.js sidecar.
 
+use tauri::Manager;
+
+use crate::domain::LighthouseOptions;
 use crate::errors::SidecarError;
-use crate::sidecar::{run_lighthouse_analysis, LighthouseResult};
+use crate::sidecar::{
+    aggregate_runs, run_lighthouse_analysis, AnalysisState, LighthouseResult,
+    DEFAULT_MAX_REQUEST_DETAILS,
+};
 use crate::utils::resolve_chrome_path;
 
 /// Commande Tauri pour l'analyse Lighthouse complète.
@@ -14,12 +20,23 @@ use crate::utils::resolve_chrome_path;
 /// - Best Practices
 /// - SEO
 /// - `EcoIndex` (via plugin)
+///
+/// `options` sets the analytics locale, the number of runs (median result when
+/// more than one), extra sidecar environment variables, the number of request
+/// details returned and a proxy for Chrome; see [`LighthouseOptions`].
+///
+/// All runs share one analysis slot and cancellation token: cancelling stops
+/// the current run and skips the remaining ones.
 #[tauri::command]
 pub async fn analyze_lighthouse(
     app: tauri::AppHandle,
     url: String,
     include_html: bool,
+    options: Option<LighthouseOptions>,
 ) -> Result<LighthouseResult, SidecarError> {
+    let options = options.unwrap_or_default();
+    options.proxy_config().map_err(SidecarError::InvalidProxy)?;
+
     // Résoudre le chemin Chrome
     let chrome_path = resolve_chrome_path(&app)
         .map_err(|e| SidecarError::BinaryNotFound(format!("Chrome not found: {e}")))?;
@@ -28,6 +45,34 @@ pub async fn analyze_lighthouse(
         .to_str()
         .ok_or_else(|| SidecarError::BinaryNotFound("Invalid Chrome path".to_string()))?;
 
-    // Exécuter l'analyse
-    run_lighthouse_analysis(&app, &url, chrome_path_str, include_html).await
+    // Without managed state, the analysis is tracked by a throwaway state
+    let analysis = match app.try_state::<AnalysisState>() {
+        Some(state) => state.begin().await?,
+        None => AnalysisState::default().begin().await?,
+    };
+
+    // Exécuter l'analyse (plusieurs fois si demandé)
+    let mut results = Vec::new();
+    for _ in 0..options.run_count() {
+        results.push(
+            run_lighthouse_analysis(
+                &app,
+                &analysis,
+                &url,
+                chrome_path_str,
+                include_html,
+                &options,
+            )
+            .await?,
+        );
+    }
+
+    let mut result = aggregate_runs(results)
+        .ok_or_else(|| SidecarError::ParseError("No analysis result".to_string()))?;
+    result.cap_request_details(
+        options
+            .max_request_details
+            .unwrap_or(DEFAULT_MAX_REQUEST_DETAILS),
+    );
+    Ok(result)
 }
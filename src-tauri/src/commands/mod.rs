@@ -1,7 +1,36 @@
 //! Tauri command handlers.
 
+mod analytics;
 mod analyze;
+mod app_info;
+mod baseline;
+mod cache;
+mod cancel;
+mod eco_debt;
+mod export;
+mod history;
 mod lighthouse;
+mod monitoring;
+mod recommendations;
+mod self_check;
+mod simulate;
+mod summary;
 
-pub use analyze::analyze_ecoindex;
+pub use analytics::compute_analytics_from_requests;
+pub use analyze::{
+    analyze_ecoindex, analyze_ecoindex_budget, analyze_ecoindex_score_only,
+    analyze_ecoindex_viewports, get_raw_metrics, InFlightAnalyses,
+};
+pub use app_info::app_info;
+pub use baseline::compare_to_baseline;
+pub use cache::clear_cache;
+pub use cancel::cancel_analysis;
+pub use eco_debt::compute_eco_debt;
+pub use export::export_json;
+pub use history::{delete_history_entry, get_history_entry, list_history};
 pub use lighthouse::analyze_lighthouse;
+pub use monitoring::append_monitoring_record;
+pub use recommendations::get_recommendations;
+pub use self_check::self_check;
+pub use simulate::{project_impact, simulate_exclusion, simulate_image_optimization};
+pub use summary::{estimate_download_times, summarize_site};
@@ -0,0 +1,24 @@
+//! Time-series monitoring command.
+
+use crate::errors::{AppError, ErrorResponse};
+use crate::export::{append_jsonl, MonitoredResult, MonitoringRecord};
+use crate::utils::AppPaths;
+
+/// Appends a compact record of `result` to the monitoring file of `url_key`.
+///
+/// Records are stored as JSON lines under the application data directory.
+/// Returns the path of the monitoring file.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn append_monitoring_record(
+    url_key: String,
+    result: MonitoredResult,
+) -> Result<String, ErrorResponse> {
+    let paths = AppPaths::new()
+        .ok_or_else(|| AppError::Config("Application data directory unavailable".to_string()))?;
+    let path = paths.monitoring_file(&url_key);
+
+    append_jsonl(&path, &MonitoringRecord::from(&result))?;
+
+    Ok(path.to_string_lossy().to_string())
+}
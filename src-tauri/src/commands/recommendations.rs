@@ -0,0 +1,14 @@
+//! Grade improvement recommendations command.
+
+use crate::analytics::Locale;
+use crate::calculator::recommendations::{self, Recommendation};
+use crate::domain::EcoIndexResult;
+
+/// Returns next-step recommendations for `result`, the most costly metric first.
+///
+/// Messages use `locale` (French when omitted).
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn get_recommendations(result: EcoIndexResult, locale: Option<Locale>) -> Vec<Recommendation> {
+    recommendations::recommendations_with_locale(&result, locale.unwrap_or_default())
+}
@@ -0,0 +1,45 @@
+//! Environment self-check command.
+
+use crate::sidecar::resolve_lighthouse_script_path;
+use crate::utils::diagnostics::{
+    check_executable, check_file, resolve_node_sidecar_path, CheckKind, CheckResult,
+    SelfCheckReport,
+};
+use crate::utils::{resolve_chrome_path, AppPaths};
+
+/// Verifies that everything needed for an analysis is available.
+///
+/// Checks that Chrome resolves and is executable, the Lighthouse script and
+/// Node.js sidecar exist, and the data directories are writable.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn self_check(app: tauri::AppHandle) -> SelfCheckReport {
+    let paths = AppPaths::new();
+    let data_dir = paths
+        .as_ref()
+        .map(|paths| paths.data_dir.clone())
+        .ok_or_else(|| "Application data directory unavailable".to_string());
+
+    SelfCheckReport::from_checks(vec![
+        CheckResult::from_resolution(
+            CheckKind::Chrome,
+            resolve_chrome_path(&app),
+            check_executable,
+        ),
+        CheckResult::from_resolution(
+            CheckKind::LighthouseScript,
+            resolve_lighthouse_script_path(&app),
+            check_file,
+        ),
+        CheckResult::from_resolution(
+            CheckKind::NodeSidecar,
+            resolve_node_sidecar_path(),
+            check_executable,
+        ),
+        CheckResult::from_resolution(CheckKind::DataDirectories, data_dir, |_| {
+            paths.as_ref().map_or(Ok(()), |paths| {
+                paths.ensure_dirs().map_err(|e| e.to_string())
+            })
+        }),
+    ])
+}
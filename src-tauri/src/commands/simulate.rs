@@ -0,0 +1,52 @@
+//! "What-if" simulation commands.
+
+use crate::calculator::simulation::{
+    self, ExclusionSimulation, ImageOptimizationSimulation, ImageTargetFormat,
+};
+use crate::domain::{AnnualImpact, EcoIndexResult, PageMetrics};
+use crate::sidecar::LighthouseResult;
+
+/// Simulates the `EcoIndex` score of a page without requests to the given domains.
+///
+/// Requests and transfer size are recomputed; the DOM element count is kept as is.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn simulate_exclusion(
+    result: LighthouseResult,
+    excluded_domains: Vec<String>,
+) -> ExclusionSimulation {
+    let metrics = PageMetrics::new(
+        result.ecoindex.dom_elements,
+        result.ecoindex.requests,
+        result.ecoindex.size_kb,
+    );
+    simulation::simulate_exclusion(&metrics, &result.requests, &excluded_domains)
+}
+
+/// Simulates the `EcoIndex` score of a page with its images converted to `target_format`.
+///
+/// Uses the Lighthouse image format savings; DOM and request counts are kept as is.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn simulate_image_optimization(
+    result: LighthouseResult,
+    target_format: Option<ImageTargetFormat>,
+) -> ImageOptimizationSimulation {
+    let metrics = PageMetrics::new(
+        result.ecoindex.dom_elements,
+        result.ecoindex.requests,
+        result.ecoindex.size_kb,
+    );
+    simulation::simulate_image_optimization(
+        &metrics,
+        result.image_formats.as_ref(),
+        target_format.unwrap_or_default(),
+    )
+}
+
+/// Projects the per-view CO2 and water of `result` over a year of `monthly_views`.
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn project_impact(result: EcoIndexResult, monthly_views: u64) -> AnnualImpact {
+    result.annual_impact(monthly_views)
+}
@@ -0,0 +1,18 @@
+//! Summary commands (multi-page scores, download estimates).
+
+use crate::calculator::download::DownloadEstimates;
+use crate::calculator::site_summary::SiteSummary;
+use crate::domain::EcoIndexResult;
+
+/// Summarizes the scores of several analyzed pages (mean, median, 10-point bands).
+#[tauri::command]
+#[allow(clippy::needless_pass_by_value)]
+pub fn summarize_site(results: Vec<EcoIndexResult>) -> SiteSummary {
+    SiteSummary::from_results(&results)
+}
+
+/// Estimates how long `transfer_bytes` take to download on typical connections.
+#[tauri::command]
+pub fn estimate_download_times(transfer_bytes: u64) -> DownloadEstimates {
+    DownloadEstimates::from_bytes(transfer_bytes)
+}
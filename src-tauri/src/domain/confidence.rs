@@ -0,0 +1,198 @@
+//! Measurement confidence checks.
+//!
+//! The fast path counts requests from CDP network events, which can include
+//! redirects and CORS preflights. Comparing with the page's own Resource
+//! Timing entries surfaces counts that are likely wrong.
+//!
+//! The fixed waits of the protocol may also end while a slow page is still
+//! loading, which undercounts requests and size.
+//!
+//! Finally, a URL serving a PDF, an image or JSON has no meaningful DOM: its
+//! grade says nothing about a web page.
+
+use serde::{Deserialize, Serialize};
+
+/// Relative difference above which the two counts are considered divergent.
+const MAX_RELATIVE_DIVERGENCE: f64 = 0.2;
+
+/// Absolute difference below which counts are never considered divergent.
+const MIN_ABSOLUTE_DIFFERENCE: u32 = 3;
+
+/// Quiet time after which the network is considered idle (as in `networkidle0`).
+pub const NETWORK_IDLE_MS: u64 = 500;
+
+/// Request count cross-check between network events and Resource Timing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestCountConfidence {
+    /// Requests counted from network events (the reported value).
+    pub network_count: u32,
+    /// Requests reported by the Resource Timing API (including the document).
+    pub resource_timing_count: u32,
+    /// Relative difference between the two counts (0.0 - 1.0).
+    pub divergence: f64,
+    /// Confidence in the reported count (1.0 - divergence).
+    pub confidence: f64,
+}
+
+impl RequestCountConfidence {
+    /// Compare both counts, returning `Some` only when they diverge beyond the threshold.
+    #[must_use]
+    pub fn check(network_count: u32, resource_timing_count: u32) -> Option<Self> {
+        let difference = network_count.abs_diff(resource_timing_count);
+        let largest = network_count.max(resource_timing_count);
+        if largest == 0 {
+            return None;
+        }
+
+        let divergence = f64::from(difference) / f64::from(largest);
+        if difference < MIN_ABSOLUTE_DIFFERENCE || divergence <= MAX_RELATIVE_DIVERGENCE {
+            return None;
+        }
+
+        Some(Self {
+            network_count,
+            resource_timing_count,
+            divergence,
+            confidence: 1.0 - divergence,
+        })
+    }
+}
+
+/// Load completeness at collection time, based on the readiness outcome and
+/// the last network activity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadCompleteness {
+    /// Whether the readiness condition (network idle, LCP settled) was met
+    /// before its timeout; activity may have resumed afterwards (scrolling,
+    /// interactions).
+    pub load_complete: bool,
+    /// Time since the last network activity, in milliseconds.
+    pub idle_ms: u64,
+}
+
+impl LoadCompleteness {
+    /// Check the readiness outcome and network idle time, returning `Some`
+    /// only when the page may still have been loading.
+    #[must_use]
+    pub const fn check(idle_ms: u64, ready: bool) -> Option<Self> {
+        if ready && idle_ms >= NETWORK_IDLE_MS {
+            return None;
+        }
+        Some(Self {
+            load_complete: ready,
+            idle_ms,
+        })
+    }
+}
+
+/// Main document served with a content type other than HTML/XHTML.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonHtmlDocument {
+    /// MIME type of the main document (`application/pdf`, `image/png`...).
+    pub content_type: String,
+}
+
+impl NonHtmlDocument {
+    /// Check the main document's MIME type, returning `Some` only when it is
+    /// known and not HTML/XHTML. Parameters (`; charset=...`) are ignored.
+    #[must_use]
+    pub fn check(content_type: &str) -> Option<Self> {
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        if mime.is_empty() || mime == "text/html" || mime == "application/xhtml+xml" {
+            return None;
+        }
+        Some(Self { content_type: mime })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching_counts() {
+        assert!(RequestCountConfidence::check(50, 50).is_none());
+        assert!(RequestCountConfidence::check(0, 0).is_none());
+    }
+
+    #[test]
+    fn test_small_divergence_tolerated() {
+        // 10% difference
+        assert!(RequestCountConfidence::check(100, 90).is_none());
+        // Large ratio but tiny absolute difference
+        assert!(RequestCountConfidence::check(3, 1).is_none());
+    }
+
+    #[test]
+    fn test_divergent_counts_flagged() {
+        let check = RequestCountConfidence::check(100, 60);
+        assert!(check.is_some());
+        let Some(check) = check else { return };
+        assert_eq!(check.network_count, 100);
+        assert_eq!(check.resource_timing_count, 60);
+        assert!((check.divergence - 0.4).abs() < 1e-9);
+        assert!((check.confidence - 0.6).abs() < 1e-9);
+
+        // Under-counting is detected too
+        assert!(RequestCountConfidence::check(40, 80).is_some());
+    }
+
+    #[test]
+    fn test_html_document_not_flagged() {
+        assert!(NonHtmlDocument::check("text/html").is_none());
+        assert!(NonHtmlDocument::check("text/html; charset=utf-8").is_none());
+        assert!(NonHtmlDocument::check("Application/XHTML+XML").is_none());
+        // Unknown type: nothing to report
+        assert!(NonHtmlDocument::check("").is_none());
+    }
+
+    #[test]
+    fn test_non_html_document_flagged() {
+        let check = NonHtmlDocument::check("application/pdf");
+        assert_eq!(
+            check,
+            Some(NonHtmlDocument {
+                content_type: "application/pdf".to_string()
+            })
+        );
+        let json = NonHtmlDocument::check("application/json; charset=utf-8");
+        assert!(json.is_some_and(|c| c.content_type == "application/json"));
+        assert!(NonHtmlDocument::check("image/png").is_some());
+    }
+
+    #[test]
+    fn test_idle_network_is_complete() {
+        assert!(LoadCompleteness::check(NETWORK_IDLE_MS, true).is_none());
+        assert!(LoadCompleteness::check(3000, true).is_none());
+        assert!(LoadCompleteness::check(u64::MAX, true).is_none());
+    }
+
+    #[test]
+    fn test_active_network_is_incomplete() {
+        // Readiness met, but activity resumed before collection
+        assert_eq!(
+            LoadCompleteness::check(120, true),
+            Some(LoadCompleteness {
+                load_complete: true,
+                idle_ms: 120,
+            })
+        );
+        // Readiness timed out
+        assert_eq!(
+            LoadCompleteness::check(3000, false),
+            Some(LoadCompleteness {
+                load_complete: false,
+                idle_ms: 3000,
+            })
+        );
+        assert!(LoadCompleteness::check(0, false).is_some_and(|c| !c.load_complete));
+    }
+}
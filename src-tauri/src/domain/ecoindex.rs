@@ -4,7 +4,62 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::metrics::PageMetrics;
+use super::confidence::{LoadCompleteness, NonHtmlDocument, RequestCountConfidence};
+use super::grade::{grade_info, grade_proximity, GradeInfo, GradeProximity};
+use super::metrics::{InlineResources, LazyLoad, PageMetrics, RouteTransition};
+use super::runs::RunStatistics;
+use crate::analytics::{ImageSizingAnalytics, InlineDuplicateAnalytics};
+
+/// Score of the initial viewport (above the fold, before scrolling).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewportSnapshot {
+    /// Metrics captured before scrolling.
+    pub metrics: PageMetrics,
+    /// `EcoIndex` score of the initial viewport.
+    pub score: f64,
+    /// Grade of the initial viewport.
+    pub grade: char,
+    /// Requests and bytes loaded only after scrolling (absent in results stored before it was added).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lazy_load: Option<LazyLoad>,
+}
+
+/// Carbon model used for the `ghg` figure of a result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Co2Model {
+    /// `EcoIndex` formula, derived from the score.
+    #[default]
+    EcoIndex,
+    /// Sustainable Web Design model, derived from transferred bytes (as Website Carbon).
+    Swd,
+    /// `EcoIndex` in `ghg`, with the SWD estimate alongside in `swd_ghg`.
+    Both,
+}
+
+/// Impact of a page projected over a year of traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnualImpact {
+    /// Page views per month used for the projection.
+    pub monthly_views: u64,
+    /// Page views over 12 months.
+    pub annual_views: f64,
+    /// Projected greenhouse gas emissions in kgCO2e.
+    pub co2_kg: f64,
+    /// Projected water consumption in liters.
+    pub water_liters: f64,
+}
+
+/// Score and grade only, for bulk scans where the details are not needed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreSummary {
+    /// `EcoIndex` score (0-100, higher is better).
+    pub score: f64,
+    /// Grade from 'A' (best) to 'G' (worst).
+    pub grade: char,
+}
 
 /// Complete result of an `EcoIndex` analysis.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,16 +68,66 @@ pub struct EcoIndexResult {
     pub score: f64,
     /// Grade from 'A' (best) to 'G' (worst).
     pub grade: char,
-    /// Greenhouse gas emissions in gCO2e per page view.
+    /// Color and label of `grade` (absent in results stored before it was added).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grade_info: Option<GradeInfo>,
+    /// Points missing to reach the next-better grade (absent at grade A).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grade_proximity: Option<GradeProximity>,
+    /// Greenhouse gas emissions in gCO2e per page view, from `co2_model`.
     pub ghg: f64,
+    /// Model used to compute `ghg` (`EcoIndex` in results stored before it was added).
+    #[serde(default)]
+    pub co2_model: Co2Model,
+    /// Sustainable Web Design estimate in gCO2e per page view, with [`Co2Model::Both`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swd_ghg: Option<f64>,
     /// Water consumption in centiliters per page view.
     pub water: f64,
+    /// Estimated energy consumption in Wh per page view (0 in results stored before it was added).
+    #[serde(default)]
+    pub energy_wh: f64,
     /// Raw metrics used for the calculation.
     pub metrics: PageMetrics,
-    /// URL of the analyzed page.
+    /// URL of the analyzed page, as requested.
     pub url: String,
+    /// URL actually measured, after redirects (absent when unknown).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_url: Option<String>,
     /// Timestamp of the analysis (ISO 8601).
     pub timestamp: String,
+    /// Spread across runs when several analyses were aggregated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runs: Option<RunStatistics>,
+    /// Set when the request count disagrees with the page's Resource Timing entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_count_confidence: Option<RequestCountConfidence>,
+    /// Requests that failed or were cancelled, left out of `metrics.requests` (median across runs).
+    #[serde(default)]
+    pub failed_requests: u32,
+    /// Set when the readiness wait timed out or the network was still active at
+    /// collection time (measurement may be incomplete).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_completeness: Option<LoadCompleteness>,
+    /// Set when the URL served something other than an HTML page (PDF, image, JSON...):
+    /// the DOM and request analysis is then meaningless.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub non_html_document: Option<NonHtmlDocument>,
+    /// Initial-viewport metrics, when requested (compare with `metrics` for the full page).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub viewport: Option<ViewportSnapshot>,
+    /// Incremental cost of the requested SPA route change (last run).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub route_transition: Option<RouteTransition>,
+    /// Images served larger than displayed (fast path only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_sizing: Option<ImageSizingAnalytics>,
+    /// Inline script/style bytes, part of the HTML size (fast path only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inline_resources: Option<InlineResources>,
+    /// Inline SVG, script and style contents repeated in the page (fast path only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inline_duplicates: Option<InlineDuplicateAnalytics>,
 }
 
 impl EcoIndexResult {
@@ -33,17 +138,63 @@ impl EcoIndexResult {
         grade: char,
         ghg: f64,
         water: f64,
+        energy_wh: f64,
         metrics: PageMetrics,
         url: String,
     ) -> Self {
         Self {
             score,
             grade,
+            grade_info: Some(grade_info(grade)),
+            grade_proximity: grade_proximity(score),
             ghg,
+            co2_model: Co2Model::EcoIndex,
+            swd_ghg: None,
             water,
+            energy_wh,
             metrics,
             url,
+            final_url: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
+            runs: None,
+            request_count_confidence: None,
+            failed_requests: 0,
+            load_completeness: None,
+            non_html_document: None,
+            viewport: None,
+            route_transition: None,
+            image_sizing: None,
+            inline_resources: None,
+            inline_duplicates: None,
+        }
+    }
+
+    /// Whether the measured page differs from the requested URL.
+    ///
+    /// URLs are compared in normalized form, so `https://a.com` and `https://a.com/` match.
+    #[must_use]
+    pub fn was_redirected(&self) -> bool {
+        let Some(final_url) = &self.final_url else {
+            return false;
+        };
+        match (url::Url::parse(&self.url), url::Url::parse(final_url)) {
+            (Ok(requested), Ok(measured)) => requested != measured,
+            _ => self.url != *final_url,
+        }
+    }
+
+    /// Projects the per-view `ghg` and `water` over 12 months of `monthly_views`.
+    ///
+    /// Computed in `f64` so very large view counts cannot overflow.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn annual_impact(&self, monthly_views: u64) -> AnnualImpact {
+        let annual_views = monthly_views as f64 * 12.0;
+        AnnualImpact {
+            monthly_views,
+            annual_views,
+            co2_kg: annual_views * self.ghg / 1000.0,
+            water_liters: annual_views * self.water / 100.0,
         }
     }
 }
@@ -60,13 +211,83 @@ mod tests {
             'B',
             1.5,
             2.25,
+            1.25,
             metrics,
             "https://example.com".to_string(),
         );
 
         assert!((result.score - 75.5).abs() < f64::EPSILON);
         assert_eq!(result.grade, 'B');
+        assert_eq!(result.grade_info.map(|info| info.letter), Some('B'));
+        assert!(result
+            .grade_proximity
+            .is_some_and(|p| p.next_grade == 'A' && (p.points_needed - 5.5).abs() < 1e-9));
         assert!(!result.timestamp.is_empty());
         assert_eq!(result.url, "https://example.com");
+        assert!((result.energy_wh - 1.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_was_redirected() {
+        let metrics = PageMetrics::new(500, 50, 1000.0);
+        let mut result = EcoIndexResult::new(
+            75.5,
+            'B',
+            1.5,
+            2.25,
+            1.25,
+            metrics,
+            "http://a.com".to_string(),
+        );
+        assert!(!result.was_redirected());
+
+        result.final_url = Some("http://a.com/".to_string());
+        assert!(!result.was_redirected());
+
+        result.final_url = Some("https://a.com/fr/".to_string());
+        assert!(result.was_redirected());
+    }
+
+    #[test]
+    fn test_annual_impact() {
+        let metrics = PageMetrics::new(500, 50, 1000.0);
+        let result = EcoIndexResult::new(
+            75.5,
+            'B',
+            1.5,
+            2.25,
+            1.25,
+            metrics,
+            "https://a.com".to_string(),
+        );
+
+        // 10 000 views/month -> 120 000 views/year
+        let impact = result.annual_impact(10_000);
+        assert!((impact.annual_views - 120_000.0).abs() < f64::EPSILON);
+        assert!((impact.co2_kg - 180.0).abs() < 1e-9);
+        assert!((impact.water_liters - 2700.0).abs() < 1e-9);
+
+        let none = result.annual_impact(0);
+        assert!(none.co2_kg.abs() < f64::EPSILON);
+        assert!(none.water_liters.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_annual_impact_large_view_count() {
+        let metrics = PageMetrics::new(500, 50, 1000.0);
+        let result = EcoIndexResult::new(
+            75.5,
+            'B',
+            1.5,
+            2.25,
+            1.25,
+            metrics,
+            "https://a.com".to_string(),
+        );
+
+        let impact = result.annual_impact(u64::MAX);
+        assert!(impact.annual_views.is_finite());
+        assert!(impact.co2_kg.is_finite() && impact.co2_kg > 0.0);
+        assert!(impact.water_liters.is_finite() && impact.water_liters > 0.0);
     }
 }
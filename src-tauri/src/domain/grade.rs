@@ -0,0 +1,169 @@
+//! Display information for `EcoIndex` grades.
+//!
+//! Single source of the grade palette and labels shown by the frontend.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::quantiles::GRADE_THRESHOLDS;
+
+/// Color and label of an `EcoIndex` grade.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GradeInfo {
+    /// Grade letter ('A' to 'G').
+    pub letter: char,
+    /// Hex color of the grade.
+    pub color: String,
+    /// Human-readable label (French, matches the application UI).
+    pub label: String,
+}
+
+/// Color and label for each grade, from A (best) to G (worst).
+pub const GRADE_PALETTE: [(char, &str, &str); 7] = [
+    ('A', "#349a47", "Excellent"),
+    ('B', "#51b84b", "Très bien"),
+    ('C', "#cadb2a", "Bien"),
+    ('D', "#f6eb15", "Moyen"),
+    ('E', "#fecd06", "Médiocre"),
+    ('F', "#f99839", "Mauvais"),
+    ('G', "#ed2124", "Très mauvais"),
+];
+
+/// Returns the display information for `grade`.
+///
+/// Unknown letters get the 'G' color and label, keeping the given letter.
+#[must_use]
+pub fn grade_info(grade: char) -> GradeInfo {
+    let upper = grade.to_ascii_uppercase();
+    let (_, color, label) = GRADE_PALETTE
+        .iter()
+        .find(|(letter, _, _)| *letter == upper)
+        .unwrap_or(&GRADE_PALETTE[GRADE_PALETTE.len() - 1]);
+
+    GradeInfo {
+        letter: upper,
+        color: (*color).to_string(),
+        label: (*label).to_string(),
+    }
+}
+
+/// Distance from a score to the next-better grade.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GradeProximity {
+    /// Next-better grade.
+    pub next_grade: char,
+    /// Minimum score of that grade.
+    pub threshold: f64,
+    /// Points missing to reach it (always positive).
+    pub points_needed: f64,
+}
+
+impl fmt::Display for GradeProximity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:.1} points from grade {}",
+            self.points_needed, self.next_grade
+        )
+    }
+}
+
+/// How far `score` is from the next-better grade boundary.
+///
+/// `None` at grade A (nothing better to reach) or for a NaN score.
+#[must_use]
+pub fn grade_proximity(score: f64) -> Option<GradeProximity> {
+    if score.is_nan() {
+        return None;
+    }
+    // Scores below 0 are graded G like 0
+    let index = GRADE_THRESHOLDS
+        .iter()
+        .position(|(threshold, _)| score >= *threshold)
+        .unwrap_or(GRADE_THRESHOLDS.len() - 1);
+    let &(threshold, next_grade) = GRADE_THRESHOLDS.get(index.checked_sub(1)?)?;
+    Some(GradeProximity {
+        next_grade,
+        threshold,
+        points_needed: threshold - score,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_grade_maps_to_documented_color_and_label() {
+        let expected = [
+            ('A', "#349a47", "Excellent"),
+            ('B', "#51b84b", "Très bien"),
+            ('C', "#cadb2a", "Bien"),
+            ('D', "#f6eb15", "Moyen"),
+            ('E', "#fecd06", "Médiocre"),
+            ('F', "#f99839", "Mauvais"),
+            ('G', "#ed2124", "Très mauvais"),
+        ];
+        for (letter, color, label) in expected {
+            let info = grade_info(letter);
+            assert_eq!(info.letter, letter);
+            assert_eq!(info.color, color);
+            assert_eq!(info.label, label);
+        }
+    }
+
+    #[test]
+    fn test_lowercase_and_unknown_grades() {
+        assert_eq!(grade_info('b').letter, 'B');
+        let unknown = grade_info('?');
+        assert_eq!(unknown.letter, '?');
+        assert_eq!(unknown.color, "#ed2124");
+    }
+
+    #[test]
+    fn test_proximity_just_below_boundary() {
+        let proximity = grade_proximity(78.7);
+        assert!(proximity.is_some(), "grade B has a better grade");
+        let Some(proximity) = proximity else { return };
+        assert_eq!(proximity.next_grade, 'A');
+        assert!((proximity.threshold - 81.0).abs() < f64::EPSILON);
+        assert!((proximity.points_needed - 2.3).abs() < 1e-9);
+        assert_eq!(proximity.to_string(), "2.3 points from grade A");
+
+        // Exactly on a boundary: the whole next band is still ahead
+        let on_boundary = grade_proximity(71.0);
+        assert!(on_boundary
+            .is_some_and(|p| p.next_grade == 'A' && (p.points_needed - 10.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_proximity_within_band() {
+        let proximity = grade_proximity(52.0);
+        assert!(proximity.is_some(), "grade D has a better grade");
+        let Some(proximity) = proximity else { return };
+        assert_eq!(proximity.next_grade, 'C');
+        assert!((proximity.points_needed - 9.0).abs() < 1e-9);
+
+        let worst = grade_proximity(0.0);
+        assert!(worst.is_some_and(|p| p.next_grade == 'F' && (p.points_needed - 31.0).abs() < 1e-9));
+        assert!(grade_proximity(-3.0).is_some_and(|p| p.next_grade == 'F'));
+    }
+
+    #[test]
+    fn test_proximity_at_grade_a() {
+        assert!(grade_proximity(81.0).is_none());
+        assert!(grade_proximity(100.0).is_none());
+        assert!(grade_proximity(f64::NAN).is_none());
+    }
+
+    #[test]
+    fn test_serializes_camel_case() {
+        let json = serde_json::to_value(grade_info('A')).unwrap_or_default();
+        assert_eq!(json["letter"], "A");
+        assert_eq!(json["color"], "#349a47");
+        assert_eq!(json["label"], "Excellent");
+    }
+}
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::analytics::safe_percentage;
+
 /// Raw metrics collected from a web page for `EcoIndex` calculation.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct PageMetrics {
@@ -15,12 +17,140 @@ pub struct PageMetrics {
 
 impl PageMetrics {
     /// Creates a new `PageMetrics` instance.
+    ///
+    /// `size_kb` is sanitized: NaN and negative sizes become 0 and infinite
+    /// sizes are clamped to the largest finite value.
     #[must_use]
-    pub const fn new(dom_elements: u32, requests: u32, size_kb: f64) -> Self {
+    pub fn new(dom_elements: u32, requests: u32, size_kb: f64) -> Self {
         Self {
             dom_elements,
+            requests,
+            size_kb: Self::sanitize_size_kb(size_kb),
+        }
+    }
+
+    /// Returns a finite, non-negative size.
+    #[must_use]
+    pub fn sanitize_size_kb(size_kb: f64) -> f64 {
+        if size_kb.is_nan() || size_kb <= 0.0 {
+            0.0
+        } else if size_kb.is_infinite() {
+            f64::MAX
+        } else {
+            size_kb
+        }
+    }
+}
+
+/// Inline `<script>` and `<style>` content, measured in the page.
+///
+/// Inline blocks are part of the HTML size and are not cacheable separately.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InlineResources {
+    /// Number of `<script>` elements without `src`.
+    pub script_count: u32,
+    /// UTF-8 bytes of inline script content.
+    pub script_bytes: u64,
+    /// Number of `<style>` elements.
+    pub style_count: u32,
+    /// UTF-8 bytes of inline style content.
+    pub style_bytes: u64,
+}
+
+impl InlineResources {
+    /// Total inline script and style bytes.
+    #[must_use]
+    pub const fn total_bytes(&self) -> u64 {
+        self.script_bytes + self.style_bytes
+    }
+}
+
+/// Page metrics as collected, before any scoring.
+///
+/// Meant for external scoring tools and for checking collection between runs.
+/// Per-request details are only captured by the Lighthouse analysis.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawPageMetrics {
+    /// Per-metric medians across runs: the inputs of the score.
+    pub metrics: PageMetrics,
+    /// Metrics of each run, in order.
+    pub runs: Vec<PageMetrics>,
+    /// Resource Timing entries (median across runs, absent when a run had none).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_timing_requests: Option<u32>,
+    /// Requests that failed (median across runs).
+    pub failed_requests: u32,
+    /// URL measured in the last run, after redirects (absent when unknown).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_url: Option<String>,
+}
+
+/// Incremental cost of a client-side route change in a single-page app.
+///
+/// Full-page analyses only see the first load; this measures what the app
+/// fetches and renders when the user navigates inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteTransition {
+    /// Metrics before the route change.
+    pub before: PageMetrics,
+    /// Metrics after the route change (requests and size are cumulative).
+    pub after: PageMetrics,
+    /// DOM element change (negative when the new route renders less).
+    pub dom_elements_delta: i64,
+    /// Requests issued by the route change.
+    pub requests_delta: i64,
+    /// Kilobytes transferred by the route change.
+    pub size_kb_delta: f64,
+}
+
+impl RouteTransition {
+    /// Delta between two snapshots of the same page.
+    #[must_use]
+    pub fn between(before: PageMetrics, after: PageMetrics) -> Self {
+        Self {
+            before,
+            after,
+            dom_elements_delta: i64::from(after.dom_elements) - i64::from(before.dom_elements),
+            requests_delta: i64::from(after.requests) - i64::from(before.requests),
+            size_kb_delta: after.size_kb - before.size_kb,
+        }
+    }
+}
+
+/// Content loaded only once the page is scrolled.
+///
+/// Compares the initial-viewport snapshot with the full-page metrics: a high
+/// lazy-load share means the first view costs less than the whole page.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LazyLoad {
+    /// Requests issued after scrolling.
+    pub requests: u32,
+    /// Kilobytes transferred after scrolling.
+    pub size_kb: f64,
+    /// Share of the page requests issued after scrolling (0-100).
+    pub request_percentage: f64,
+    /// Share of the page weight transferred after scrolling (0-100).
+    pub size_percentage: f64,
+}
+
+impl LazyLoad {
+    /// Content loaded between the `viewport` snapshot and the `full_page` metrics.
+    ///
+    /// Counters are cumulative, so a full page below the viewport (a run
+    /// mixing samples) counts as nothing lazy-loaded.
+    #[must_use]
+    pub fn between(viewport: &PageMetrics, full_page: &PageMetrics) -> Self {
+        let requests = full_page.requests.saturating_sub(viewport.requests);
+        let size_kb = (full_page.size_kb - viewport.size_kb).max(0.0);
+        Self {
             requests,
             size_kb,
+            request_percentage: safe_percentage(f64::from(requests), f64::from(full_page.requests)),
+            size_percentage: safe_percentage(size_kb, full_page.size_kb),
         }
     }
 }
@@ -47,6 +177,64 @@ mod tests {
         assert!((m.size_kb - 1024.5).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_new_sanitizes_size() {
+        assert!(PageMetrics::new(1, 1, f64::NAN).size_kb.abs() < f64::EPSILON);
+        assert!(PageMetrics::new(1, 1, -12.0).size_kb.abs() < f64::EPSILON);
+        assert!(PageMetrics::new(1, 1, f64::NEG_INFINITY).size_kb.abs() < f64::EPSILON);
+        assert!(PageMetrics::new(1, 1, f64::INFINITY).size_kb.is_finite());
+    }
+
+    #[test]
+    fn test_lazy_load_ratio() {
+        let viewport = PageMetrics::new(300, 10, 100.0);
+        let full_page = PageMetrics::new(400, 40, 400.0);
+        let lazy = LazyLoad::between(&viewport, &full_page);
+
+        assert_eq!(lazy.requests, 30);
+        assert!((lazy.size_kb - 300.0).abs() < 1e-9);
+        assert!((lazy.request_percentage - 75.0).abs() < 1e-9);
+        assert!((lazy.size_percentage - 75.0).abs() < 1e-9);
+
+        // Nothing loaded on scroll, or inconsistent snapshots
+        let none = LazyLoad::between(&full_page, &full_page);
+        assert_eq!(none.requests, 0);
+        assert!(none.size_percentage.abs() < f64::EPSILON);
+        let inverted = LazyLoad::between(&full_page, &viewport);
+        assert_eq!(inverted.requests, 0);
+        assert!(inverted.size_kb.abs() < f64::EPSILON);
+        let empty = LazyLoad::between(&PageMetrics::default(), &PageMetrics::default());
+        assert!(empty.request_percentage.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_inline_resources_payload_shape() {
+        let payload = r#"{"scriptCount":2,"scriptBytes":1500,"styleCount":1,"styleBytes":300}"#;
+        let inline: InlineResources = serde_json::from_str(payload).unwrap_or_default();
+        assert_eq!(inline.script_count, 2);
+        assert_eq!(inline.script_bytes, 1500);
+        assert_eq!(inline.style_count, 1);
+        assert_eq!(inline.style_bytes, 300);
+        assert_eq!(inline.total_bytes(), 1800);
+    }
+
+    #[test]
+    fn test_route_transition_delta() {
+        let before = PageMetrics::new(800, 40, 1200.0);
+        let after = PageMetrics::new(650, 52, 1375.5);
+        let transition = RouteTransition::between(before, after);
+
+        assert_eq!(transition.dom_elements_delta, -150);
+        assert_eq!(transition.requests_delta, 12);
+        assert!((transition.size_kb_delta - 175.5).abs() < 1e-9);
+        assert_eq!(transition.before, before);
+        assert_eq!(transition.after, after);
+
+        let unchanged = RouteTransition::between(before, before);
+        assert_eq!(unchanged.requests_delta, 0);
+        assert!(unchanged.size_kb_delta.abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_default() {
         let m = PageMetrics::default();
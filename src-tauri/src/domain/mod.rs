@@ -2,11 +2,26 @@
 //!
 //! This module contains all domain types used throughout the application.
 
+mod confidence;
 mod ecoindex;
+mod grade;
 mod lighthouse;
 mod metrics;
+mod network;
+mod options;
 pub mod quantiles;
+mod runs;
 
-pub use ecoindex::EcoIndexResult;
+pub use confidence::{LoadCompleteness, NonHtmlDocument, RequestCountConfidence, NETWORK_IDLE_MS};
+pub use ecoindex::{AnnualImpact, Co2Model, EcoIndexResult, ScoreSummary, ViewportSnapshot};
+pub use grade::{grade_info, grade_proximity, GradeInfo, GradeProximity, GRADE_PALETTE};
 pub use lighthouse::{CoreWebVitals, LighthouseResult, MetricStatus, PerformanceMetrics};
-pub use metrics::PageMetrics;
+pub use metrics::{InlineResources, LazyLoad, PageMetrics, RawPageMetrics, RouteTransition};
+pub use network::NetworkProfile;
+pub use options::{
+    AnalysisOptions, BasicAuth, ChromeLimits, CookieSpec, HeadlessMode, Interaction,
+    LighthouseOptions, MockResponse, ProxyConfig, ReadinessStrategy, RouteChange, Viewport,
+    EMPTY_PAGE_DOM_THRESHOLD, MAX_NICE, MAX_READINESS_WAIT_MS, MAX_RUNS, MAX_SELECTOR_LEN,
+    MAX_VIEWPORT_SIDE, MIN_JS_HEAP_MB, PROXY_SCHEMES,
+};
+pub use runs::{MetricRange, RunStatistics};
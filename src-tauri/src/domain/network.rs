@@ -0,0 +1,66 @@
+//! Connection types used to put page weight in real-world terms.
+
+use serde::{Deserialize, Serialize};
+
+/// Typical connection, with the throttling values of Chrome `DevTools` and Lighthouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NetworkProfile {
+    /// `DevTools` "Slow 3G".
+    Slow3g,
+    /// `DevTools` "Fast 3G".
+    Fast3g,
+    /// Lighthouse mobile throttling ("Slow 4G").
+    Slow4g,
+    /// `DevTools` "Fast 4G".
+    Fast4g,
+    /// Lighthouse desktop throttling (cable / fiber).
+    Broadband,
+}
+
+impl NetworkProfile {
+    /// Every profile, slowest first.
+    pub const ALL: [Self; 5] = [
+        Self::Slow3g,
+        Self::Fast3g,
+        Self::Slow4g,
+        Self::Fast4g,
+        Self::Broadband,
+    ];
+
+    /// Display name.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Slow3g => "Slow 3G",
+            Self::Fast3g => "Fast 3G",
+            Self::Slow4g => "Slow 4G",
+            Self::Fast4g => "Fast 4G",
+            Self::Broadband => "Broadband",
+        }
+    }
+
+    /// Download throughput (kbit/s, 1 kbit = 1000 bits).
+    #[must_use]
+    pub const fn throughput_kbps(self) -> f64 {
+        match self {
+            Self::Slow3g => 400.0,
+            Self::Fast3g => 1_440.0,
+            Self::Slow4g => 1_638.4,
+            Self::Fast4g => 8_100.0,
+            Self::Broadband => 10_240.0,
+        }
+    }
+
+    /// Round-trip time (ms).
+    #[must_use]
+    pub const fn latency_ms(self) -> f64 {
+        match self {
+            Self::Slow3g => 2_000.0,
+            Self::Fast3g => 562.5,
+            Self::Slow4g => 150.0,
+            Self::Fast4g => 165.0,
+            Self::Broadband => 40.0,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Multi-run statistics.
+//!
+//! Spread of the main metrics when a page is analyzed several times.
+
+use serde::{Deserialize, Serialize};
+
+/// Minimum, median and maximum of a metric across runs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MetricRange {
+    /// Lowest observed value.
+    pub min: f64,
+    /// Median value.
+    pub median: f64,
+    /// Highest observed value.
+    pub max: f64,
+}
+
+impl MetricRange {
+    /// Difference between the highest and lowest values.
+    #[must_use]
+    pub fn spread(&self) -> f64 {
+        self.max - self.min
+    }
+}
+
+/// Variance of the `EcoIndex` inputs and score across repeated runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunStatistics {
+    /// Number of runs aggregated.
+    pub runs: u32,
+    /// `EcoIndex` score of each run.
+    pub score: MetricRange,
+    /// Number of DOM elements.
+    pub dom_elements: MetricRange,
+    /// Number of HTTP requests.
+    pub requests: MetricRange,
+    /// Page size in KB.
+    pub size_kb: MetricRange,
+}
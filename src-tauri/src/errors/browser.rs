@@ -4,7 +4,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 /// Errors related to browser operations.
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum BrowserError {
     /// Chrome/Chromium not found on the system.
     #[error("Chrome browser not found: {0}")]
@@ -45,6 +45,34 @@ pub enum BrowserError {
     /// Invalid URL provided.
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
+
+    /// Invalid cookie in the analysis options.
+    #[error("Invalid cookie: {0}")]
+    InvalidCookie(String),
+
+    /// Invalid emulated viewport in the analysis options.
+    #[error("Invalid viewport: {0}")]
+    InvalidViewport(String),
+
+    /// Browser display mode not available in this build.
+    #[error("Invalid headless mode: {0}")]
+    InvalidHeadlessMode(String),
+
+    /// Invalid proxy in the analysis options.
+    #[error("Invalid proxy: {0}")]
+    InvalidProxy(String),
+
+    /// The browser crashed or its connection dropped during the analysis.
+    #[error("Browser crashed: {0}")]
+    BrowserCrashed(String),
+
+    /// Another analysis is still running; this one was not started.
+    #[error("An analysis is already running, retry once it has finished")]
+    AnalysisInProgress,
+
+    /// Analysis cancelled by the user.
+    #[error("Analysis cancelled")]
+    Cancelled,
 }
 
 impl Serialize for BrowserError {
@@ -8,6 +8,8 @@ mod sidecar;
 pub use browser::BrowserError;
 pub use sidecar::SidecarError;
 
+use std::path::PathBuf;
+
 use serde::Serialize;
 use thiserror::Error;
 
@@ -30,9 +32,26 @@ pub enum AppError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    /// Binary (`MessagePack`) serialization errors, with the `binary-history` feature.
+    #[error("Binary serialization error: {0}")]
+    BinarySerialization(String),
+
     /// Configuration errors.
     #[error("Configuration error: {0}")]
     Config(String),
+
+    /// Analysis or calculation errors (invalid metrics, inconsistent results).
+    #[error("Analysis error: {0}")]
+    Analysis(String),
+
+    /// An application directory cannot be created or does not accept new files.
+    #[error("Directory {} is not writable: {reason}", path.display())]
+    UnwritableDirectory {
+        /// The directory.
+        path: PathBuf,
+        /// Underlying I/O error.
+        reason: String,
+    },
 }
 
 /// Error response for Tauri commands.
@@ -47,11 +66,15 @@ pub struct ErrorResponse {
 impl From<AppError> for ErrorResponse {
     fn from(error: AppError) -> Self {
         let code = match &error {
+            AppError::Browser(BrowserError::AnalysisInProgress)
+            | AppError::Sidecar(SidecarError::AnalysisInProgress) => "ANALYSIS_IN_PROGRESS",
             AppError::Browser(_) => "BROWSER_ERROR",
             AppError::Sidecar(_) => "SIDECAR_ERROR",
             AppError::Io(_) => "IO_ERROR",
-            AppError::Serialization(_) => "SERIALIZATION_ERROR",
+            AppError::Serialization(_) | AppError::BinarySerialization(_) => "SERIALIZATION_ERROR",
             AppError::Config(_) => "CONFIG_ERROR",
+            AppError::Analysis(_) => "ANALYSIS_ERROR",
+            AppError::UnwritableDirectory { .. } => "UNWRITABLE_DIRECTORY",
         };
         Self {
             message: error.to_string(),
@@ -60,5 +83,80 @@ impl From<AppError> for ErrorResponse {
     }
 }
 
+impl From<BrowserError> for ErrorResponse {
+    fn from(error: BrowserError) -> Self {
+        AppError::from(error).into()
+    }
+}
+
+impl From<SidecarError> for ErrorResponse {
+    fn from(error: SidecarError) -> Self {
+        AppError::from(error).into()
+    }
+}
+
 /// Result type alias using `AppError`.
 pub type Result<T> = std::result::Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code_of(error: impl Into<ErrorResponse>) -> String {
+        error.into().code
+    }
+
+    #[test]
+    fn test_app_error_codes() {
+        assert_eq!(code_of(AppError::Config("x".to_string())), "CONFIG_ERROR");
+        assert_eq!(
+            code_of(AppError::Analysis("x".to_string())),
+            "ANALYSIS_ERROR"
+        );
+        assert_eq!(
+            code_of(AppError::Io(std::io::Error::other("x"))),
+            "IO_ERROR"
+        );
+        let json_error = serde_json::from_str::<u32>("x").err();
+        assert!(json_error.is_some());
+        if let Some(json_error) = json_error {
+            assert_eq!(code_of(AppError::from(json_error)), "SERIALIZATION_ERROR");
+        }
+    }
+
+    #[test]
+    fn test_browser_and_sidecar_errors_convert_directly() {
+        assert_eq!(code_of(BrowserError::Cancelled), "BROWSER_ERROR");
+        assert_eq!(
+            code_of(BrowserError::AnalysisInProgress),
+            "ANALYSIS_IN_PROGRESS"
+        );
+        assert_eq!(
+            code_of(SidecarError::AnalysisInProgress),
+            "ANALYSIS_IN_PROGRESS"
+        );
+        assert_eq!(code_of(SidecarError::Timeout(1000)), "SIDECAR_ERROR");
+
+        let response = ErrorResponse::from(BrowserError::InvalidUrl("foo".to_string()));
+        assert_eq!(response.message, "Invalid URL: foo");
+    }
+
+    #[test]
+    fn test_analysis_error_message() {
+        let response = ErrorResponse::from(AppError::Analysis("no requests".to_string()));
+        assert_eq!(response.message, "Analysis error: no requests");
+    }
+
+    #[test]
+    fn test_unwritable_directory_names_the_directory() {
+        let response = ErrorResponse::from(AppError::UnwritableDirectory {
+            path: PathBuf::from("/data/logs"),
+            reason: "permission denied".to_string(),
+        });
+        assert_eq!(response.code, "UNWRITABLE_DIRECTORY");
+        assert_eq!(
+            response.message,
+            "Directory /data/logs is not writable: permission denied"
+        );
+    }
+}
@@ -43,6 +43,27 @@ pub enum SidecarError {
         /// Error message.
         message: String,
     },
+
+    /// Sidecar output format not supported by this version of the app.
+    #[error("Sidecar version {found} incompatible, expected {expected}")]
+    IncompatibleVersion {
+        /// Protocol version reported by the sidecar.
+        found: u32,
+        /// Supported protocol versions.
+        expected: String,
+    },
+
+    /// Invalid proxy given for the analysis.
+    #[error("Invalid proxy: {0}")]
+    InvalidProxy(String),
+
+    /// Another analysis is still running; this one was not started.
+    #[error("An analysis is already running, retry once it has finished")]
+    AnalysisInProgress,
+
+    /// Analysis cancelled by the user.
+    #[error("Analysis cancelled")]
+    Cancelled,
 }
 
 impl Serialize for SidecarError {
@@ -0,0 +1,193 @@
+//! Comparison of a result against a baseline saved on disk.
+//!
+//! Meant for CI workflows: a result exported with `export_json` is committed
+//! as the reference, and later analyses are diffed against it.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::jsonl::{MonitoredResult, MonitoringRecord};
+use crate::errors::{AppError, Result};
+
+/// Differences between a result and its baseline (`current - baseline`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EcoIndexDiff {
+    /// URL of the baseline result.
+    pub baseline_url: String,
+    /// Timestamp of the baseline result.
+    pub baseline_timestamp: String,
+    /// Score change (positive is an improvement).
+    pub score_delta: f64,
+    /// Grade of the baseline.
+    pub baseline_grade: String,
+    /// Grade of the current result.
+    pub grade: String,
+    /// Page size change in KB.
+    pub size_kb_delta: f64,
+    /// Request count change.
+    pub requests_delta: i64,
+    /// DOM element count change.
+    pub dom_elements_delta: i64,
+    /// Lighthouse performance score change, when both results are Lighthouse
+    /// analyses with a measured performance score.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub performance_delta: Option<i64>,
+}
+
+impl EcoIndexDiff {
+    /// Diff `current` against `baseline`.
+    #[must_use]
+    pub fn between(baseline: &MonitoredResult, current: &MonitoredResult) -> Self {
+        let before = MonitoringRecord::from(baseline);
+        let after = MonitoringRecord::from(current);
+        let performance_delta = match (baseline, current) {
+            (MonitoredResult::Lighthouse(before), MonitoredResult::Lighthouse(after)) => after
+                .performance
+                .performance_score
+                .zip(before.performance.performance_score)
+                .map(|(after, before)| i64::from(after) - i64::from(before)),
+            _ => None,
+        };
+        Self {
+            baseline_url: before.url,
+            baseline_timestamp: before.timestamp,
+            score_delta: after.score - before.score,
+            baseline_grade: before.grade,
+            grade: after.grade,
+            size_kb_delta: after.size_kb - before.size_kb,
+            requests_delta: i64::from(after.requests) - i64::from(before.requests),
+            dom_elements_delta: i64::from(after.dom_elements) - i64::from(before.dom_elements),
+            performance_delta,
+        }
+    }
+
+    /// Whether the score dropped compared to the baseline.
+    #[must_use]
+    pub fn is_regression(&self) -> bool {
+        self.score_delta < 0.0
+    }
+}
+
+/// Outcome of a baseline comparison.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum BaselineComparison {
+    /// No baseline file exists yet at the given path.
+    NoBaseline {
+        /// Path that was looked up.
+        path: String,
+    },
+    /// The baseline was found and compared.
+    Compared {
+        /// Differences with the baseline.
+        diff: EcoIndexDiff,
+    },
+}
+
+/// Diff `result` against the result saved at `baseline_path`.
+///
+/// A missing file yields [`BaselineComparison::NoBaseline`], so the first CI
+/// run can create the baseline.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not an analysis result.
+pub fn compare_to_baseline(
+    result: &MonitoredResult,
+    baseline_path: &Path,
+) -> Result<BaselineComparison> {
+    if !baseline_path.exists() {
+        return Ok(BaselineComparison::NoBaseline {
+            path: baseline_path.to_string_lossy().to_string(),
+        });
+    }
+    let baseline = load_baseline(baseline_path)?;
+    Ok(BaselineComparison::Compared {
+        diff: EcoIndexDiff::between(&baseline, result),
+    })
+}
+
+/// Read and validate a result saved with `export_json`.
+fn load_baseline(path: &Path) -> Result<MonitoredResult> {
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| {
+        AppError::Config(format!(
+            "Baseline {} is not an analysis result: {e}",
+            path.display()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{EcoIndexResult, PageMetrics};
+    use crate::export::to_json;
+
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("ecoindex-baseline-{}", std::process::id()));
+        let _ = std::fs::create_dir_all(&dir);
+        dir.join(name)
+    }
+
+    fn result(score: f64, grade: char, requests: u32) -> MonitoredResult {
+        MonitoredResult::EcoIndex(Box::new(EcoIndexResult::new(
+            score,
+            grade,
+            2.0,
+            3.0,
+            1.0,
+            PageMetrics::new(600, requests, 900.0),
+            "https://example.com".to_string(),
+        )))
+    }
+
+    #[test]
+    fn test_compare_with_saved_baseline() {
+        let path = temp_file("present.json");
+        let saved = to_json(&result(80.0, 'A', 40), true).unwrap_or_default();
+        let _ = std::fs::write(&path, saved);
+
+        let comparison = compare_to_baseline(&result(70.0, 'B', 55), &path);
+        assert!(
+            matches!(comparison, Ok(BaselineComparison::Compared { .. })),
+            "expected a comparison, got {comparison:?}"
+        );
+        let Ok(BaselineComparison::Compared { diff }) = comparison else {
+            return;
+        };
+        assert!((diff.score_delta + 10.0).abs() < 1e-9);
+        assert_eq!(diff.baseline_grade, "A");
+        assert_eq!(diff.grade, "B");
+        assert_eq!(diff.requests_delta, 15);
+        assert_eq!(diff.dom_elements_delta, 0);
+        assert_eq!(diff.performance_delta, None);
+        assert!(diff.is_regression());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_missing_baseline_is_not_an_error() {
+        let path = temp_file("missing.json");
+        let comparison = compare_to_baseline(&result(70.0, 'B', 55), &path);
+
+        assert!(matches!(
+            comparison,
+            Ok(BaselineComparison::NoBaseline { path: p }) if p.ends_with("missing.json")
+        ));
+    }
+
+    #[test]
+    fn test_invalid_baseline_is_rejected() {
+        let path = temp_file("invalid.json");
+        let _ = std::fs::write(&path, r#"{"score": "high"}"#);
+
+        let comparison = compare_to_baseline(&result(70.0, 'B', 55), &path);
+        assert!(matches!(comparison, Err(AppError::Config(_))));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,55 @@
+//! Compact binary export of analysis results (`binary-history` feature).
+//!
+//! Results are encoded as `MessagePack` maps, smaller than compact JSON. Field
+//! names are kept, so records written by older versions (missing fields)
+//! still decode.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::errors::{AppError, Result};
+
+/// Serialize `value` to `MessagePack`.
+pub fn to_binary<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    rmp_serde::to_vec_named(value).map_err(|e| AppError::BinarySerialization(e.to_string()))
+}
+
+/// Deserialize a value written by [`to_binary`].
+pub fn from_binary<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    rmp_serde::from_slice(bytes).map_err(|e| AppError::BinarySerialization(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{EcoIndexResult, PageMetrics};
+
+    #[test]
+    fn test_ecoindex_result_round_trip() {
+        let result = EcoIndexResult::new(
+            72.5,
+            'B',
+            2.55,
+            3.83,
+            1.28,
+            PageMetrics::new(600, 45, 900.0),
+            "https://example.com".to_string(),
+        );
+        let bytes = to_binary(&result).unwrap_or_default();
+        let json = serde_json::to_vec(&result).unwrap_or_default();
+        assert!(!bytes.is_empty());
+        assert!(bytes.len() < json.len());
+
+        let decoded: Option<EcoIndexResult> = from_binary(&bytes).ok();
+        assert_eq!(
+            decoded.and_then(|r| serde_json::to_value(r).ok()),
+            serde_json::to_value(&result).ok()
+        );
+    }
+
+    #[test]
+    fn test_invalid_bytes_are_rejected() {
+        let decoded = from_binary::<EcoIndexResult>(&[0xc1, 0x00]);
+        assert!(matches!(decoded, Err(AppError::BinarySerialization(_))));
+    }
+}
@@ -0,0 +1,59 @@
+//! JSON export of analysis results.
+
+use serde::Serialize;
+
+use crate::errors::Result;
+
+/// Serialize `value` to JSON, indented when `pretty` is set.
+///
+/// Pretty output is meant for humans and version control diffs; compact
+/// output matches what the frontend stores.
+pub fn to_json<T: Serialize>(value: &T, pretty: bool) -> Result<String> {
+    let json = if pretty {
+        serde_json::to_string_pretty(value)?
+    } else {
+        serde_json::to_string(value)?
+    };
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{EcoIndexResult, PageMetrics};
+    use crate::export::MonitoredResult;
+
+    fn result() -> MonitoredResult {
+        MonitoredResult::EcoIndex(Box::new(EcoIndexResult::new(
+            72.5,
+            'B',
+            2.55,
+            3.83,
+            1.28,
+            PageMetrics::new(600, 45, 900.0),
+            "https://example.com".to_string(),
+        )))
+    }
+
+    #[test]
+    fn test_compact_and_pretty_parse_to_same_value() {
+        let result = result();
+        let compact = to_json(&result, false).unwrap_or_default();
+        let pretty = to_json(&result, true).unwrap_or_default();
+
+        assert!(!compact.contains('\n'));
+        assert!(pretty.contains("\n  \"score\": 72.5"));
+
+        let compact_value: serde_json::Value = serde_json::from_str(&compact).unwrap_or_default();
+        let pretty_value: serde_json::Value = serde_json::from_str(&pretty).unwrap_or_default();
+        assert!(compact_value.is_object());
+        assert_eq!(compact_value, pretty_value);
+    }
+
+    #[test]
+    fn test_round_trips_through_monitored_result() {
+        let json = to_json(&result(), true).unwrap_or_default();
+        let parsed: Option<MonitoredResult> = serde_json::from_str(&json).ok();
+        assert!(matches!(parsed, Some(MonitoredResult::EcoIndex(r)) if r.grade == 'B'));
+    }
+}
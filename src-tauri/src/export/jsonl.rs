@@ -0,0 +1,184 @@
+//! JSON-lines export for time-series monitoring.
+//!
+//! Each analysis is appended as one compact JSON object per line, so a URL can
+//! be tracked over time and plotted with external tools.
+
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::EcoIndexResult;
+use crate::errors::Result;
+use crate::sidecar::LighthouseResult;
+
+/// One line of a monitoring file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitoringRecord {
+    /// Timestamp of the analysis (ISO 8601).
+    pub timestamp: String,
+    /// Analyzed URL.
+    pub url: String,
+    /// `EcoIndex` score (0-100).
+    pub score: f64,
+    /// Grade (A-G).
+    pub grade: String,
+    /// Page size in KB.
+    pub size_kb: f64,
+    /// Number of HTTP requests.
+    pub requests: u32,
+    /// Number of DOM elements.
+    pub dom_elements: u32,
+}
+
+/// Result of either analysis mode, as sent back by the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MonitoredResult {
+    /// Full Lighthouse analysis.
+    Lighthouse(Box<LighthouseResult>),
+    /// Fast `EcoIndex` analysis.
+    EcoIndex(Box<EcoIndexResult>),
+}
+
+impl From<&EcoIndexResult> for MonitoringRecord {
+    fn from(result: &EcoIndexResult) -> Self {
+        Self {
+            timestamp: result.timestamp.clone(),
+            url: result.url.clone(),
+            score: result.score,
+            grade: result.grade.to_string(),
+            size_kb: result.metrics.size_kb,
+            requests: result.metrics.requests,
+            dom_elements: result.metrics.dom_elements,
+        }
+    }
+}
+
+impl From<&LighthouseResult> for MonitoringRecord {
+    fn from(result: &LighthouseResult) -> Self {
+        Self {
+            timestamp: result.timestamp.clone(),
+            url: result.url.clone(),
+            score: result.ecoindex.score,
+            grade: result.ecoindex.grade.clone(),
+            size_kb: result.ecoindex.size_kb,
+            requests: result.ecoindex.requests,
+            dom_elements: result.ecoindex.dom_elements,
+        }
+    }
+}
+
+impl From<&MonitoredResult> for MonitoringRecord {
+    fn from(result: &MonitoredResult) -> Self {
+        match result {
+            MonitoredResult::Lighthouse(result) => Self::from(result.as_ref()),
+            MonitoredResult::EcoIndex(result) => Self::from(result.as_ref()),
+        }
+    }
+}
+
+/// Append `value` as a single JSON line to `path`, creating the file if needed.
+///
+/// The line is written with a single `write_all` on a file opened in append
+/// mode. If a previous write left the file without a trailing newline, one is
+/// inserted first so records never end up on the same line.
+pub fn append_jsonl<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(path)?;
+
+    if file.metadata()?.len() > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            line.insert(0, b'\n');
+        }
+    }
+
+    file.write_all(&line)?;
+    file.sync_data()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::PageMetrics;
+
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir()
+            .join(format!("ecoindex-jsonl-{}", std::process::id()))
+            .join(name)
+    }
+
+    fn record(score: f64) -> MonitoringRecord {
+        let result = EcoIndexResult::new(
+            score,
+            'B',
+            1.5,
+            2.25,
+            1.25,
+            PageMetrics::new(500, 50, 1000.0),
+            "https://example.com".to_string(),
+        );
+        MonitoringRecord::from(&result)
+    }
+
+    fn read_records(path: &Path) -> Vec<MonitoringRecord> {
+        std::fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    #[test]
+    fn test_append_several_records() {
+        let path = temp_file("several.jsonl");
+        let _ = std::fs::remove_file(&path);
+
+        for score in [70.0, 72.5, 68.0] {
+            assert!(append_jsonl(&path, &record(score)).is_ok());
+        }
+
+        let content = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(content.ends_with('\n'));
+        assert_eq!(content.lines().count(), 3);
+
+        let records = read_records(&path);
+        let scores: Vec<f64> = records.iter().map(|r| r.score).collect();
+        assert_eq!(scores, vec![70.0, 72.5, 68.0]);
+        assert_eq!(records[0].requests, 50);
+        assert_eq!(records[0].grade, "B");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_append_repairs_missing_newline() {
+        let path = temp_file("truncated.jsonl");
+        let _ = std::fs::create_dir_all(path.parent().unwrap_or_else(|| Path::new(".")));
+        let _ = std::fs::write(&path, r#"{"partial":true}"#);
+
+        assert!(append_jsonl(&path, &record(50.0)).is_ok());
+
+        let content = std::fs::read_to_string(&path).unwrap_or_default();
+        assert_eq!(content.lines().count(), 2);
+        assert_eq!(read_records(&path).len(), 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,13 @@
+//! Export of analysis results to files.
+
+mod baseline;
+#[cfg(feature = "binary-history")]
+mod binary;
+mod json;
+mod jsonl;
+
+pub use baseline::{compare_to_baseline, BaselineComparison, EcoIndexDiff};
+#[cfg(feature = "binary-history")]
+pub use binary::{from_binary, to_binary};
+pub use json::to_json;
+pub use jsonl::{append_jsonl, MonitoredResult, MonitoringRecord};
@@ -0,0 +1,776 @@
+//! Library entry points that do not depend on a Tauri `AppHandle`.
+//!
+//! The Tauri commands are thin wrappers around these functions, so the crate can
+//! also be embedded (CLI, tests) with an explicit Chrome path.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use chromiumoxide::browser::Browser;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::analytics::{AnalyticsOptions, ImageSizingAnalytics, RequestAnalytics};
+use crate::browser::{BrowserLauncher, CollectedMetrics, MetricsCollector};
+use crate::calculator::budget::{self, Budget, BudgetCheck, BudgetReport};
+use crate::calculator::multi_run::{aggregate_page_metrics, median_u32};
+use crate::calculator::EcoIndexCalculator;
+use crate::domain::{
+    AnalysisOptions, Co2Model, EcoIndexResult, LazyLoad, LoadCompleteness, NonHtmlDocument,
+    PageMetrics, RawPageMetrics, RequestCountConfidence, RunStatistics, ScoreSummary, Viewport,
+    ViewportSnapshot,
+};
+use crate::errors::BrowserError;
+use crate::sidecar::RequestDetail;
+
+/// Analyzes `url` with the Chrome binary at `chrome_path`.
+///
+/// See [`analyze_url_with_cancel`] for the meaning of `options`.
+pub async fn analyze_url(
+    chrome_path: &Path,
+    url: &str,
+    options: &AnalysisOptions,
+) -> Result<EcoIndexResult, BrowserError> {
+    analyze_url_with_cancel(chrome_path, url, options, &CancellationToken::new()).await
+}
+
+/// Analyzes `url`, stopping early when `cancel_token` is cancelled.
+///
+/// With `options.runs > 1` the page is analyzed several times (fresh browser each
+/// time) and the score is computed from the per-metric medians.
+pub async fn analyze_url_with_cancel(
+    chrome_path: &Path,
+    url: &str,
+    options: &AnalysisOptions,
+    cancel_token: &CancellationToken,
+) -> Result<EcoIndexResult, BrowserError> {
+    let samples = collect_runs(chrome_path, url, options, cancel_token).await?;
+    build_result(url, &samples, options.co2_model)
+}
+
+/// Analyzes `url` like [`analyze_url_with_cancel`] but only returns the score and grade.
+///
+/// Diagnostics, snapshots and environmental figures are not computed, which keeps
+/// bulk scans light. The score is the same as the full result's.
+pub async fn analyze_url_score_only(
+    chrome_path: &Path,
+    url: &str,
+    options: &AnalysisOptions,
+    cancel_token: &CancellationToken,
+) -> Result<ScoreSummary, BrowserError> {
+    let samples = collect_runs(chrome_path, url, options, cancel_token).await?;
+    score_summary(&samples)
+}
+
+/// Analyzes `url` once per viewport and returns the results keyed by viewport name.
+///
+/// Viewports are emulated one after the other in a single browser (each with
+/// `options.runs` runs), so only the emulated screen differs between results.
+///
+/// # Errors
+///
+/// Fails with [`BrowserError::InvalidViewport`] when a viewport is invalid or
+/// two share a name, or with the first collection error.
+pub async fn analyze_viewports(
+    chrome_path: &Path,
+    url: &str,
+    viewports: &[Viewport],
+    options: &AnalysisOptions,
+    cancel_token: &CancellationToken,
+) -> Result<BTreeMap<String, EcoIndexResult>, BrowserError> {
+    check_viewports(viewports)?;
+
+    let (browser, mut handler) = launcher(chrome_path, options)?.launch().await?;
+
+    let mut collector = SharedBrowser {
+        browser: &browser,
+        handler: &mut handler,
+        cancel_token,
+    };
+    let results = analyze_each_viewport(&mut collector, url, viewports, options).await;
+
+    drop(browser);
+    handler.abort();
+
+    results
+}
+
+/// Analyzes `url` like [`analyze_url_with_cancel`] and evaluates the result against `budget`.
+///
+/// As soon as one run exceeds a hard ceiling of the budget (page size, request
+/// count), the remaining runs are skipped and the violation is reported at once:
+/// the page fails the budget whatever the other runs would measure.
+pub async fn analyze_url_with_budget(
+    chrome_path: &Path,
+    url: &str,
+    options: &AnalysisOptions,
+    budget: &Budget,
+    cancel_token: &CancellationToken,
+) -> Result<BudgetCheck, BrowserError> {
+    let mut collector = FreshBrowser {
+        chrome_path,
+        cancel_token,
+    };
+    collect_within_budget(&mut collector, url, options, budget).await
+}
+
+/// Collects the metrics of `url` like [`analyze_url_with_cancel`] without scoring them.
+///
+/// The returned medians are exactly the inputs the score is computed from.
+pub async fn collect_raw_metrics(
+    chrome_path: &Path,
+    url: &str,
+    options: &AnalysisOptions,
+    cancel_token: &CancellationToken,
+) -> Result<RawPageMetrics, BrowserError> {
+    let samples = collect_runs(chrome_path, url, options, cancel_token).await?;
+    raw_metrics(&samples)
+}
+
+/// Computes the request analytics of a Lighthouse result.
+#[must_use]
+pub fn compute_analytics(
+    requests: &[RequestDetail],
+    options: AnalyticsOptions,
+) -> RequestAnalytics {
+    RequestAnalytics::compute_with_options(requests, options)
+}
+
+/// Collects the samples of every run (fresh browser each time).
+async fn collect_runs(
+    chrome_path: &Path,
+    url: &str,
+    options: &AnalysisOptions,
+    cancel_token: &CancellationToken,
+) -> Result<Vec<CollectedMetrics>, BrowserError> {
+    let mut samples = Vec::new();
+    for _ in 0..options.run_count() {
+        samples.push(collect_once(chrome_path, url, options, cancel_token).await?);
+    }
+    Ok(samples)
+}
+
+/// Source of measurement runs.
+trait RunCollector {
+    /// Collects one run of `url` with `options`.
+    async fn collect(
+        &mut self,
+        url: &str,
+        options: &AnalysisOptions,
+    ) -> Result<CollectedMetrics, BrowserError>;
+}
+
+/// Runs collected in an already launched browser.
+struct SharedBrowser<'a> {
+    browser: &'a Browser,
+    handler: &'a mut JoinHandle<BrowserError>,
+    cancel_token: &'a CancellationToken,
+}
+
+impl RunCollector for SharedBrowser<'_> {
+    async fn collect(
+        &mut self,
+        url: &str,
+        options: &AnalysisOptions,
+    ) -> Result<CollectedMetrics, BrowserError> {
+        let collector = MetricsCollector::new(self.browser)
+            .with_options(options.clone())
+            .with_cancel_token(self.cancel_token.clone());
+        // A crashed browser fails the run at once instead of stalling on page events
+        tokio::select! {
+            metrics = collector.collect_detailed(url) => metrics,
+            crashed = &mut *self.handler => {
+                Err(crashed.unwrap_or_else(|e| BrowserError::BrowserCrashed(e.to_string())))
+            },
+        }
+    }
+}
+
+/// Runs collected in a fresh browser each time.
+struct FreshBrowser<'a> {
+    chrome_path: &'a Path,
+    cancel_token: &'a CancellationToken,
+}
+
+impl RunCollector for FreshBrowser<'_> {
+    async fn collect(
+        &mut self,
+        url: &str,
+        options: &AnalysisOptions,
+    ) -> Result<CollectedMetrics, BrowserError> {
+        collect_once(self.chrome_path, url, options, self.cancel_token).await
+    }
+}
+
+/// Collects the runs of `url`, stopping at the first run that busts a hard ceiling.
+async fn collect_within_budget(
+    collector: &mut impl RunCollector,
+    url: &str,
+    options: &AnalysisOptions,
+    budget: &Budget,
+) -> Result<BudgetCheck, BrowserError> {
+    let run_count = options.run_count();
+    let mut samples = Vec::new();
+    for run in 1..=run_count {
+        let sample = collector.collect(url, options).await?;
+        let violations = budget::hard_limit_violations(&sample.metrics, budget);
+        samples.push(sample);
+        if !violations.is_empty() {
+            if run < run_count {
+                log::info!("{url} exceeds its budget on run {run}, skipping the remaining runs");
+            }
+            // Report the busting run itself: the median could hide the violation
+            return Ok(BudgetCheck {
+                result: build_result(url, &samples, options.co2_model)?,
+                report: BudgetReport::from_violations(violations),
+                completed_runs: run,
+                stopped_early: run < run_count,
+            });
+        }
+    }
+    let result = build_result(url, &samples, options.co2_model)?;
+    Ok(BudgetCheck {
+        report: budget::evaluate(&result, budget),
+        result,
+        completed_runs: run_count,
+        stopped_early: false,
+    })
+}
+
+/// Rejects invalid viewports and duplicate names (results are keyed by name).
+fn check_viewports(viewports: &[Viewport]) -> Result<(), BrowserError> {
+    let mut names = HashSet::new();
+    for viewport in viewports {
+        viewport.validate().map_err(BrowserError::InvalidViewport)?;
+        if !names.insert(viewport.name.as_str()) {
+            return Err(BrowserError::InvalidViewport(format!(
+                "duplicate viewport name {}",
+                viewport.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Analyzes `url` with each viewport in turn, keying results by viewport name.
+async fn analyze_each_viewport(
+    collector: &mut impl RunCollector,
+    url: &str,
+    viewports: &[Viewport],
+    options: &AnalysisOptions,
+) -> Result<BTreeMap<String, EcoIndexResult>, BrowserError> {
+    let mut results = BTreeMap::new();
+    for viewport in viewports {
+        let options = AnalysisOptions {
+            viewport: Some(viewport.clone()),
+            ..options.clone()
+        };
+        let mut samples = Vec::new();
+        for _ in 0..options.run_count() {
+            samples.push(collector.collect(url, &options).await?);
+        }
+        results.insert(
+            viewport.name.clone(),
+            build_result(url, &samples, options.co2_model)?,
+        );
+    }
+    Ok(results)
+}
+
+/// Browser launcher configured from the analysis options.
+fn launcher(
+    chrome_path: &Path,
+    options: &AnalysisOptions,
+) -> Result<BrowserLauncher, BrowserError> {
+    let proxy = options.proxy_config().map_err(BrowserError::InvalidProxy)?;
+    let headless = options
+        .headless_mode()
+        .map_err(BrowserError::InvalidHeadlessMode)?;
+    Ok(BrowserLauncher::new(chrome_path.to_path_buf())
+        .with_headless_mode(headless)
+        .with_proxy(proxy.map(|p| p.server))
+        .with_limits(options.chrome_limits))
+}
+
+/// Launches a fresh browser and collects metrics for a single run.
+async fn collect_once(
+    chrome_path: &Path,
+    url: &str,
+    options: &AnalysisOptions,
+    cancel_token: &CancellationToken,
+) -> Result<CollectedMetrics, BrowserError> {
+    let (browser, mut handler) = launcher(chrome_path, options)?.launch().await?;
+
+    let metrics = SharedBrowser {
+        browser: &browser,
+        handler: &mut handler,
+        cancel_token,
+    }
+    .collect(url, options)
+    .await;
+
+    drop(browser);
+    handler.abort();
+
+    metrics
+}
+
+/// Per-metric medians of every run, with their spread.
+fn median_metrics(
+    samples: &[CollectedMetrics],
+) -> Result<(PageMetrics, RunStatistics), BrowserError> {
+    let page_metrics: Vec<PageMetrics> = samples.iter().map(|s| s.metrics).collect();
+    aggregate_page_metrics(&page_metrics)
+        .ok_or_else(|| BrowserError::PageLoadFailed("No metrics collected".to_string()))
+}
+
+/// Median Resource Timing count, or `None` unless every run has one.
+fn median_resource_timing(samples: &[CollectedMetrics]) -> Option<u32> {
+    let resource_timing: Option<Vec<u32>> =
+        samples.iter().map(|s| s.resource_timing_requests).collect();
+    resource_timing.as_deref().and_then(median_u32)
+}
+
+/// Median count of failed requests (0 without samples).
+fn median_failed_requests(samples: &[CollectedMetrics]) -> u32 {
+    let failed: Vec<u32> = samples.iter().map(|s| s.failed_requests).collect();
+    median_u32(&failed).unwrap_or_default()
+}
+
+/// Gathers the metrics of every run and their per-metric medians.
+fn raw_metrics(samples: &[CollectedMetrics]) -> Result<RawPageMetrics, BrowserError> {
+    let (metrics, _) = median_metrics(samples)?;
+    Ok(RawPageMetrics {
+        metrics,
+        runs: samples.iter().map(|s| s.metrics).collect(),
+        resource_timing_requests: median_resource_timing(samples),
+        failed_requests: median_failed_requests(samples),
+        final_url: samples.last().and_then(|s| s.final_url.clone()),
+    })
+}
+
+/// Computes the score and grade from the per-metric medians of every run.
+fn score_summary(samples: &[CollectedMetrics]) -> Result<ScoreSummary, BrowserError> {
+    let (metrics, _) = median_metrics(samples)?;
+    let score = EcoIndexCalculator::compute_score(&metrics);
+    Ok(ScoreSummary {
+        score,
+        grade: EcoIndexCalculator::get_grade(score),
+    })
+}
+
+/// Computes the `EcoIndex` result from the samples of every run.
+fn build_result(
+    url: &str,
+    samples: &[CollectedMetrics],
+    co2_model: Co2Model,
+) -> Result<EcoIndexResult, BrowserError> {
+    let (metrics, run_stats) = median_metrics(samples)?;
+
+    let mut result = EcoIndexCalculator::compute_with_model(&metrics, url, co2_model);
+    if run_stats.runs > 1 {
+        result.runs = Some(run_stats);
+    }
+
+    // Above-the-fold snapshot (median across runs)
+    let viewport: Option<Vec<PageMetrics>> = samples.iter().map(|s| s.viewport).collect();
+    if let Some((viewport, _)) = viewport.as_deref().and_then(aggregate_page_metrics) {
+        let score = EcoIndexCalculator::compute_score(&viewport);
+        let lazy_load = LazyLoad::between(&viewport, &metrics);
+        log::info!(
+            "Lazy-loaded on scroll: {} requests ({:.0}%), {:.1} KB ({:.0}%)",
+            lazy_load.requests,
+            lazy_load.request_percentage,
+            lazy_load.size_kb,
+            lazy_load.size_percentage
+        );
+        result.viewport = Some(ViewportSnapshot {
+            metrics: viewport,
+            score,
+            grade: EcoIndexCalculator::get_grade(score),
+            lazy_load: Some(lazy_load),
+        });
+    }
+
+    // Client-side navigation cost from the last run
+    result.route_transition = samples.last().and_then(|s| s.route_transition);
+    if let Some(transition) = &result.route_transition {
+        log::info!(
+            "Route change on {url}: {:+} requests, {:+.1} KB, {:+} DOM elements",
+            transition.requests_delta,
+            transition.size_kb_delta,
+            transition.dom_elements_delta
+        );
+    }
+
+    // Image sizing from the last run (dimensions do not vary between runs)
+    result.image_sizing = samples
+        .last()
+        .filter(|s| !s.images.is_empty())
+        .map(|s| ImageSizingAnalytics::compute(&s.images));
+
+    // URL actually measured after redirects
+    result.final_url = samples.last().and_then(|s| s.final_url.clone());
+    if result.was_redirected() {
+        log::info!(
+            "{url} redirected to {}",
+            result.final_url.as_deref().unwrap_or_default()
+        );
+    }
+
+    // A PDF, image or JSON "page" has no meaningful DOM (last run's document)
+    result.non_html_document = samples
+        .last()
+        .and_then(|s| s.content_type.as_deref())
+        .and_then(NonHtmlDocument::check);
+    if let Some(check) = &result.non_html_document {
+        log::warn!(
+            "{url} is not an HTML page ({}), the grade is not meaningful",
+            check.content_type
+        );
+    }
+
+    // Inline script/style bytes from the last run (part of the HTML size)
+    result.inline_resources = samples.last().and_then(|s| s.inline);
+
+    // Repeated inline contents from the last run (only reported when found)
+    result.inline_duplicates = samples
+        .last()
+        .and_then(|s| s.inline_duplicates.clone())
+        .filter(|d| d.repeat_count > 0);
+
+    // Cross-check the request count when every run has Resource Timing data
+    if let Some(resource_timing) = median_resource_timing(samples) {
+        result.request_count_confidence =
+            RequestCountConfidence::check(metrics.requests, resource_timing);
+        if let Some(check) = &result.request_count_confidence {
+            log::warn!(
+                "Request count may be inaccurate: {} network requests vs {} resource timing entries",
+                check.network_count,
+                check.resource_timing_count
+            );
+        }
+    }
+
+    result.failed_requests = median_failed_requests(samples);
+
+    // Flag runs collected while the network was still active (least idle run)
+    // or before the readiness condition was met (any run)
+    let idle_ms = samples.iter().map(|s| s.network_idle_ms).min();
+    let ready = samples.iter().all(|s| s.ready);
+    result.load_completeness = idle_ms.and_then(|idle_ms| LoadCompleteness::check(idle_ms, ready));
+    if let Some(check) = &result.load_completeness {
+        log::warn!(
+            "Page still loading at collection time (readiness met: {}, {} ms idle), metrics may be incomplete",
+            check.load_complete,
+            check.idle_ms
+        );
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(dom_elements: u32, requests: u32, size_kb: f64) -> CollectedMetrics {
+        CollectedMetrics {
+            metrics: PageMetrics::new(dom_elements, requests, size_kb),
+            viewport: None,
+            resource_timing_requests: None,
+            images: Vec::new(),
+            inline: None,
+            inline_duplicates: None,
+            final_url: None,
+            network_idle_ms: 1000,
+            ready: true,
+            failed_requests: 0,
+            content_type: Some("text/html; charset=utf-8".to_string()),
+            route_transition: None,
+        }
+    }
+
+    #[test]
+    fn test_score_only_matches_full_result() {
+        let samples = vec![
+            sample(400, 40, 900.0),
+            sample(420, 45, 1100.0),
+            sample(410, 42, 1000.0),
+        ];
+        let summary = score_summary(&samples);
+        let full = build_result("https://example.com", &samples, Co2Model::default());
+
+        assert!(summary.is_ok() && full.is_ok());
+        let (Ok(summary), Ok(full)) = (summary, full) else {
+            return;
+        };
+        assert!((summary.score - full.score).abs() < f64::EPSILON);
+        assert_eq!(summary.grade, full.grade);
+    }
+
+    #[test]
+    fn test_viewport_snapshot_reports_lazy_load() {
+        let mut run = sample(400, 40, 1000.0);
+        run.viewport = Some(PageMetrics::new(300, 30, 250.0));
+        let result = build_result("https://example.com", &[run], Co2Model::default());
+
+        let lazy_load = result
+            .ok()
+            .and_then(|r| r.viewport)
+            .and_then(|v| v.lazy_load);
+        assert!(lazy_load.is_some_and(|lazy| lazy.requests == 10
+            && (lazy.size_kb - 750.0).abs() < 1e-9
+            && (lazy.request_percentage - 25.0).abs() < 1e-9
+            && (lazy.size_percentage - 75.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_raw_metrics_match_scored_inputs() {
+        let mut samples = vec![
+            sample(400, 40, 900.0),
+            sample(420, 45, 1100.0),
+            sample(410, 42, 1000.0),
+        ];
+        samples[2].final_url = Some("https://example.com/home".to_string());
+        let raw = raw_metrics(&samples);
+        let full = build_result("https://example.com", &samples, Co2Model::default());
+
+        assert!(raw.is_ok() && full.is_ok());
+        let (Ok(raw), Ok(full)) = (raw, full) else {
+            return;
+        };
+        assert_eq!(raw.metrics, full.metrics);
+        assert_eq!(raw.runs.len(), 3);
+        assert_eq!(raw.runs[1], PageMetrics::new(420, 45, 1100.0));
+        assert_eq!(raw.final_url, full.final_url);
+        assert!(
+            (EcoIndexCalculator::compute_score(&raw.metrics) - full.score).abs() < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_non_html_document_is_flagged() {
+        let html = build_result(
+            "https://example.com",
+            &[sample(400, 40, 900.0)],
+            Co2Model::default(),
+        );
+        assert!(html.is_ok_and(|r| r.non_html_document.is_none()));
+
+        let mut pdf = sample(12, 3, 250.0);
+        pdf.content_type = Some("application/pdf".to_string());
+        let result = build_result("https://example.com/doc.pdf", &[pdf], Co2Model::default());
+        assert!(result.is_ok_and(|r| r
+            .non_html_document
+            .is_some_and(|d| d.content_type == "application/pdf")));
+    }
+
+    #[test]
+    fn test_readiness_timeout_is_flagged() {
+        let idle = build_result(
+            "https://example.com",
+            &[sample(400, 40, 900.0)],
+            Co2Model::default(),
+        );
+        assert!(idle.is_ok_and(|r| r.load_completeness.is_none()));
+
+        // Second run gave up waiting although the network went quiet since
+        let mut timed_out = sample(410, 42, 1000.0);
+        timed_out.ready = false;
+        let result = build_result(
+            "https://example.com",
+            &[sample(400, 40, 900.0), timed_out],
+            Co2Model::default(),
+        );
+        assert!(result.is_ok_and(|r| r
+            .load_completeness
+            .is_some_and(|c| !c.load_complete && c.idle_ms == 1000)));
+    }
+
+    /// Page whose weight depends on the emulated width (responsive images).
+    struct ResponsiveCollector {
+        calls: Vec<String>,
+    }
+
+    impl RunCollector for ResponsiveCollector {
+        async fn collect(
+            &mut self,
+            _url: &str,
+            options: &AnalysisOptions,
+        ) -> Result<CollectedMetrics, BrowserError> {
+            let width = options.viewport.as_ref().map_or(1920, |v| v.width);
+            self.calls.push(
+                options
+                    .viewport
+                    .as_ref()
+                    .map(|v| v.name.clone())
+                    .unwrap_or_default(),
+            );
+            Ok(sample(400, 40, f64::from(width)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_viewports_produce_independent_keyed_results() {
+        let mut collector = ResponsiveCollector { calls: Vec::new() };
+        let options = AnalysisOptions {
+            runs: 2,
+            ..AnalysisOptions::default()
+        };
+        let results = analyze_each_viewport(
+            &mut collector,
+            "https://example.com",
+            &Viewport::presets(),
+            &options,
+        )
+        .await
+        .unwrap_or_default();
+
+        let keys: Vec<&str> = results.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["desktop", "mobile", "tablet"]);
+        assert_eq!(
+            collector.calls,
+            vec!["mobile", "mobile", "tablet", "tablet", "desktop", "desktop"]
+        );
+        let size = |name: &str| results.get(name).map_or(0.0, |r| r.metrics.size_kb);
+        assert!((size("mobile") - 360.0).abs() < f64::EPSILON);
+        assert!((size("tablet") - 768.0).abs() < f64::EPSILON);
+        assert!((size("desktop") - 1920.0).abs() < f64::EPSILON);
+        assert!(results.get("mobile").is_some_and(|r| r.runs.is_some()));
+    }
+
+    /// Replays a fixed sequence of runs, counting how many were collected.
+    struct ScriptedCollector {
+        runs: Vec<CollectedMetrics>,
+        collected: usize,
+    }
+
+    impl RunCollector for ScriptedCollector {
+        async fn collect(
+            &mut self,
+            _url: &str,
+            _options: &AnalysisOptions,
+        ) -> Result<CollectedMetrics, BrowserError> {
+            let run = self.runs.get(self.collected).cloned();
+            self.collected += 1;
+            run.ok_or_else(|| BrowserError::PageLoadFailed("no more runs".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_budget_stops_at_first_busting_run() {
+        let mut collector = ScriptedCollector {
+            runs: vec![sample(400, 200, 900.0), sample(400, 40, 900.0)],
+            collected: 0,
+        };
+        let options = AnalysisOptions {
+            runs: 3,
+            ..AnalysisOptions::default()
+        };
+        let budget = Budget {
+            max_requests: Some(100),
+            ..Budget::default()
+        };
+        let check =
+            collect_within_budget(&mut collector, "https://example.com", &options, &budget).await;
+
+        assert_eq!(collector.collected, 1);
+        assert!(check.is_ok());
+        let Ok(check) = check else { return };
+        assert!(check.stopped_early);
+        assert_eq!(check.completed_runs, 1);
+        assert!(!check.report.passed);
+        assert_eq!(
+            check.report.violations,
+            vec![budget::BudgetViolation::Requests {
+                limit: 100,
+                actual: 200
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_budget_fails_when_only_last_run_busts() {
+        let mut collector = ScriptedCollector {
+            runs: vec![
+                sample(400, 40, 900.0),
+                sample(400, 40, 900.0),
+                sample(400, 200, 900.0),
+            ],
+            collected: 0,
+        };
+        let options = AnalysisOptions {
+            runs: 3,
+            ..AnalysisOptions::default()
+        };
+        let budget = Budget {
+            max_requests: Some(100),
+            ..Budget::default()
+        };
+        let check =
+            collect_within_budget(&mut collector, "https://example.com", &options, &budget).await;
+
+        assert_eq!(collector.collected, 3);
+        assert!(check.is_ok());
+        let Ok(check) = check else { return };
+        // The median (40 requests) is within budget, the last run is not
+        assert!(!check.stopped_early);
+        assert_eq!(check.completed_runs, 3);
+        assert!(!check.report.passed);
+        assert_eq!(
+            check.report.violations,
+            vec![budget::BudgetViolation::Requests {
+                limit: 100,
+                actual: 200
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_budget_runs_all_when_within_limits() {
+        let mut collector = ScriptedCollector {
+            runs: vec![sample(400, 40, 900.0); 3],
+            collected: 0,
+        };
+        let options = AnalysisOptions {
+            runs: 3,
+            ..AnalysisOptions::default()
+        };
+        let budget = Budget {
+            max_size_kb: Some(1000.0),
+            max_requests: Some(100),
+            min_grade: Some('G'),
+        };
+        let check =
+            collect_within_budget(&mut collector, "https://example.com", &options, &budget).await;
+
+        assert_eq!(collector.collected, 3);
+        assert!(check.is_ok_and(|c| c.report.passed && !c.stopped_early && c.completed_runs == 3));
+    }
+
+    #[test]
+    fn test_check_viewports() {
+        assert!(check_viewports(&Viewport::presets()).is_ok());
+        assert!(matches!(
+            check_viewports(&[Viewport::mobile(), Viewport::mobile()]),
+            Err(BrowserError::InvalidViewport(msg)) if msg.contains("duplicate")
+        ));
+        let invalid = Viewport {
+            height: 0,
+            ..Viewport::desktop()
+        };
+        assert!(matches!(
+            check_viewports(&[invalid]),
+            Err(BrowserError::InvalidViewport(_))
+        ));
+    }
+
+    #[test]
+    fn test_score_only_without_samples() {
+        assert!(matches!(
+            score_summary(&[]),
+            Err(BrowserError::PageLoadFailed(_))
+        ));
+    }
+}
@@ -10,7 +10,10 @@ pub mod calculator;
 pub mod commands;
 pub mod domain;
 pub mod errors;
+pub mod export;
+pub mod facade;
 pub mod sidecar;
+pub mod storage;
 pub mod utils;
 
 use tauri::Manager;
@@ -40,7 +43,7 @@ pub fn run() {
 
 /// Kill a process by PID.
 /// Sends SIGTERM on Unix, uses taskkill on Windows.
-fn kill_process(pid: u32) {
+pub(crate) fn kill_process(pid: u32) {
     #[cfg(unix)]
     {
         // Send SIGTERM to Node.js process using kill command
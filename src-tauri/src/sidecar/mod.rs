@@ -5,4 +5,9 @@
 
 mod lighthouse;
 
-pub use lighthouse::{run_lighthouse_analysis, AnalysisState, LighthouseResult, RequestDetail};
+pub use lighthouse::{
+    aggregate_runs, resolve_lighthouse_script_path, run_lighthouse_analysis, AnalysisGuard,
+    AnalysisState, CacheItem, ImageFormatAnalytics, ImageFormatItem, LighthouseResult,
+    OmittedRequests, Opportunity, OpportunityKind, PotentialSavings, RequestDetail,
+    DEFAULT_MAX_REQUEST_DETAILS, MAX_SIDECAR_PROTOCOL_VERSION, MIN_SIDECAR_PROTOCOL_VERSION,
+};
@@ -0,0 +1,208 @@
+//! Size and age limits of the cache directory.
+//!
+//! Cache files are disposable: when the directory grows past its limits, the
+//! oldest files (by modification time) are removed first.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+
+use crate::errors::Result;
+
+/// Default maximum total size of the cache directory (50 MB).
+pub const DEFAULT_MAX_CACHE_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Default maximum age of a cache file (30 days).
+pub const DEFAULT_MAX_CACHE_AGE: Duration = Duration::from_secs(30 * 24 * 3600);
+
+/// Limits enforced on the cache directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheLimits {
+    /// Maximum total size of the cache files in bytes.
+    pub max_bytes: u64,
+    /// Files not modified for longer than this are removed.
+    pub max_age: Duration,
+}
+
+impl Default for CacheLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_CACHE_BYTES,
+            max_age: DEFAULT_MAX_CACHE_AGE,
+        }
+    }
+}
+
+/// Outcome of a cache cleanup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvictionReport {
+    /// Number of files removed.
+    pub removed_files: u32,
+    /// Bytes freed.
+    pub removed_bytes: u64,
+    /// Total size of the files left in the directory.
+    pub remaining_bytes: u64,
+}
+
+/// Remove expired files, then the oldest ones until the cache fits `limits`.
+///
+/// Meant to run on startup and after each cache write. A missing directory
+/// is an empty cache; subdirectories are left alone.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read or a file cannot be removed.
+pub fn enforce_cache_limits(dir: &Path, limits: CacheLimits) -> Result<EvictionReport> {
+    evict(dir, limits, SystemTime::now())
+}
+
+/// Remove every file of the cache directory.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read or a file cannot be removed.
+pub fn clear_cache(dir: &Path) -> Result<EvictionReport> {
+    evict(
+        dir,
+        CacheLimits {
+            max_bytes: 0,
+            max_age: Duration::ZERO,
+        },
+        SystemTime::now(),
+    )
+}
+
+fn evict(dir: &Path, limits: CacheLimits, now: SystemTime) -> Result<EvictionReport> {
+    let mut files = cache_files(dir)?;
+    // Oldest first
+    files.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut report = EvictionReport {
+        remaining_bytes: files.iter().map(|(_, _, size)| size).sum(),
+        ..EvictionReport::default()
+    };
+    for (path, modified, size) in files {
+        let expired = now
+            .duration_since(modified)
+            .is_ok_and(|age| age > limits.max_age);
+        if !expired && report.remaining_bytes <= limits.max_bytes {
+            continue;
+        }
+        std::fs::remove_file(&path)?;
+        log::debug!("Evicted cache file {}", path.display());
+        report.removed_files += 1;
+        report.removed_bytes += size;
+        report.remaining_bytes -= size;
+    }
+    Ok(report)
+}
+
+/// Regular files of `dir` with their modification time and size.
+fn cache_files(dir: &Path) -> Result<Vec<(PathBuf, SystemTime, u64)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            files.push((entry.path(), modified, metadata.len()));
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("ecoindex-cache-{}", std::process::id()))
+            .join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::create_dir_all(&dir);
+        dir
+    }
+
+    /// Write a file of `size` bytes modified `age_secs` seconds before `now`.
+    fn write_file(dir: &Path, name: &str, size: usize, now: SystemTime, age_secs: u64) {
+        let path = dir.join(name);
+        let _ = std::fs::write(&path, vec![b'x'; size]);
+        if let Ok(file) = std::fs::File::options().write(true).open(&path) {
+            let _ = file.set_modified(now - Duration::from_secs(age_secs));
+        }
+    }
+
+    fn remaining(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(std::result::Result::ok)
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_size_limit_evicts_oldest_first() {
+        let dir = temp_dir("size");
+        let now = SystemTime::now();
+        write_file(&dir, "a.json", 400, now, 300);
+        write_file(&dir, "b.json", 400, now, 200);
+        write_file(&dir, "c.json", 400, now, 100);
+        write_file(&dir, "d.json", 400, now, 0);
+
+        let limits = CacheLimits {
+            max_bytes: 1000,
+            max_age: DEFAULT_MAX_CACHE_AGE,
+        };
+        let report = evict(&dir, limits, now).unwrap_or_default();
+
+        assert_eq!(report.removed_files, 2);
+        assert_eq!(report.removed_bytes, 800);
+        assert_eq!(report.remaining_bytes, 800);
+        assert!(report.remaining_bytes <= limits.max_bytes);
+        assert_eq!(remaining(&dir), vec!["c.json", "d.json"]);
+    }
+
+    #[test]
+    fn test_age_limit_evicts_expired_files() {
+        let dir = temp_dir("age");
+        let now = SystemTime::now();
+        write_file(&dir, "old.json", 10, now, 3 * 24 * 3600);
+        write_file(&dir, "new.json", 10, now, 60);
+
+        let limits = CacheLimits {
+            max_bytes: DEFAULT_MAX_CACHE_BYTES,
+            max_age: Duration::from_secs(24 * 3600),
+        };
+        let report = evict(&dir, limits, now).unwrap_or_default();
+
+        assert_eq!(report.removed_files, 1);
+        assert_eq!(remaining(&dir), vec!["new.json"]);
+    }
+
+    #[test]
+    fn test_clear_cache_and_missing_dir() {
+        let dir = temp_dir("clear");
+        let now = SystemTime::now();
+        write_file(&dir, "a.json", 10, now, 0);
+        write_file(&dir, "b.json", 20, now, 0);
+
+        let report = clear_cache(&dir).unwrap_or_default();
+        assert_eq!(report.removed_files, 2);
+        assert_eq!(report.removed_bytes, 30);
+        assert!(remaining(&dir).is_empty());
+
+        let missing = enforce_cache_limits(&dir.join("missing"), CacheLimits::default());
+        assert!(matches!(missing, Ok(report) if report == EvictionReport::default()));
+    }
+}
@@ -0,0 +1,442 @@
+//! Versioned analysis history file.
+//!
+//! Each record carries a `schemaVersion`. Older records are migrated when
+//! loading; records that cannot be read are skipped with a warning instead of
+//! failing the whole history.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::domain::EcoIndexResult;
+use crate::errors::Result;
+use crate::sidecar::LighthouseResult;
+
+/// Current schema version of history records.
+///
+/// - v1: unversioned `{ mode, data }` records (frontend format)
+/// - v2: adds `schemaVersion`
+/// - v3: adds a stable `id`
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// Analysis result stored in the history, tagged by analysis mode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "mode", content = "data", rename_all = "lowercase")]
+pub enum StoredResult {
+    /// Fast `EcoIndex` analysis.
+    Quick(Box<EcoIndexResult>),
+    /// Full Lighthouse analysis.
+    Full(Box<LighthouseResult>),
+}
+
+/// Versioned history record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRecord {
+    /// Schema version the record was written with.
+    pub schema_version: u32,
+    /// Stable identifier, derived from the mode, URL and timestamp of the result.
+    pub id: String,
+    /// Stored analysis result.
+    #[serde(flatten)]
+    pub result: StoredResult,
+}
+
+impl HistoryRecord {
+    /// Wrap a result with the current schema version.
+    #[must_use]
+    pub fn new(result: StoredResult) -> Self {
+        let id = match &result {
+            StoredResult::Quick(r) => record_id("quick", &r.url, &r.timestamp),
+            StoredResult::Full(r) => record_id("full", &r.url, &r.timestamp),
+        };
+        Self {
+            schema_version: SCHEMA_VERSION,
+            id,
+            result,
+        }
+    }
+}
+
+/// Stable identifier of a result (same input, same ID across runs and versions).
+fn record_id(mode: &str, url: &str, timestamp: &str) -> String {
+    let hash = [mode, url, timestamp]
+        .join("\n")
+        .bytes()
+        .fold(0u64, |acc, b| {
+            acc.wrapping_mul(31).wrapping_add(u64::from(b))
+        });
+    format!("{hash:016x}")
+}
+
+/// Load the history from `path`, migrating older records.
+///
+/// A missing file yields an empty history. Unreadable or newer-version records
+/// are skipped with a logged warning.
+pub fn load_history(path: &Path) -> Result<Vec<HistoryRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let Value::Array(values) = serde_json::from_str::<Value>(&content)? else {
+        log::warn!("History file {} is not a list, ignoring it", path.display());
+        return Ok(Vec::new());
+    };
+
+    Ok(values
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, value)| match migrate(value) {
+            Ok(record) => Some(record),
+            Err(reason) => {
+                log::warn!("Skipping history record #{index}: {reason}");
+                None
+            },
+        })
+        .collect())
+}
+
+/// Save the history to `path` (written to a temporary file, then renamed).
+pub fn save_history(path: &Path, records: &[HistoryRecord]) -> Result<()> {
+    write_atomically(path, &serde_json::to_vec(records)?)
+}
+
+/// Find the record with the given `id` in the history at `path`.
+pub fn get_history_entry(path: &Path, id: &str) -> Result<Option<HistoryRecord>> {
+    Ok(load_history(path)?
+        .into_iter()
+        .find(|record| record.id == id))
+}
+
+/// Remove the record with the given `id` from the history at `path`.
+///
+/// The other records are written back as they were, including those this
+/// version cannot read. Returns whether a record was removed.
+pub fn delete_history_entry(path: &Path, id: &str) -> Result<bool> {
+    if !path.exists() {
+        return Ok(false);
+    }
+    let content = std::fs::read_to_string(path)?;
+    let Value::Array(mut values) = serde_json::from_str::<Value>(&content)? else {
+        return Ok(false);
+    };
+    let Some(index) = values
+        .iter()
+        .position(|value| migrate(value.clone()).is_ok_and(|record| record.id == id))
+    else {
+        return Ok(false);
+    };
+    values.remove(index);
+    write_atomically(path, &serde_json::to_vec(&values)?)?;
+    Ok(true)
+}
+
+/// Write `content` to a temporary file next to `path`, then rename it over `path`.
+fn write_atomically(path: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, content)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Upgrade a raw record to the current schema version.
+fn migrate(mut value: Value) -> std::result::Result<HistoryRecord, String> {
+    let version = value
+        .get("schemaVersion")
+        .and_then(Value::as_u64)
+        .unwrap_or(1);
+
+    if version > u64::from(SCHEMA_VERSION) {
+        return Err(format!(
+            "schema version {version} is newer than supported version {SCHEMA_VERSION}"
+        ));
+    }
+
+    if version < 2 {
+        migrate_v1_to_v2(&mut value)?;
+    }
+    if version < 3 {
+        migrate_v2_to_v3(&mut value)?;
+    }
+
+    serde_json::from_value(value).map_err(|e| format!("incompatible record: {e}"))
+}
+
+/// v1 records are the unversioned frontend format: only the version is added.
+fn migrate_v1_to_v2(value: &mut Value) -> std::result::Result<(), String> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| "record is not an object".to_string())?;
+    object.insert("schemaVersion".to_string(), Value::from(2));
+    Ok(())
+}
+
+/// v3 adds the record ID, computed like [`HistoryRecord::new`] so it is stable.
+fn migrate_v2_to_v3(value: &mut Value) -> std::result::Result<(), String> {
+    let field = |name: &str| {
+        value
+            .pointer(name)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let id = record_id(
+        &field("/mode"),
+        &field("/data/url"),
+        &field("/data/timestamp"),
+    );
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| "record is not an object".to_string())?;
+    object.insert("id".to_string(), Value::from(id));
+    object.insert("schemaVersion".to_string(), Value::from(3));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::RequestAnalytics;
+    use crate::domain::PageMetrics;
+
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir()
+            .join(format!("ecoindex-history-{}", std::process::id()))
+            .join(name)
+    }
+
+    fn quick_result(score: f64) -> EcoIndexResult {
+        EcoIndexResult::new(
+            score,
+            'B',
+            1.5,
+            2.25,
+            1.25,
+            PageMetrics::new(500, 50, 1000.0),
+            "https://example.com".to_string(),
+        )
+    }
+
+    fn quick_record(url: &str, timestamp: &str) -> HistoryRecord {
+        let mut result = quick_result(50.0);
+        result.url = url.to_string();
+        result.timestamp = timestamp.to_string();
+        HistoryRecord::new(StoredResult::Quick(Box::new(result)))
+    }
+
+    fn urls(records: &[HistoryRecord]) -> Vec<String> {
+        records
+            .iter()
+            .map(|record| match &record.result {
+                StoredResult::Quick(r) => r.url.clone(),
+                StoredResult::Full(r) => r.url.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_migrate_v1_record() {
+        let data = serde_json::to_value(quick_result(75.0)).unwrap_or_default();
+        let v1 = serde_json::json!({ "mode": "quick", "data": data });
+
+        let record = migrate(v1);
+        assert!(record.is_ok());
+        let Ok(record) = record else { return };
+        assert_eq!(record.schema_version, SCHEMA_VERSION);
+        assert!(matches!(
+            record.result,
+            StoredResult::Quick(result) if (result.score - 75.0).abs() < f64::EPSILON
+        ));
+    }
+
+    #[test]
+    fn test_full_record_with_older_analytics() {
+        // Only the analytics that existed when the history was introduced
+        let mut analytics =
+            serde_json::to_value(RequestAnalytics::compute(&[])).unwrap_or_default();
+        if let Some(fields) = analytics.as_object_mut() {
+            fields.retain(|name, _| {
+                [
+                    "domainStats",
+                    "protocolStats",
+                    "cacheStats",
+                    "duplicateStats",
+                    "compressionStats",
+                    "priorityStats",
+                    "connectionStats",
+                ]
+                .contains(&name.as_str())
+            });
+        }
+        // A domain entry from before `parts` was added
+        analytics["domainStats"]["domains"] = serde_json::json!([{
+            "domain": "cdn.example.com", "requestCount": 3, "totalTransferSize": 3000,
+            "percentage": 100.0, "color": "#3b82f6"
+        }]);
+        let record = serde_json::json!({
+            "schemaVersion": 2,
+            "mode": "full",
+            "data": {
+                "url": "https://example.com",
+                "timestamp": "2025-01-01T00:00:00Z",
+                "ecoindex": {
+                    "score": 75.0, "grade": "B", "ghg": 1.5, "water": 2.25,
+                    "domElements": 500, "requests": 50, "sizeKb": 1000.0
+                },
+                "performance": {
+                    "performanceScore": 90, "firstContentfulPaint": 1000.0,
+                    "largestContentfulPaint": 2000.0, "totalBlockingTime": 100.0,
+                    "cumulativeLayoutShift": 0.05, "speedIndex": 1500.0,
+                    "timeToInteractive": 2500.0
+                },
+                "accessibility": { "accessibilityScore": 95, "issues": [] },
+                "bestPractices": { "bestPracticesScore": 100 },
+                "seo": { "seoScore": 100 },
+                "analytics": analytics
+            }
+        });
+
+        let record = migrate(record);
+        assert!(record.is_ok(), "{record:?}");
+        let Ok(HistoryRecord {
+            result: StoredResult::Full(result),
+            ..
+        }) = record
+        else {
+            return;
+        };
+        assert!(result.analytics.is_some());
+        let Some(analytics) = result.analytics else {
+            return;
+        };
+        assert_eq!(analytics.cache_busting.checked_count, 0);
+        assert_eq!(analytics.domain_stats.domains.len(), 1);
+        let parts = &analytics.domain_stats.domains[0].parts;
+        assert_eq!(parts.subdomain, "cdn.");
+        assert_eq!(parts.registrable, "example.com");
+        assert!(analytics.protocol_downgrade.origins.is_empty());
+        assert!(analytics.start_time_histogram.buckets.is_empty());
+    }
+
+    #[test]
+    fn test_newer_and_invalid_records_rejected() {
+        let data = serde_json::to_value(quick_result(75.0)).unwrap_or_default();
+        let future = serde_json::json!({ "schemaVersion": 99, "mode": "quick", "data": data });
+        assert!(migrate(future).is_err());
+
+        let unknown_mode = serde_json::json!({ "mode": "turbo", "data": {} });
+        assert!(migrate(unknown_mode).is_err());
+        assert!(migrate(Value::from(42)).is_err());
+    }
+
+    #[test]
+    fn test_load_skips_bad_records_and_keeps_others() {
+        let path = temp_file("mixed.json");
+        let data = serde_json::to_value(quick_result(60.0)).unwrap_or_default();
+        let content = serde_json::json!([
+            { "mode": "quick", "data": data },
+            { "mode": "quick", "data": { "score": "broken" } },
+            { "schemaVersion": 2, "mode": "quick", "data": data },
+        ]);
+        let _ = std::fs::create_dir_all(path.parent().unwrap_or_else(|| Path::new(".")));
+        let _ = std::fs::write(&path, content.to_string());
+
+        let records = load_history(&path).unwrap_or_default();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|r| r.schema_version == SCHEMA_VERSION));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let path = temp_file("roundtrip.json");
+        let records = vec![
+            HistoryRecord::new(StoredResult::Quick(Box::new(quick_result(80.0)))),
+            HistoryRecord::new(StoredResult::Quick(Box::new(quick_result(40.0)))),
+        ];
+        assert!(save_history(&path, &records).is_ok());
+
+        let loaded = load_history(&path).unwrap_or_default();
+        assert_eq!(loaded.len(), 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_migrated_id_matches_new_record_id() {
+        let result = quick_result(75.0);
+        let expected = HistoryRecord::new(StoredResult::Quick(Box::new(result.clone()))).id;
+        let data = serde_json::to_value(result).unwrap_or_default();
+        let v2 = serde_json::json!({ "schemaVersion": 2, "mode": "quick", "data": data });
+
+        let record = migrate(v2);
+        assert!(record.is_ok_and(|r| r.id == expected && r.schema_version == 3));
+    }
+
+    #[test]
+    fn test_delete_middle_entry_keeps_others_in_order() {
+        let path = temp_file("delete.json");
+        let records = vec![
+            quick_record("https://a.example", "2025-01-01T00:00:00Z"),
+            quick_record("https://b.example", "2025-01-02T00:00:00Z"),
+            quick_record("https://c.example", "2025-01-03T00:00:00Z"),
+        ];
+        assert!(save_history(&path, &records).is_ok());
+        let middle = records[1].id.clone();
+
+        let found = get_history_entry(&path, &middle).unwrap_or_default();
+        assert_eq!(
+            urls(&found.into_iter().collect::<Vec<_>>()),
+            vec!["https://b.example"]
+        );
+
+        assert!(delete_history_entry(&path, &middle).unwrap_or_default());
+        let left = load_history(&path).unwrap_or_default();
+        assert_eq!(urls(&left), vec!["https://a.example", "https://c.example"]);
+        assert_eq!(left[0].id, records[0].id);
+        assert_eq!(left[1].id, records[2].id);
+
+        // Already gone
+        assert!(!delete_history_entry(&path, &middle).unwrap_or(true));
+        assert!(get_history_entry(&path, &middle).is_ok_and(|r| r.is_none()));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_delete_preserves_unreadable_records() {
+        let path = temp_file("delete-unreadable.json");
+        let kept = quick_record("https://a.example", "2025-01-01T00:00:00Z");
+        let removed = quick_record("https://b.example", "2025-01-02T00:00:00Z");
+        let future = serde_json::json!({ "schemaVersion": 99, "mode": "quick", "data": {} });
+        let content = serde_json::json!([
+            serde_json::to_value(&kept).unwrap_or_default(),
+            future,
+            serde_json::to_value(&removed).unwrap_or_default(),
+        ]);
+        let _ = std::fs::create_dir_all(path.parent().unwrap_or_else(|| Path::new(".")));
+        let _ = std::fs::write(&path, content.to_string());
+
+        assert!(delete_history_entry(&path, &removed.id).unwrap_or_default());
+        let raw: Value = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default();
+        assert_eq!(raw.as_array().map(Vec::len), Some(2));
+        assert_eq!(raw.pointer("/1/schemaVersion"), Some(&Value::from(99)));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_missing_file_is_empty_history() {
+        let loaded = load_history(&temp_file("missing.json"));
+        assert!(loaded.is_ok_and(|records| records.is_empty()));
+    }
+}
@@ -0,0 +1,13 @@
+//! Persistence of analysis results.
+
+mod cache;
+mod history;
+
+pub use cache::{
+    clear_cache, enforce_cache_limits, CacheLimits, EvictionReport, DEFAULT_MAX_CACHE_AGE,
+    DEFAULT_MAX_CACHE_BYTES,
+};
+pub use history::{
+    delete_history_entry, get_history_entry, load_history, save_history, HistoryRecord,
+    StoredResult, SCHEMA_VERSION,
+};
@@ -0,0 +1,262 @@
+//! Environment self-check.
+//!
+//! Verifies that the bundled binaries and data directories needed for an
+//! analysis are available, and reports each check with its resolved path.
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::get_target_triple;
+
+/// Build profile of the running binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildProfile {
+    /// Debug build (`debug_assertions` enabled).
+    Debug,
+    /// Optimized release build.
+    Release,
+}
+
+/// Application identity, included in bug reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppInfo {
+    /// Package name.
+    pub name: String,
+    /// Package version.
+    pub version: String,
+    /// Target triple the binary was built for (also used for sidecar names).
+    pub target_triple: String,
+    /// Build profile.
+    pub build_profile: BuildProfile,
+}
+
+impl AppInfo {
+    /// Information about the running binary.
+    #[must_use]
+    pub fn current() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            target_triple: get_target_triple().to_string(),
+            build_profile: if cfg!(debug_assertions) {
+                BuildProfile::Debug
+            } else {
+                BuildProfile::Release
+            },
+        }
+    }
+}
+
+/// Environment component being checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckKind {
+    /// Chrome Headless Shell (or system Chrome) executable.
+    Chrome,
+    /// Lighthouse sidecar script.
+    LighthouseScript,
+    /// Bundled Node.js sidecar binary.
+    NodeSidecar,
+    /// Application data directories.
+    DataDirectories,
+}
+
+/// Outcome of a single environment check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckResult {
+    /// Component checked.
+    pub kind: CheckKind,
+    /// Whether the check passed.
+    pub passed: bool,
+    /// Resolved path (if it could be resolved).
+    pub path: Option<String>,
+    /// Failure reason (empty when the check passed).
+    pub message: String,
+}
+
+impl CheckResult {
+    /// Build a check from a path resolution and a validation of the resolved path.
+    pub fn from_resolution<E: Display>(
+        kind: CheckKind,
+        resolved: Result<PathBuf, E>,
+        validate: impl Fn(&Path) -> Result<(), String>,
+    ) -> Self {
+        match resolved {
+            Ok(path) => {
+                let validation = validate(&path);
+                Self {
+                    kind,
+                    passed: validation.is_ok(),
+                    path: Some(path.display().to_string()),
+                    message: validation.err().unwrap_or_default(),
+                }
+            },
+            Err(e) => Self {
+                kind,
+                passed: false,
+                path: None,
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+/// Result of all environment checks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfCheckReport {
+    /// Individual checks, in execution order.
+    pub checks: Vec<CheckResult>,
+    /// Whether every check passed.
+    pub passed: bool,
+}
+
+impl SelfCheckReport {
+    /// Assemble the report from individual checks.
+    #[must_use]
+    pub fn from_checks(checks: Vec<CheckResult>) -> Self {
+        let passed = checks.iter().all(|c| c.passed);
+        for check in checks.iter().filter(|c| !c.passed) {
+            log::warn!("Self-check failed for {:?}: {}", check.kind, check.message);
+        }
+        Self { checks, passed }
+    }
+}
+
+/// Check that `path` is an existing file.
+pub fn check_file(path: &Path) -> Result<(), String> {
+    if path.is_file() {
+        Ok(())
+    } else {
+        Err(format!("{} does not exist", path.display()))
+    }
+}
+
+/// Check that `path` is an existing, executable file.
+pub fn check_executable(path: &Path) -> Result<(), String> {
+    check_file(path)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(path)
+            .map_err(|e| format!("{}: {e}", path.display()))?
+            .permissions()
+            .mode();
+        if mode & 0o111 == 0 {
+            return Err(format!("{} is not executable", path.display()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that `dir` exists (creating it if needed) and accepts new files.
+pub fn check_writable_dir(dir: &Path) -> Result<(), String> {
+    super::ensure_writable_dir(dir).map_err(|e| e.to_string())
+}
+
+/// Resolve the Node.js sidecar binary (placed next to the executable by Tauri).
+pub fn resolve_node_sidecar_path() -> Result<PathBuf, String> {
+    let exe_path = std::env::current_exe().map_err(|e| e.to_string())?;
+    let exe_dir = exe_path
+        .parent()
+        .ok_or_else(|| "Executable has no parent directory".to_string())?;
+    let name = if cfg!(windows) { "node.exe" } else { "node" };
+    let path = exe_dir.join(name);
+    if path.exists() {
+        Ok(path)
+    } else {
+        Err(format!("Node sidecar not found at {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(kind: CheckKind, passed: bool) -> CheckResult {
+        CheckResult {
+            kind,
+            passed,
+            path: None,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_app_info_current() {
+        let info = AppInfo::current();
+        assert_eq!(info.name, "ecoindex-app");
+        assert_eq!(info.version.split('.').count(), 3);
+        assert!(!info.target_triple.is_empty());
+        #[cfg(debug_assertions)]
+        assert_eq!(info.build_profile, BuildProfile::Debug);
+
+        let json = serde_json::to_value(&info).unwrap_or_default();
+        assert!(json["targetTriple"].is_string());
+        assert!(json["buildProfile"].is_string());
+    }
+
+    #[test]
+    fn test_report_passes_when_all_checks_pass() {
+        let report = SelfCheckReport::from_checks(vec![
+            check(CheckKind::Chrome, true),
+            check(CheckKind::NodeSidecar, true),
+        ]);
+        assert!(report.passed);
+        assert_eq!(report.checks.len(), 2);
+    }
+
+    #[test]
+    fn test_report_fails_on_any_failure() {
+        let report = SelfCheckReport::from_checks(vec![
+            check(CheckKind::Chrome, true),
+            check(CheckKind::LighthouseScript, false),
+            check(CheckKind::DataDirectories, true),
+        ]);
+        assert!(!report.passed);
+        assert_eq!(report.checks[1].kind, CheckKind::LighthouseScript);
+    }
+
+    #[test]
+    fn test_from_resolution() {
+        let resolved: Result<PathBuf, String> = Ok(PathBuf::from("/bin/chrome"));
+        let ok = CheckResult::from_resolution(CheckKind::Chrome, resolved, |_| Ok(()));
+        assert!(ok.passed);
+        assert_eq!(ok.path.as_deref(), Some("/bin/chrome"));
+        assert!(ok.message.is_empty());
+
+        let resolved: Result<PathBuf, String> = Ok(PathBuf::from("/bin/chrome"));
+        let invalid = CheckResult::from_resolution(CheckKind::Chrome, resolved, |_| {
+            Err("not executable".to_string())
+        });
+        assert!(!invalid.passed);
+        assert!(invalid.path.is_some());
+        assert_eq!(invalid.message, "not executable");
+
+        let unresolved: Result<PathBuf, String> = Err("Chrome not found".to_string());
+        let missing = CheckResult::from_resolution(CheckKind::Chrome, unresolved, check_file);
+        assert!(!missing.passed);
+        assert!(missing.path.is_none());
+        assert_eq!(missing.message, "Chrome not found");
+    }
+
+    #[test]
+    fn test_check_file_and_writable_dir() {
+        let dir = std::env::temp_dir().join(format!("ecoindex-self-check-{}", std::process::id()));
+        assert!(check_writable_dir(&dir).is_ok());
+        assert!(check_file(&dir.join("missing")).is_err());
+
+        let file = dir.join("present");
+        let _ = std::fs::write(&file, b"x");
+        assert!(check_file(&file).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
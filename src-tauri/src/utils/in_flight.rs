@@ -0,0 +1,172 @@
+//! Coalescing of identical concurrent operations.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, PoisonError};
+
+use futures::future::{BoxFuture, FutureExt, Shared};
+
+type SharedTask<T> = Shared<BoxFuture<'static, T>>;
+
+/// Operations in progress, keyed by what they compute.
+///
+/// A caller asking for a key that is already being computed awaits the running
+/// operation and gets a copy of its output instead of starting its own. The key
+/// is forgotten as soon as the operation finishes or a caller gives up on it:
+/// later callers start afresh.
+pub struct InFlight<T> {
+    pending: Mutex<HashMap<String, SharedTask<T>>>,
+}
+
+impl<T> Default for InFlight<T> {
+    fn default() -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> InFlight<T> {
+    /// Run the operation built by `start` for `key`, or join the one already running.
+    ///
+    /// `start` is only called when no operation is in flight for `key`.
+    pub async fn run<F, Fut>(&self, key: String, start: F) -> T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let task = {
+            let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+            match pending.entry(key.clone()) {
+                Entry::Occupied(entry) => {
+                    // Keys may embed credentials: never log them
+                    log::info!("Joining an identical operation already in progress");
+                    entry.get().clone()
+                },
+                Entry::Vacant(entry) => entry.insert(start().boxed().shared()).clone(),
+            }
+        };
+
+        // Removes the entry once this caller is done, even if it is dropped
+        // before the operation completes
+        let entry = PendingEntry {
+            pending: &self.pending,
+            key,
+            task,
+        };
+        entry.task.clone().await
+    }
+
+    /// Number of operations in progress.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Whether no operation is in progress.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Entry of [`InFlight`] awaited by a caller, forgotten when the caller is done.
+struct PendingEntry<'a, T> {
+    pending: &'a Mutex<HashMap<String, SharedTask<T>>>,
+    key: String,
+    task: SharedTask<T>,
+}
+
+impl<T> Drop for PendingEntry<'_, T> {
+    fn drop(&mut self) {
+        // The first caller to finish or give up removes the entry, unless a new
+        // operation already replaced it; callers still waiting keep driving
+        // their copy of the operation
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        if pending
+            .get(&self.key)
+            .is_some_and(|current| current.ptr_eq(&self.task))
+        {
+            pending.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Operation counting its executions and returning `value` after a short delay.
+    fn counted(runs: &Arc<AtomicU32>, value: u32) -> impl FnOnce() -> BoxFuture<'static, u32> {
+        let runs = Arc::clone(runs);
+        move || {
+            async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                value
+            }
+            .boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_identical_concurrent_calls_run_once() {
+        let in_flight = InFlight::default();
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let (first, second) = tokio::join!(
+            in_flight.run("https://example.com/".to_string(), counted(&runs, 1)),
+            in_flight.run("https://example.com/".to_string(), counted(&runs, 2)),
+        );
+
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!((first, second), (1, 1));
+        assert!(in_flight.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_different_keys_and_later_calls_run_separately() {
+        let in_flight = InFlight::default();
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let (a, b) = tokio::join!(
+            in_flight.run("a".to_string(), counted(&runs, 1)),
+            in_flight.run("b".to_string(), counted(&runs, 2)),
+        );
+        assert_eq!((a, b), (1, 2));
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        // Finished operations are not cached
+        let again = in_flight.run("a".to_string(), counted(&runs, 3)).await;
+        assert_eq!(again, 3);
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_caller_releases_key() {
+        let in_flight = InFlight::default();
+        let runs = Arc::new(AtomicU32::new(0));
+
+        // The only caller gives up before the operation completes
+        let abandoned = tokio::time::timeout(
+            Duration::from_millis(10),
+            in_flight.run("a".to_string(), counted(&runs, 1)),
+        )
+        .await;
+        assert!(abandoned.is_err());
+        assert!(in_flight.is_empty());
+
+        // A later caller starts afresh instead of joining the abandoned operation
+        let again = in_flight.run("a".to_string(), counted(&runs, 2)).await;
+        assert_eq!(again, 2);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert!(in_flight.is_empty());
+    }
+}
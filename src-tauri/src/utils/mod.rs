@@ -1,5 +1,11 @@
 //! Utility functions for the `EcoIndex` Analyzer application.
 
+pub mod diagnostics;
+mod in_flight;
 mod paths;
 
-pub use paths::{resolve_chrome_path, resolve_chrome_path_from_resource_dir, AppPaths};
+pub use in_flight::InFlight;
+pub use paths::{
+    ensure_writable_dir, get_target_triple, resolve_chrome_path,
+    resolve_chrome_path_from_resource_dir, AppPaths,
+};
@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 
 use tauri::Manager;
 
-use crate::errors::BrowserError;
+use crate::errors::{AppError, BrowserError};
 
 /// Application paths for data storage and sidecars.
 #[derive(Debug, Clone)]
@@ -66,16 +66,18 @@ impl AppPaths {
         }
     }
 
-    /// Ensure all directories exist, creating them if necessary.
+    /// Ensure all directories exist and accept new files, creating them if necessary.
+    ///
+    /// Safe to call repeatedly: existing directories are kept as they are.
     ///
     /// # Errors
     ///
-    /// Returns an error if directories cannot be created.
-    pub fn ensure_dirs(&self) -> std::io::Result<()> {
-        std::fs::create_dir_all(&self.data_dir)?;
-        std::fs::create_dir_all(&self.cache_dir)?;
-        std::fs::create_dir_all(&self.logs_dir)?;
-        Ok(())
+    /// Returns [`AppError::UnwritableDirectory`] naming the first directory that
+    /// cannot be created or written to.
+    pub fn ensure_dirs(&self) -> Result<(), AppError> {
+        ensure_writable_dir(&self.data_dir)?;
+        ensure_writable_dir(&self.cache_dir)?;
+        ensure_writable_dir(&self.logs_dir)
     }
 
     /// Get the path for storing analysis history.
@@ -88,11 +90,64 @@ impl AppPaths {
     #[must_use]
     pub fn cache_file_for_url(&self, url: &str) -> PathBuf {
         // Create a simple hash of the URL for the filename
-        let hash = url.bytes().fold(0u64, |acc, b| {
-            acc.wrapping_mul(31).wrapping_add(u64::from(b))
-        });
+        let hash = url_hash(url);
         self.cache_dir.join(format!("{hash:016x}.json"))
     }
+
+    /// Get the monitoring file (JSON lines) for a URL key.
+    ///
+    /// The name keeps a readable prefix of the key plus its hash, so the same
+    /// key always maps to the same file.
+    #[must_use]
+    pub fn monitoring_file(&self, url_key: &str) -> PathBuf {
+        const MAX_PREFIX_LEN: usize = 64;
+
+        let prefix: String = url_key
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .take(MAX_PREFIX_LEN)
+            .collect();
+        let hash = url_hash(url_key);
+        self.data_dir
+            .join("monitoring")
+            .join(format!("{prefix}-{hash:016x}.jsonl"))
+    }
+}
+
+/// Create `dir` if needed and check that it accepts new files.
+///
+/// Writes then deletes a probe file, so the check holds for the actual user
+/// (permission bits alone miss ACLs and read-only mounts).
+///
+/// # Errors
+///
+/// Returns [`AppError::UnwritableDirectory`] if the directory cannot be
+/// created or the probe file cannot be written.
+pub fn ensure_writable_dir(dir: &Path) -> Result<(), AppError> {
+    let unwritable = |e: std::io::Error| AppError::UnwritableDirectory {
+        path: dir.to_path_buf(),
+        reason: e.to_string(),
+    };
+    std::fs::create_dir_all(dir).map_err(unwritable)?;
+    let probe = dir.join(format!(".write-test-{}", std::process::id()));
+    std::fs::write(&probe, b"ok").map_err(unwritable)?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// Simple, stable hash of a URL used for file names.
+fn url_hash(url: &str) -> u64 {
+    url.bytes().fold(0u64, |acc, b| {
+        acc.wrapping_mul(31).wrapping_add(u64::from(b))
+    })
 }
 
 impl Default for AppPaths {
@@ -112,6 +167,8 @@ impl Default for AppPaths {
 /// 1. Resource directory (bundled production mode)
 /// 2. Binaries directory in lib folder (installed deb/rpm)
 /// 3. Binaries directory next to executable (development mode)
+/// 4. `CHROME_PATH` environment variable
+/// 5. System-installed Chrome/Chromium (common per-OS locations)
 ///
 /// # Errors
 ///
@@ -119,31 +176,39 @@ impl Default for AppPaths {
 pub fn resolve_chrome_path(app: &tauri::AppHandle) -> Result<PathBuf, BrowserError> {
     let target_triple = get_target_triple();
     let mut tried_paths: Vec<String> = Vec::new();
+    let mut resource_dir_error: Option<String> = None;
 
     // Try resource directory first (production bundle)
-    if let Ok(resource_dir) = app.path().resource_dir() {
-        log::debug!("Resource dir: {}", resource_dir.display());
-
-        // Try direct chrome-headless-shell directory
-        let chrome_path = resolve_chrome_from_dir(&resource_dir.join("chrome-headless-shell"));
-        tried_paths.push(format!("{}", chrome_path.display()));
-        if chrome_path.exists() {
-            log::info!("Chrome found at: {}", chrome_path.display());
-            return Ok(chrome_path);
-        }
-
-        // Try binaries directory with target triple (deb/rpm structure)
-        // Structure: /usr/lib/EcoIndex Analyzer/binaries/chrome-headless-shell-{target}/
-        let chrome_dir = resource_dir
-            .join("binaries")
-            .join(format!("chrome-headless-shell-{target_triple}"));
-        let chrome_path = resolve_chrome_from_dir(&chrome_dir);
-        tried_paths.push(format!("{}", chrome_path.display()));
-        log::debug!("Trying installed path: {}", chrome_path.display());
-        if chrome_path.exists() {
-            log::info!("Chrome found at: {}", chrome_path.display());
-            return Ok(chrome_path);
-        }
+    match app.path().resource_dir() {
+        Ok(resource_dir) => {
+            log::debug!("Resource dir: {}", resource_dir.display());
+
+            // Try direct chrome-headless-shell directory
+            let chrome_path = resolve_chrome_from_dir(&resource_dir.join("chrome-headless-shell"));
+            tried_paths.push(format!("{}", chrome_path.display()));
+            if chrome_path.exists() {
+                log::info!("Chrome found at: {}", chrome_path.display());
+                return Ok(chrome_path);
+            }
+
+            // Try binaries directory with target triple (deb/rpm structure)
+            // Structure: /usr/lib/EcoIndex Analyzer/binaries/chrome-headless-shell-{target}/
+            let chrome_dir = resource_dir
+                .join("binaries")
+                .join(format!("chrome-headless-shell-{target_triple}"));
+            let chrome_path = resolve_chrome_from_dir(&chrome_dir);
+            tried_paths.push(format!("{}", chrome_path.display()));
+            log::debug!("Trying installed path: {}", chrome_path.display());
+            if chrome_path.exists() {
+                log::info!("Chrome found at: {}", chrome_path.display());
+                return Ok(chrome_path);
+            }
+        },
+        Err(e) => {
+            log::warn!("Resource dir unavailable: {e}");
+            tried_paths.push(format!("<resource dir unavailable: {e}>"));
+            resource_dir_error = Some(e.to_string());
+        },
     }
 
     // Try binaries directory next to executable (development)
@@ -152,10 +217,123 @@ pub fn resolve_chrome_path(app: &tauri::AppHandle) -> Result<PathBuf, BrowserErr
         return Ok(chrome_path);
     }
 
+    // Fallback: CHROME_PATH, then system-installed browsers
+    let candidates = system_chrome_candidates(std::env::consts::OS, |key| std::env::var(key).ok());
+    for candidate in candidates {
+        tried_paths.push(format!("{}", candidate.path.display()));
+        if candidate.path.is_file() {
+            log::info!(
+                "Chrome found via {}: {}",
+                candidate.source,
+                candidate.path.display()
+            );
+            return Ok(candidate.path);
+        }
+    }
+
     log::error!("Chrome NOT FOUND. Tried paths: {tried_paths:?}");
-    Err(BrowserError::NotFound(format!(
-        "Chrome Headless Shell not found. Tried: {tried_paths:?}. Run 'pnpm download:chrome' first."
-    )))
+    Err(chrome_not_found_error(
+        &tried_paths,
+        resource_dir_error.as_deref(),
+    ))
+}
+
+/// Build the "Chrome not found" error, including why the resource directory
+/// could not be used (if it failed to resolve).
+fn chrome_not_found_error(
+    tried_paths: &[String],
+    resource_dir_error: Option<&str>,
+) -> BrowserError {
+    let reason = resource_dir_error
+        .map(|e| format!(" Resource directory unavailable: {e}."))
+        .unwrap_or_default();
+    BrowserError::NotFound(format!(
+        "Chrome Headless Shell not found. Tried: {tried_paths:?}.{reason} Run 'pnpm download:chrome' first or set CHROME_PATH."
+    ))
+}
+
+/// Environment variable pointing to a Chrome executable.
+const CHROME_PATH_ENV: &str = "CHROME_PATH";
+
+/// Chrome executable candidate outside the app bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChromeCandidate {
+    /// Candidate executable path.
+    path: PathBuf,
+    /// Where the candidate comes from (for logging).
+    source: &'static str,
+}
+
+/// Build the fallback Chrome candidates for `os` (`std::env::consts::OS`).
+///
+/// `CHROME_PATH` comes first, followed by the usual install locations of
+/// Chrome and Chromium. `env` resolves environment variables.
+fn system_chrome_candidates(
+    os: &str,
+    env: impl Fn(&str) -> Option<String>,
+) -> Vec<ChromeCandidate> {
+    let mut candidates = Vec::new();
+
+    if let Some(path) = env(CHROME_PATH_ENV).filter(|p| !p.trim().is_empty()) {
+        candidates.push(ChromeCandidate {
+            path: PathBuf::from(path.trim()),
+            source: "CHROME_PATH",
+        });
+    }
+
+    let system_paths: Vec<PathBuf> = match os {
+        "linux" => [
+            "/usr/bin/google-chrome-stable",
+            "/usr/bin/google-chrome",
+            "/opt/google/chrome/chrome",
+            "/usr/bin/chromium",
+            "/usr/bin/chromium-browser",
+            "/snap/bin/chromium",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect(),
+        "macos" => {
+            let mut paths = vec![
+                PathBuf::from("/Applications/Google Chrome.app/Contents/MacOS/Google Chrome"),
+                PathBuf::from("/Applications/Chromium.app/Contents/MacOS/Chromium"),
+            ];
+            if let Some(home) = env("HOME") {
+                paths.push(
+                    PathBuf::from(home)
+                        .join("Applications/Google Chrome.app/Contents/MacOS/Google Chrome"),
+                );
+            }
+            paths
+        },
+        "windows" => {
+            let program_files =
+                env("ProgramFiles").unwrap_or_else(|| r"C:\Program Files".to_string());
+            let program_files_x86 =
+                env("ProgramFiles(x86)").unwrap_or_else(|| r"C:\Program Files (x86)".to_string());
+            let mut roots = vec![program_files, program_files_x86];
+            if let Some(local_app_data) = env("LOCALAPPDATA") {
+                roots.push(local_app_data);
+            }
+            roots
+                .into_iter()
+                .map(|root| {
+                    PathBuf::from(root)
+                        .join("Google")
+                        .join("Chrome")
+                        .join("Application")
+                        .join("chrome.exe")
+                })
+                .collect()
+        },
+        _ => Vec::new(),
+    };
+
+    candidates.extend(system_paths.into_iter().map(|path| ChromeCandidate {
+        path,
+        source: "system install",
+    }));
+    candidates
 }
 
 /// Resolve Chrome Headless Shell path from a directory.
@@ -206,22 +384,25 @@ fn resolve_chrome_from_dev_binaries() -> Option<PathBuf> {
 
 /// Get the current target triple.
 #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
-const fn get_target_triple() -> &'static str {
+pub const fn get_target_triple() -> &'static str {
     "x86_64-pc-windows-msvc"
 }
 
+/// Get the current target triple.
 #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
-const fn get_target_triple() -> &'static str {
+pub const fn get_target_triple() -> &'static str {
     "x86_64-unknown-linux-gnu"
 }
 
+/// Get the current target triple.
 #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
-const fn get_target_triple() -> &'static str {
+pub const fn get_target_triple() -> &'static str {
     "x86_64-apple-darwin"
 }
 
+/// Get the current target triple.
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
-const fn get_target_triple() -> &'static str {
+pub const fn get_target_triple() -> &'static str {
     "aarch64-apple-darwin"
 }
 
@@ -232,7 +413,7 @@ const fn get_target_triple() -> &'static str {
     all(target_os = "macos", target_arch = "x86_64"),
     all(target_os = "macos", target_arch = "aarch64"),
 )))]
-const fn get_target_triple() -> &'static str {
+pub const fn get_target_triple() -> &'static str {
     "unknown"
 }
 
@@ -259,6 +440,136 @@ mod tests {
         assert!(cache_file.extension().is_some_and(|ext| ext == "json"));
     }
 
+    #[test]
+    fn test_monitoring_file_is_stable_per_key() {
+        let paths = AppPaths::default();
+        let file = paths.monitoring_file("https://example.com/a?b=1");
+        assert_eq!(file, paths.monitoring_file("https://example.com/a?b=1"));
+        assert_ne!(file, paths.monitoring_file("https://example.com/a?b=2"));
+        assert!(file.extension().is_some_and(|ext| ext == "jsonl"));
+        let name = file
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        assert!(name.starts_with("example.com_a_b_1-"));
+    }
+
+    #[test]
+    fn test_chrome_not_found_error_includes_resource_dir_reason() {
+        let tried = vec!["<resource dir unavailable: unknown path>".to_string()];
+        let message = chrome_not_found_error(&tried, Some("unknown path")).to_string();
+        assert!(message.contains("Resource directory unavailable: unknown path"));
+
+        let message = chrome_not_found_error(&[], None).to_string();
+        assert!(!message.contains("Resource directory unavailable"));
+    }
+
+    #[test]
+    fn test_chrome_path_env_comes_first() {
+        let env = |key: &str| (key == "CHROME_PATH").then(|| "/custom/chrome".to_string());
+        let candidates = system_chrome_candidates("linux", env);
+        assert_eq!(candidates[0].path, PathBuf::from("/custom/chrome"));
+        assert_eq!(candidates[0].source, "CHROME_PATH");
+        assert!(candidates.len() > 1);
+
+        let candidates = system_chrome_candidates("linux", |_| Some("  ".to_string()));
+        assert!(candidates.iter().all(|c| c.source == "system install"));
+    }
+
+    #[test]
+    fn test_system_chrome_candidates_per_os() {
+        let linux = system_chrome_candidates("linux", |_| None);
+        assert!(linux
+            .iter()
+            .any(|c| c.path == Path::new("/usr/bin/google-chrome")));
+        assert!(linux
+            .iter()
+            .any(|c| c.path == Path::new("/usr/bin/chromium")));
+
+        let macos = system_chrome_candidates("macos", |key| {
+            (key == "HOME").then(|| "/Users/me".to_string())
+        });
+        assert_eq!(macos.len(), 3);
+        assert!(macos[0]
+            .path
+            .ends_with("Google Chrome.app/Contents/MacOS/Google Chrome"));
+        assert!(macos[2].path.starts_with("/Users/me/Applications"));
+
+        let windows = system_chrome_candidates("windows", |key| {
+            (key == "LOCALAPPDATA").then(|| "C:/Users/me/AppData/Local".to_string())
+        });
+        assert_eq!(windows.len(), 3);
+        assert!(windows
+            .iter()
+            .all(|c| c.path.ends_with("Application/chrome.exe")));
+        assert!(windows[2].path.starts_with("C:/Users/me/AppData/Local"));
+
+        assert!(system_chrome_candidates("freebsd", |_| None).is_empty());
+    }
+
+    fn temp_paths(name: &str) -> AppPaths {
+        let data_dir =
+            std::env::temp_dir().join(format!("ecoindex-paths-{name}-{}", std::process::id()));
+        AppPaths {
+            cache_dir: data_dir.join("cache"),
+            logs_dir: data_dir.join("logs"),
+            config_file: data_dir.join("config.json"),
+            data_dir,
+        }
+    }
+
+    #[test]
+    fn test_ensure_dirs_is_idempotent() {
+        let paths = temp_paths("writable");
+        assert!(paths.ensure_dirs().is_ok());
+        assert!(paths.ensure_dirs().is_ok());
+        assert!(paths.cache_dir.is_dir() && paths.logs_dir.is_dir());
+
+        // Probe files are cleaned up
+        let leftovers = std::fs::read_dir(&paths.logs_dir).map_or(1, Iterator::count);
+        assert_eq!(leftovers, 0);
+        let _ = std::fs::remove_dir_all(&paths.data_dir);
+    }
+
+    #[test]
+    fn test_ensure_dirs_names_the_blocked_directory() {
+        let paths = temp_paths("blocked");
+        let _ = std::fs::create_dir_all(&paths.data_dir);
+        // A file where the logs directory should be
+        let _ = std::fs::write(&paths.logs_dir, b"not a directory");
+
+        let result = paths.ensure_dirs();
+        assert!(matches!(
+            &result,
+            Err(AppError::UnwritableDirectory { path, .. }) if *path == paths.logs_dir
+        ));
+        let _ = std::fs::remove_dir_all(&paths.data_dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ensure_dirs_read_only_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let paths = temp_paths("read-only");
+        let _ = std::fs::create_dir_all(&paths.cache_dir);
+        let _ = std::fs::set_permissions(&paths.cache_dir, std::fs::Permissions::from_mode(0o555));
+
+        // Root ignores permission bits: nothing to test then
+        let privileged = std::fs::write(paths.cache_dir.join("root-check"), b"").is_ok();
+        if !privileged {
+            let result = paths.ensure_dirs();
+            assert!(matches!(
+                &result,
+                Err(AppError::UnwritableDirectory { path, .. }) if *path == paths.cache_dir
+            ));
+            assert!(result.is_err_and(|e| e.to_string().contains("cache")));
+        }
+
+        let _ = std::fs::set_permissions(&paths.cache_dir, std::fs::Permissions::from_mode(0o755));
+        let _ = std::fs::remove_dir_all(&paths.data_dir);
+    }
+
     #[test]
     fn test_get_target_triple() {
         let triple = get_target_triple();
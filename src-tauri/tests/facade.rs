@@ -0,0 +1,73 @@
+//! End-to-end tests of the Tauri-free library API.
+
+use std::collections::HashMap;
+
+use ecoindex_app_lib::analytics::AnalyticsOptions;
+use ecoindex_app_lib::domain::{AnalysisOptions, MockResponse};
+use ecoindex_app_lib::facade::{analyze_url, compute_analytics};
+use ecoindex_app_lib::sidecar::RequestDetail;
+
+fn request(url: &str, size: u64) -> RequestDetail {
+    RequestDetail {
+        url: url.to_string(),
+        domain: url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default(),
+        protocol: "h2".to_string(),
+        status_code: 200,
+        mime_type: "application/javascript".to_string(),
+        resource_type: "Script".to_string(),
+        transfer_size: size,
+        resource_size: size,
+        priority: "High".to_string(),
+        end_time: 100.0,
+        duration: 100.0,
+        ..RequestDetail::default()
+    }
+}
+
+#[test]
+fn compute_analytics_without_app_handle() {
+    let requests = vec![
+        request("https://cdn1.example/app.js", 5000),
+        request("https://cdn2.example/app.js", 5000),
+    ];
+    let analytics = compute_analytics(&requests, AnalyticsOptions::default());
+
+    assert_eq!(analytics.domain_stats.total_requests, 2);
+    assert_eq!(analytics.duplicate_stats.duplicate_count, 1);
+}
+
+/// Analyzes a fully mocked page with a real Chrome (`CHROME_PATH`).
+#[tokio::test]
+#[cfg_attr(
+    not(feature = "integration-tests"),
+    ignore = "requires Chrome: set CHROME_PATH and enable integration-tests"
+)]
+async fn analyze_url_against_mock() {
+    let Some(chrome_path) = std::env::var_os("CHROME_PATH") else {
+        return;
+    };
+    let options = AnalysisOptions {
+        mock_responses: HashMap::from([(
+            "https://mock.test/".to_string(),
+            MockResponse {
+                headers: HashMap::from([("content-type".to_string(), "text/html".to_string())]),
+                body: "<html><body><p>facade</p></body></html>".to_string(),
+                ..MockResponse::default()
+            },
+        )]),
+        ..AnalysisOptions::default()
+    };
+
+    let result = analyze_url(chrome_path.as_ref(), "https://mock.test/", &options).await;
+
+    assert!(result.is_ok(), "analysis failed: {:?}", result.err());
+    let Ok(result) = result else {
+        return;
+    };
+    assert_eq!(result.url, "https://mock.test/");
+    assert_eq!(result.metrics.requests, 1);
+    assert!((0.0..=100.0).contains(&result.score));
+}
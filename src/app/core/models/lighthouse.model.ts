@@ -15,16 +15,16 @@ export interface LighthouseEcoIndex {
 }
 
 /**
- * Métriques Performance Lighthouse
+ * Métriques Performance Lighthouse (null quand Lighthouse n'a pas pu les mesurer)
  */
 export interface PerformanceMetrics {
-  performanceScore: number;
-  firstContentfulPaint: number;
-  largestContentfulPaint: number;
-  totalBlockingTime: number;
-  cumulativeLayoutShift: number;
-  speedIndex: number;
-  timeToInteractive: number;
+  performanceScore: number | null;
+  firstContentfulPaint: number | null;
+  largestContentfulPaint: number | null;
+  totalBlockingTime: number | null;
+  cumulativeLayoutShift: number | null;
+  speedIndex: number | null;
+  timeToInteractive: number | null;
 }
 
 /**
@@ -40,7 +40,7 @@ export interface AccessibilityIssue {
  * Métriques Accessibility
  */
 export interface AccessibilityMetrics {
-  accessibilityScore: number;
+  accessibilityScore: number | null;
   issues: AccessibilityIssue[];
 }
 
@@ -48,14 +48,14 @@ export interface AccessibilityMetrics {
  * Métriques Best Practices
  */
 export interface BestPracticesMetrics {
-  bestPracticesScore: number;
+  bestPracticesScore: number | null;
 }
 
 /**
  * Métriques SEO
  */
 export interface SeoMetrics {
-  seoScore: number;
+  seoScore: number | null;
 }
 
 /**
@@ -150,10 +150,16 @@ export interface ProtocolAnalytics {
   totalRequests: number;
 }
 
+/**
+ * Cache TTL bucket, from no cache to well cached
+ */
+export type CacheBucket = 'noCache' | 'hour' | 'day' | 'week' | 'longLived';
+
 /**
  * Cache TTL group
  */
 export interface CacheGroup {
+  bucket: CacheBucket;
   label: string;
   count: number;
   percentage: number;
@@ -205,6 +211,15 @@ export interface DuplicateAnalytics {
   duplicateCount: number;
 }
 
+/**
+ * Data-quality problem found while computing the analytics
+ */
+export type AnalyticsWarning =
+  | { kind: 'unparseableUrls'; count: number }
+  | { kind: 'missingDomain'; count: number }
+  | { kind: 'invalidTimings'; count: number }
+  | { kind: 'analyticFailed'; name: string };
+
 /**
  * All pre-computed request analytics
  */
@@ -213,6 +228,8 @@ export interface RequestAnalytics {
   protocolStats: ProtocolAnalytics;
   cacheStats: CacheAnalytics;
   duplicateStats: DuplicateAnalytics;
+  /** Absent when the data was clean */
+  warnings?: AnalyticsWarning[];
 }
 
 // ============================================================================
@@ -315,6 +332,25 @@ export interface ImageFormatAnalytics {
   score: number;
 }
 
+/**
+ * Audit a savings opportunity comes from
+ */
+export type OpportunityKind = 'unusedJs' | 'unusedCss' | 'compression' | 'imageFormat';
+
+/**
+ * Savings opportunity on one resource, whatever audit reported it
+ */
+export interface Opportunity {
+  /** Full URL of the resource */
+  url: string;
+  /** Total bytes of the resource */
+  totalBytes: number;
+  /** Bytes that could be saved */
+  wastedBytes: number;
+  /** Audit that reported the opportunity */
+  kind: OpportunityKind;
+}
+
 // ============================================================================
 // Main Result Types
 // ============================================================================
@@ -346,4 +382,6 @@ export interface LighthouseResult {
   compression?: CompressionAnalytics;
   /** Image format analytics (WebP/AVIF opportunities) */
   imageFormats?: ImageFormatAnalytics;
+  /** Every savings opportunity, largest savings first */
+  opportunities?: Opportunity[];
 }
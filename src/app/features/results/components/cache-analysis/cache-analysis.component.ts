@@ -90,6 +90,7 @@ export class CacheAnalysisComponent {
     const result: CacheGroup[] = [];
     if (groups.none > 0) {
       result.push({
+        bucket: 'noCache',
         label: 'Aucun',
         count: groups.none,
         color: '#ef4444',
@@ -98,6 +99,7 @@ export class CacheAnalysisComponent {
     }
     if (groups.hour > 0) {
       result.push({
+        bucket: 'hour',
         label: '< 1 heure',
         count: groups.hour,
         color: '#f59e0b',
@@ -106,6 +108,7 @@ export class CacheAnalysisComponent {
     }
     if (groups.day > 0) {
       result.push({
+        bucket: 'day',
         label: '< 1 jour',
         count: groups.day,
         color: '#eab308',
@@ -114,6 +117,7 @@ export class CacheAnalysisComponent {
     }
     if (groups.week > 0) {
       result.push({
+        bucket: 'week',
         label: '< 7 jours',
         count: groups.week,
         color: '#84cc16',
@@ -122,6 +126,7 @@ export class CacheAnalysisComponent {
     }
     if (groups.good > 0) {
       result.push({
+        bucket: 'longLived',
         label: '>= 7 jours',
         count: groups.good,
         color: '#10b981',
@@ -36,10 +36,10 @@ import type { EcoIndexGrade } from '../../../../core/models';
         <!-- Performance (right) -->
         <div class="flex flex-col items-center">
           <h2 class="text-lg font-semibold text-gray-700 mb-4">Performance</h2>
-          <app-progress-ring [value]="perfScore()" [size]="160" [strokeWidth]="12">
+          <app-progress-ring [value]="perfScore() ?? 0" [size]="160" [strokeWidth]="12">
             <div class="flex flex-col items-center">
               <span class="text-4xl font-bold text-gray-800">
-                {{ perfScore() }}
+                {{ perfScore() ?? '–' }}
               </span>
               <span class="text-sm text-gray-500">/100</span>
             </div>
@@ -84,6 +84,7 @@ export class HeroSectionComponent {
 
   readonly perfLabel = computed(() => {
     const score = this.perfScore();
+    if (score === null) return 'Indisponible';
     if (score >= 90) return 'Excellent';
     if (score >= 50) return 'Moyen';
     return 'Faible';
@@ -91,6 +92,7 @@ export class HeroSectionComponent {
 
   readonly perfBadgeClass = computed(() => {
     const score = this.perfScore();
+    if (score === null) return 'bg-gray-100 text-gray-800';
     if (score >= 90) return 'bg-green-100 text-green-800';
     if (score >= 50) return 'bg-amber-100 text-amber-800';
     return 'bg-red-100 text-red-800';
@@ -5,7 +5,7 @@ import type { LighthouseResult } from '../../../../core/models';
 
 interface ScoreItem {
   label: string;
-  value: number;
+  value: number | null;
 }
 
 @Component({
@@ -21,8 +21,8 @@ interface ScoreItem {
       <div class="grid grid-cols-4 gap-4 mb-6">
         @for (score of scores(); track score.label) {
           <div class="flex flex-col items-center p-4 bg-gray-50 rounded-xl">
-            <app-progress-ring [value]="score.value" [size]="70" [strokeWidth]="5">
-              <span class="text-lg font-bold text-gray-800">{{ score.value }}</span>
+            <app-progress-ring [value]="score.value ?? 0" [size]="70" [strokeWidth]="5">
+              <span class="text-lg font-bold text-gray-800">{{ score.value ?? '–' }}</span>
             </app-progress-ring>
             <span class="mt-2 text-sm text-gray-600 text-center">{{ score.label }}</span>
           </div>
@@ -32,7 +32,13 @@ interface ScoreItem {
       <!-- Key Metrics -->
       <div class="grid grid-cols-2 gap-4 pt-4 border-t border-gray-100">
         <div class="p-4 bg-blue-50 rounded-lg">
-          <div class="text-xl font-bold text-blue-800">{{ fcp() | number: '1.1-1' }}s</div>
+          <div class="text-xl font-bold text-blue-800">
+            @if (fcp() !== null) {
+              {{ fcp() | number: '1.1-1' }}s
+            } @else {
+              –
+            }
+          </div>
           <div class="text-sm text-blue-600">First Contentful Paint</div>
           <div class="text-xs text-blue-500 mt-1">Temps avant le premier contenu visible</div>
         </div>
@@ -55,6 +61,9 @@ export class LighthouseSectionComponent {
     { label: 'SEO', value: this.result().seo.seoScore },
   ]);
 
-  readonly fcp = computed(() => this.result().performance.firstContentfulPaint / 1000);
+  readonly fcp = computed(() => {
+    const fcp = this.result().performance.firstContentfulPaint;
+    return fcp === null ? null : fcp / 1000;
+  });
   readonly ttfb = computed(() => this.result().ttfb?.ttfb ?? 0);
 }
@@ -5,7 +5,11 @@ import { ProtocolStatsComponent } from '../protocol-stats/protocol-stats.compone
 import { CacheAnalysisComponent } from '../cache-analysis/cache-analysis.component';
 import { CacheIssuesComponent } from '../cache-issues/cache-issues.component';
 import { DuplicatesComponent } from '../duplicates/duplicates.component';
-import type { LighthouseResult, ResourceBreakdown } from '../../../../core/models';
+import type {
+  AnalyticsWarning,
+  LighthouseResult,
+  ResourceBreakdown,
+} from '../../../../core/models';
 
 @Component({
   selector: 'app-network-section',
@@ -23,6 +27,18 @@ import type { LighthouseResult, ResourceBreakdown } from '../../../../core/model
     <div class="space-y-6">
       <h2 class="text-xl font-semibold text-gray-800">Analyse réseau</h2>
 
+      <!-- Data-quality warnings -->
+      @if (warnings().length > 0) {
+        <div class="p-4 rounded-lg bg-amber-50 text-amber-800">
+          <p class="font-medium">Analyse partielle</p>
+          <ul class="text-sm mt-1 list-disc list-inside">
+            @for (warning of warnings(); track $index) {
+              <li>{{ warningMessage(warning) }}</li>
+            }
+          </ul>
+        </div>
+      }
+
       <!-- Requests Summary -->
       <div class="bg-white rounded-2xl shadow-lg p-6">
         <h3 class="text-lg font-semibold text-gray-800 mb-4">Résumé des requêtes</h3>
@@ -126,6 +142,7 @@ export class NetworkSectionComponent {
   readonly analytics = computed(() => this.result().analytics);
   readonly requests = computed(() => this.result().requests ?? []);
   readonly compression = computed(() => this.result().compression);
+  readonly warnings = computed(() => this.analytics()?.warnings ?? []);
 
   readonly breakdown = computed<ResourceBreakdown>(() => {
     return (
@@ -199,4 +216,17 @@ export class NetworkSectionComponent {
 
     return parts.join(', ');
   });
+
+  warningMessage(warning: AnalyticsWarning): string {
+    switch (warning.kind) {
+      case 'unparseableUrls':
+        return `${warning.count} requête(s) avec une URL illisible`;
+      case 'missingDomain':
+        return `${warning.count} requête(s) sans domaine`;
+      case 'invalidTimings':
+        return `${warning.count} requête(s) avec des timings invalides`;
+      case 'analyticFailed':
+        return `Statistique « ${warning.name} » non calculée`;
+    }
+  }
 }